@@ -0,0 +1,70 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use wtime::format::{email_date_header_at, email_date_header_into, format_sql_datetime_into};
+use wtime::format::{format_sql_datetime_with_precision, format_ts, format_ts_into};
+use wtime::utc::{format_utc_ts, format_utc_ts_bytes, format_utc_ts_into};
+
+const TS: u64 = 1728933069;
+const NANOS: u64 = 123_456_789;
+
+fn utc_now(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format_utc_ts");
+    group.bench_function("string", |b| b.iter(format_utc_ts));
+    group.bench_function("into", |b| {
+        let mut buf = String::with_capacity(64);
+        b.iter(|| {
+            buf.clear();
+            format_utc_ts_into(&mut buf).unwrap();
+            black_box(buf.len())
+        })
+    });
+    group.bench_function("bytes", |b| b.iter(format_utc_ts_bytes));
+    group.finish();
+}
+
+fn fixed_ts(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format_ts");
+    group.bench_function("string", |b| {
+        b.iter(|| format_ts(black_box(TS), black_box(NANOS)))
+    });
+    group.bench_function("into", |b| {
+        let mut buf = String::with_capacity(64);
+        b.iter(|| {
+            buf.clear();
+            format_ts_into(&mut buf, black_box(TS), black_box(NANOS)).unwrap();
+            black_box(buf.len())
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("email_date_header");
+    group.bench_function("string", |b| {
+        b.iter(|| email_date_header_at(black_box(TS), black_box(120)))
+    });
+    group.bench_function("into", |b| {
+        let mut buf = String::with_capacity(64);
+        b.iter(|| {
+            buf.clear();
+            email_date_header_into(&mut buf, black_box(TS), black_box(120)).unwrap();
+            black_box(buf.len())
+        })
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("format_sql_datetime");
+    group.bench_function("string", |b| {
+        b.iter(|| format_sql_datetime_with_precision(black_box(TS), black_box(NANOS), 6))
+    });
+    group.bench_function("into", |b| {
+        let mut buf = String::with_capacity(64);
+        b.iter(|| {
+            buf.clear();
+            format_sql_datetime_into(&mut buf, black_box(TS), black_box(NANOS), 6).unwrap();
+            black_box(buf.len())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, utc_now, fixed_ts);
+criterion_main!(benches);
@@ -0,0 +1,201 @@
+use super::{
+    error::WTimeError,
+    format::atom_date,
+    logical::{from_timestamp_millis, to_timestamp_millis},
+    parse::parse_rfc3339_parts,
+};
+
+/// Seconds of 10000-01-01T00:00:00Z; relaxed extended JSON only uses ISO strings before it.
+const YEAR_10000: u64 = 253_402_300_800;
+
+/// ### to_bson_datetime(ts: u64, nanos: u64) -> i64
+///
+/// Encodes a UNIX timestamp as a BSON UTC datetime (element type `0x09`).
+///
+/// BSON stores datetimes as signed 64-bit milliseconds since the UNIX epoch. `nanos` is the
+/// sub-second part and is truncated to milliseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::bson::to_bson_datetime;
+///
+/// assert_eq!(to_bson_datetime(1728933069, 123_456_789), 1728933069123);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds since the UNIX epoch as an `i64`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_bson_datetime(ts: u64, nanos: u64) -> i64 {
+    to_timestamp_millis(ts, nanos)
+}
+
+/// ### from_bson_datetime(millis: i64) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes a BSON UTC datetime into a UNIX timestamp and its sub-second part.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::bson::from_bson_datetime;
+///
+/// assert_eq!(from_bson_datetime(1728933069123), Ok((1728933069, 123_000_000)));
+/// assert!(from_bson_datetime(-1).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for datetimes before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_bson_datetime(millis: i64) -> Result<(u64, u64), WTimeError> {
+    from_timestamp_millis(millis)
+}
+
+/// ### to_extended_json(ts: u64, nanos: u64) -> String
+///
+/// Formats a UNIX timestamp as a datetime in MongoDB relaxed extended JSON.
+///
+/// Dates up to the year 9999 are written as `{"$date":"2024-10-14T19:11:09.123Z"}` with
+/// millisecond precision. Later dates fall back to the canonical `$numberLong` form, as the
+/// specification requires.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::bson::to_extended_json;
+///
+/// assert_eq!(
+///     to_extended_json(1728933069, 123_456_789),
+///     r#"{"$date":"2024-10-14T19:11:09.123Z"}"#
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns the extended JSON object as a `String`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_extended_json(ts: u64, nanos: u64) -> String {
+    if ts >= YEAR_10000 {
+        return to_extended_json_canonical(ts, nanos);
+    }
+    // `YYYY-MM-DDTHH:MM:SS` followed by the milliseconds and `Z`
+    let date = atom_date(ts, 0);
+    format!(
+        r#"{{"$date":"{}.{:03}Z"}}"#,
+        &date[..19],
+        nanos % 1_000_000_000 / 1_000_000
+    )
+}
+
+/// ### to_extended_json_canonical(ts: u64, nanos: u64) -> String
+///
+/// Formats a UNIX timestamp as a datetime in MongoDB canonical extended JSON.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::bson::to_extended_json_canonical;
+///
+/// assert_eq!(
+///     to_extended_json_canonical(1728933069, 123_456_789),
+///     r#"{"$date":{"$numberLong":"1728933069123"}}"#
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns the extended JSON object as a `String`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_extended_json_canonical(ts: u64, nanos: u64) -> String {
+    format!(
+        r#"{{"$date":{{"$numberLong":"{}"}}}}"#,
+        to_bson_datetime(ts, nanos)
+    )
+}
+
+/// Splits `"key"` followed by `:` off the front of `s`, skipping whitespace.
+fn strip_key<'a>(s: &'a str, key: &str) -> Option<&'a str> {
+    let s = s.trim_start().strip_prefix('"')?.strip_prefix(key)?;
+    s.strip_prefix('"')?.trim_start().strip_prefix(':')
+}
+
+/// Splits a JSON string without escapes off the front of `s`, skipping whitespace.
+fn strip_string(s: &str) -> Option<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('"')?;
+    let end = s.find('"')?;
+    Some((&s[..end], &s[end + 1..]))
+}
+
+/// ### parse_extended_json(s: &str) -> Result<(u64, u64), WTimeError>
+///
+/// Parses a datetime in MongoDB extended JSON.
+///
+/// All three shapes of the `$date` wrapper are accepted:
+/// - relaxed: `{"$date": "2024-10-14T19:11:09.123Z"}`
+/// - canonical: `{"$date": {"$numberLong": "1728933069123"}}`
+/// - legacy: `{"$date": 1728933069123}`
+///
+/// ### Example
+///
+/// ```
+/// use wtime::bson::parse_extended_json;
+///
+/// let expected = Ok((1728933069, 123_000_000));
+/// assert_eq!(parse_extended_json(r#"{"$date": "2024-10-14T19:11:09.123Z"}"#), expected);
+/// assert_eq!(
+///     parse_extended_json(r#"{"$date": {"$numberLong": "1728933069123"}}"#),
+///     expected
+/// );
+/// assert_eq!(parse_extended_json(r#"{ "$date" : 1728933069123 }"#), expected);
+/// assert!(parse_extended_json(r#"{"date": 1728933069123}"#).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not a `$date` object, or
+/// [`WTimeError::OutOfRange`] for datetimes before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_extended_json(s: &str) -> Result<(u64, u64), WTimeError> {
+    const EXPECTED: &str = r#"an extended JSON `{"$date": ...}` object"#;
+    let invalid = || WTimeError::InvalidFormat(EXPECTED);
+
+    let value = s.trim_start().strip_prefix('{').ok_or_else(invalid)?;
+    let value = strip_key(value, "$date").ok_or_else(invalid)?.trim_start();
+
+    let (datetime, rest) = if value.starts_with('"') {
+        let (text, rest) = strip_string(value).ok_or_else(invalid)?;
+        let (ts, nanos, _) = parse_rfc3339_parts(text)?;
+        ((ts, nanos), rest)
+    } else if let Some(inner) = value.strip_prefix('{') {
+        let inner = strip_key(inner, "$numberLong").ok_or_else(invalid)?;
+        let (number, rest) = strip_string(inner).ok_or_else(invalid)?;
+        let millis = number.parse::<i64>().map_err(|_| invalid())?;
+        let rest = rest.trim_start().strip_prefix('}').ok_or_else(invalid)?;
+        (from_bson_datetime(millis)?, rest)
+    } else {
+        let end = value
+            .find(|c: char| c != '-' && !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let millis = value[..end].parse::<i64>().map_err(|_| invalid())?;
+        (from_bson_datetime(millis)?, &value[end..])
+    };
+
+    match rest.trim().strip_prefix('}') {
+        Some("") => Ok(datetime),
+        _ => Err(invalid()),
+    }
+}
@@ -1,22 +1,55 @@
-use super::utc::{utc_now, utc_ts_sec};
-use std::time::{Duration, UNIX_EPOCH};
+use super::error::WTimeError;
+#[cfg(feature = "std")]
+use super::{
+    config,
+    utc::{utc_now, utc_ts_sec},
+};
+use core::fmt::{self, Write};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::UNIX_EPOCH;
+
+/// The last year handled by the date arithmetic of this module, 100 billion.
+///
+/// Functions such as [`add_days`] and [`add_months`] saturate at December 31 of this year
+/// instead of overflowing, and treat later input dates as that day.
+pub const MAX_YEAR: u64 = 100_000_000_000;
+
+/// The last second of [`MAX_YEAR`] as a UNIX timestamp, 100000000000-12-31T23:59:59Z.
+pub const MAX_TS: u64 = 3_155_695_137_864_403_199;
+
+/// More days than separate 0000-01-01 from the last day of [`MAX_YEAR`].
+const MAX_DAYS: i64 = 2 * 36_524_249_280_837;
+
+/// More months than separate year 0 from the end of [`MAX_YEAR`].
+const MAX_MONTHS: i64 = 2 * (MAX_YEAR as i64 + 1) * 12;
 
 /// ### calc_date(total_seconds: u64) -> (u64, u64, u64)
 ///
 /// Calculates the date (year, month, day) from total seconds since the UNIX epoch.
 ///
 /// This function takes a count of seconds since the UNIX epoch and computes the corresponding
-/// calendar date.
+/// calendar date. It runs in constant time using Howard Hinnant's days-to-civil algorithm,
+/// so converting a date in the year 9999 costs no more than one in 1970.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::calc_date;
+/// use wtime::calc::{calc_date, calc_timestamp};
 ///
 /// let seconds = 1_600_000_000; // Example total seconds since UNIX epoch
 /// let (year, month, day) = calc_date(seconds);
 /// println!("Date: {}-{}-{}", year, month, day);
 /// assert_eq!(calc_date(1728933069), (2024, 10, 14));
+/// assert_eq!(calc_date(0), (1970, 1, 1));
+/// assert_eq!(calc_date(951_782_400), (2000, 2, 29));
+/// assert_eq!(calc_date(253_402_300_799), (9999, 12, 31));
+///
+/// // Every date up to the year 9999 maps back to the midnight that starts it
+/// for ts in (0..253_402_300_800u64).step_by(7_919_993) {
+///     let (year, month, day) = calc_date(ts);
+///     assert_eq!(calc_timestamp(year, month, day, 0, 0, 0), Ok(ts - ts % 86_400));
+/// }
 /// ```
 ///
 /// ### Returns
@@ -25,330 +58,3105 @@ use std::time::{Duration, UNIX_EPOCH};
 ///
 /// <small>End Fun Doc</small>
 pub fn calc_date(total_seconds: u64) -> (u64, u64, u64) {
-    let mut seconds_remaining = total_seconds;
-
-    // Calculate current year
-    let mut year = 1970;
-    while seconds_remaining >= 31_536_000 + if is_leap_year(year) { 86_400 } else { 0 } {
-        seconds_remaining -= 31_536_000 + if is_leap_year(year) { 86_400 } else { 0 };
-        year += 1;
-    }
-
-    // Calculate current month and day
-    let month_days = if is_leap_year(year) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
-
-    let mut month = 0;
-    while month < 12 {
-        let days_in_month = month_days[month];
-        let seconds_in_month = days_in_month * 86_400; // 86,400 seconds in a day
+    // Constant-time conversion from the day count, whatever the year
+    let (year, month, day) = civil_from_days((total_seconds / 86_400) as i64);
+    (year as u64, month, day)
+}
 
-        if seconds_remaining < seconds_in_month {
-            break;
-        }
+/// ### try_calc_date(total_seconds: u64) -> Result<(u64, u64, u64), WTimeError>
+///
+/// Calculates the date (year, month, day) from total seconds since the UNIX epoch, rejecting
+/// timestamps past the year 9999.
+///
+/// This is the checked form of [`calc_date`] for timestamps that come from untrusted input.
+/// Dates after 9999-12-31 have no four-digit year, so the crate's formatters and parsers do
+/// not support them.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::try_calc_date;
+///
+/// assert_eq!(try_calc_date(1728933069), Ok((2024, 10, 14)));
+/// assert!(try_calc_date(u64::MAX).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a tuple containing the year, month, and day.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the date is after 9999-12-31.
+///
+/// <small>End Fun Doc</small>
+pub fn try_calc_date(total_seconds: u64) -> Result<(u64, u64, u64), WTimeError> {
+    // Seconds from 1970-01-01 to 10000-01-01
+    const YEAR_10000: u64 = 253_402_300_800;
 
-        seconds_remaining -= seconds_in_month;
-        month += 1;
+    if total_seconds >= YEAR_10000 {
+        return Err(WTimeError::OutOfRange("timestamp"));
     }
-
-    let day = seconds_remaining / 86_400 + 1; // +1 to convert to 1-based day
-    (year, (month + 1) as u64, day) // +1 for 1-based month
+    Ok(calc_date(total_seconds))
 }
 
-/// ### calc_week(date: (u64, u64, u64)) -> u64
+/// ### calc_time(total_seconds: u64) -> (u64, u64, u64)
 ///
-/// Calculates the week number in the year based on a provided date.
+/// Calculates the time of day (hour, minute, second) from total seconds since the UNIX epoch.
 ///
-/// The function takes a date in the format `(year, month, day)` and returns the week number
-/// according to the ISO 8601 standard (where the first week of the year is the week
-/// containing the first Thursday).
+/// Unlike `get_hour`, `get_minute` and `get_second`, which read the clock, this function
+/// decomposes any stored timestamp. The time is in UTC; add an offset to the timestamp
+/// first for a local wall-clock time.
 ///
 /// ### Example
 ///
-/// ```rust
-/// use wtime::calc::calc_week;
+/// ```
+/// use wtime::calc::calc_time;
 ///
-/// let week_number = calc_week((2024, 10, 14));
-/// println!("Week number: {}", week_number);
+/// assert_eq!(calc_time(1728933069), (19, 11, 9));
+/// assert_eq!(calc_time(0), (0, 0, 0));
+/// assert_eq!(calc_time(86_399), (23, 59, 59));
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the week number as a `u64`.
+/// Returns a tuple containing the hour (0-23), minute (0-59) and second (0-59).
 ///
 /// <small>End Fun Doc</small>
-pub fn calc_week(date: (u64, u64, u64)) -> u64 {
-    let (year, month, day) = date;
-
-    // Calculate the day of the year
-    let month_days = if is_leap_year(year) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31] // Leap year
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31] // Non-leap year
-    };
-
-    // Sum the days in the preceding months and add the current day
-    let day_of_year = month_days.iter().take((month - 1) as usize).sum::<u64>() + day;
+pub fn calc_time(total_seconds: u64) -> (u64, u64, u64) {
+    let secs_of_day = total_seconds % 86_400;
+    (secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60)
+}
 
-    // Calculate the weekday of January 1 of the given year
-    // 0: Saturday, 1: Sunday, ..., 6: Friday (we want 0 for ISO week calculation)
-    let weekday_of_first_jan =
-        (365 * (year - 1970) + (year - 1970) / 4 - (year - 1970) / 100 + (year - 1970) / 400 + 1)
-            % 7;
+/// ### calc_datetime(total_seconds: u64) -> (u64, u64, u64, u64, u64, u64)
+///
+/// Calculates the date and time (year, month, day, hour, minute, second) from total seconds
+/// since the UNIX epoch.
+///
+/// This combines [`calc_date`] and [`calc_time`] and is the inverse of [`calc_timestamp`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_datetime, calc_timestamp};
+///
+/// assert_eq!(calc_datetime(1728933069), (2024, 10, 14, 19, 11, 9));
+///
+/// let (year, month, day, hour, minute, second) = calc_datetime(1728933069);
+/// assert_eq!(
+///     calc_timestamp(year, month, day, hour, minute, second),
+///     Ok(1728933069)
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns a tuple containing the year, month, day, hour, minute and second in UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn calc_datetime(total_seconds: u64) -> (u64, u64, u64, u64, u64, u64) {
+    let (year, month, day) = calc_date(total_seconds);
+    let (hour, minute, second) = calc_time(total_seconds);
+    (year, month, day, hour, minute, second)
+}
 
-    // Adjust for the ISO week number
-    let first_thursday_in_year = if weekday_of_first_jan <= 3 {
-        (1 + (3 - weekday_of_first_jan)) as u64
-    } else {
-        (8 - weekday_of_first_jan) as u64
-    };
+/// ### calc_timestamp(year: u64, month: u64, day: u64, hour: u64, minute: u64, second: u64) -> Result<u64, WTimeError>
+///
+/// Calculates the UNIX timestamp in seconds of a UTC calendar date and time.
+///
+/// This is the inverse of [`calc_date`] combined with the time of day. Every component is
+/// validated, including the number of days in the month for leap and common years, so an
+/// impossible date is reported instead of silently rolling over into the next month.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_date, calc_timestamp};
+///
+/// assert_eq!(calc_timestamp(2024, 10, 14, 19, 11, 9), Ok(1728933069));
+/// assert_eq!(calc_timestamp(1970, 1, 1, 0, 0, 0), Ok(0));
+/// assert_eq!(calc_date(calc_timestamp(2024, 2, 29, 12, 0, 0).unwrap()), (2024, 2, 29));
+///
+/// assert!(calc_timestamp(2023, 2, 29, 0, 0, 0).is_err());
+/// assert!(calc_timestamp(2024, 10, 14, 24, 0, 0).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the number of seconds since the UNIX epoch as a `u64`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] naming the first invalid component: a month outside
+/// 1-12, a day outside the month, an hour above 23, a minute or second above 59, or a year
+/// before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn calc_timestamp(
+    year: u64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+) -> Result<u64, WTimeError> {
+    // Timestamps run out in the year 584,554,051,223; the larger bound only keeps the day
+    // arithmetic below from overflowing
+    if !(1970..=1_000_000_000_000).contains(&year) {
+        return Err(WTimeError::OutOfRange("year"));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    if day == 0 || day > days_in_month(year, month) {
+        return Err(WTimeError::OutOfRange("day"));
+    }
+    if hour > 23 {
+        return Err(WTimeError::OutOfRange("hour"));
+    }
+    if minute > 59 {
+        return Err(WTimeError::OutOfRange("minute"));
+    }
+    if second > 59 {
+        return Err(WTimeError::OutOfRange("second"));
+    }
 
-    // Calculate the week number
-    let week_number = ((day_of_year - first_thursday_in_year + 10) / 7) as u64; // +10 to adjust full weeks starting with Thursday
+    let days = days_from_civil(year as i64, month, day) as u64;
+    days.checked_mul(86_400)
+        .and_then(|secs| secs.checked_add(hour * 3600 + minute * 60 + second))
+        .ok_or(WTimeError::OutOfRange("year"))
+}
 
-    week_number
+/// ### calc_date_signed(total_seconds: i64) -> (i64, u64, u64)
+///
+/// Calculates the date (year, month, day) from signed seconds since the UNIX epoch.
+///
+/// This is [`calc_date`] for timestamps that may be negative, i.e. dates before 1970. The
+/// proleptic Gregorian calendar is used throughout, so years before 1582 follow the same
+/// leap-year rules as today, and year 0 is 1 BC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::calc_date_signed;
+///
+/// assert_eq!(calc_date_signed(1728933069), (2024, 10, 14));
+/// assert_eq!(calc_date_signed(-1), (1969, 12, 31));
+/// assert_eq!(calc_date_signed(-14_182_940), (1969, 7, 20)); // Apollo 11 landing
+/// assert_eq!(calc_date_signed(-62_135_596_800), (1, 1, 1));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a tuple containing the year, month, and day in UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn calc_date_signed(total_seconds: i64) -> (i64, u64, u64) {
+    civil_from_days(total_seconds.div_euclid(86_400))
 }
 
-/// ### duration_since()
+/// ### calc_datetime_signed(total_seconds: i64) -> (i64, u64, u64, u64, u64, u64)
 ///
-/// Returns the duration from the UNIX epoch to the current time.
+/// Calculates the date and time (year, month, day, hour, minute, second) from signed
+/// seconds since the UNIX epoch.
 ///
-/// This function calculates the amount of time that has elapsed since the UNIX epoch
-/// (January 1, 1970) and returns it as a `Duration`. This can be useful for calculations
-/// involving specific time intervals.
+/// This is [`calc_datetime`] for timestamps that may be negative, and the inverse of
+/// [`calc_timestamp_signed`].
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::duration_since;
+/// use wtime::calc::calc_datetime_signed;
 ///
-/// let duration = duration_since();
-/// println!("Seconds since UNIX epoch: {:?}", duration.as_secs());
-/// println!("Milli-seconds since UNIX epoch: {:?}", duration_since().as_millis());
+/// assert_eq!(calc_datetime_signed(-1), (1969, 12, 31, 23, 59, 59));
+/// assert_eq!(calc_datetime_signed(-14_182_940), (1969, 7, 20, 20, 17, 40));
 /// ```
 ///
-/// ### Panics
+/// ### Returns
 ///
-/// This function will panic if the current system time is before the UNIX epoch.
+/// Returns a tuple containing the year, month, day, hour, minute and second in UTC.
 ///
 /// <small>End Fun Doc</small>
-pub fn duration_since() -> Duration {
-    utc_now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards")
+pub fn calc_datetime_signed(total_seconds: i64) -> (i64, u64, u64, u64, u64, u64) {
+    let (year, month, day) = calc_date_signed(total_seconds);
+    let secs_of_day = total_seconds.rem_euclid(86_400) as u64;
+    (
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
 }
 
-/// ### get_day_name(total_seconds: u64) -> &'static str
+/// ### calc_timestamp_signed(year: i64, month: u64, day: u64, hour: u64, minute: u64, second: u64) -> Result<i64, WTimeError>
 ///
-/// Returns the name of the day of the week corresponding to the total seconds since the UNIX epoch.
+/// Calculates the signed UNIX timestamp in seconds of a UTC calendar date and time.
 ///
-/// This function calculates the day name based on the total seconds since the UNIX epoch.
+/// This is [`calc_timestamp`] without the 1970 lower bound: dates before the UNIX epoch give
+/// negative timestamps. Every component is validated in the same way.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_day_name;
-/// use wtime::utc::get_day;
+/// use wtime::calc::{calc_datetime_signed, calc_timestamp_signed};
 ///
-/// let day = get_day();
-/// println!("Current day: {}", day);
+/// assert_eq!(calc_timestamp_signed(1969, 12, 31, 23, 59, 59), Ok(-1));
+/// assert_eq!(calc_timestamp_signed(2024, 10, 14, 19, 11, 9), Ok(1728933069));
 ///
-/// let day_name = get_day_name(1_670_000_000);
-/// println!("Day name: {}", day_name); // Day name: Friday
-/// println!("Day name: {}", get_day_name(day));
+/// let ts = calc_timestamp_signed(1600, 2, 29, 12, 0, 0).unwrap();
+/// assert_eq!(calc_datetime_signed(ts), (1600, 2, 29, 12, 0, 0));
+///
+/// assert!(calc_timestamp_signed(1900, 2, 29, 0, 0, 0).is_err());
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the name of the day as a static string reference.
+/// Returns the number of seconds since the UNIX epoch as an `i64`, negative before 1970.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] naming the first invalid component, or `"year"` if the
+/// timestamp does not fit in an `i64`.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_day_name(total_seconds: u64) -> &'static str {
-    const DAYS: [&str; 7] = [
-        "Thursday",
-        "Friday",
-        "Saturday",
-        "Sunday",
-        "Monday",
-        "Tuesday",
-        "Wednesday",
-    ]; // Starting from 1970-01-01
-    let days_since_epoch = (total_seconds / 86_400) % 7; // Total days since UNIX epoch modulo 7
-    DAYS[days_since_epoch as usize]
+pub fn calc_timestamp_signed(
+    year: i64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+) -> Result<i64, WTimeError> {
+    // `i64` seconds run out around the year 292,277,026,596; the bound only keeps the day
+    // arithmetic below from overflowing
+    if year.unsigned_abs() > 1_000_000_000_000 {
+        return Err(WTimeError::OutOfRange("year"));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    if day == 0 || day > month_length(year, month) {
+        return Err(WTimeError::OutOfRange("day"));
+    }
+    if hour > 23 {
+        return Err(WTimeError::OutOfRange("hour"));
+    }
+    if minute > 59 {
+        return Err(WTimeError::OutOfRange("minute"));
+    }
+    if second > 59 {
+        return Err(WTimeError::OutOfRange("second"));
+    }
+
+    days_from_civil(year, month, day)
+        .checked_mul(86_400)
+        .and_then(|secs| secs.checked_add((hour * 3600 + minute * 60 + second) as i64))
+        .ok_or(WTimeError::OutOfRange("year"))
 }
 
-/// ### get_month_name(month: u64) -> &'static str
+/// ### duration_between(start: (u64, u64, u64, u64, u64, u64), end: (u64, u64, u64, u64, u64, u64)) -> Duration
 ///
-/// Returns the name of the month corresponding to the provided month number.
+/// Calculates the time elapsed between two UTC calendar points.
 ///
-/// This function takes a month number (from 1 to 12) and returns the corresponding month name.
+/// Each point is `(year, month, day, hour, minute, second)`. The result is the absolute
+/// difference, so the order of the arguments does not matter; use
+/// [`duration_between_signed`] to know which point comes first. Years before 1970 are
+/// supported. The components are not validated; check them with [`calc_timestamp`] first
+/// if they come from user input.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_month_name;
-/// use wtime::utc::get_month;
+/// use std::time::Duration;
+/// use wtime::calc::duration_between;
+///
+/// let deployed = (2024, 2, 28, 22, 0, 0);
+/// let restarted = (2024, 3, 1, 1, 30, 0);
+///
+/// // 2024 is a leap year, so February 29 lies in between
+/// assert_eq!(
+///     duration_between(deployed, restarted),
+///     Duration::from_secs(27 * 3600 + 30 * 60)
+/// );
+/// assert_eq!(
+///     duration_between(restarted, deployed),
+///     duration_between(deployed, restarted)
+/// );
+/// ```
 ///
-/// let month = get_month();
-/// println!("Current month: {}", month);
+/// ### Returns
+///
+/// Returns the elapsed time as a `Duration`.
+///
+/// <small>End Fun Doc</small>
+pub fn duration_between(
+    start: (u64, u64, u64, u64, u64, u64),
+    end: (u64, u64, u64, u64, u64, u64),
+) -> Duration {
+    Duration::from_secs(duration_between_signed(start, end).unsigned_abs())
+}
+
+/// ### duration_between_signed(start: (u64, u64, u64, u64, u64, u64), end: (u64, u64, u64, u64, u64, u64)) -> i64
+///
+/// Calculates the number of seconds from one UTC calendar point to another.
+///
+/// Each point is `(year, month, day, hour, minute, second)`. The result is positive when
+/// `end` is after `start` and negative when it is before. Dates after [`MAX_YEAR`] count as
+/// its December 31, and the result saturates at the bounds of `i64`.
+///
+/// ### Example
 ///
-/// let month_name = get_month_name(4);
-/// println!("Month name: {}", month_name); // "April"
-/// println!("Month name: {}", get_month_name(month));
 /// ```
+/// use wtime::calc::{duration_between_signed, MAX_YEAR};
 ///
-/// ### Panics
+/// let launch = (2024, 10, 14, 19, 0, 0);
+/// let now = (2024, 10, 14, 18, 45, 0);
 ///
-/// This function will panic if provided with an invalid month number (not between 1 and 12).
+/// assert_eq!(duration_between_signed(now, launch), 900);
+/// assert_eq!(duration_between_signed(launch, now), -900);
+///
+/// // Works across the UNIX epoch
+/// assert_eq!(duration_between_signed((1969, 12, 31, 0, 0, 0), (1970, 1, 1, 0, 0, 0)), 86_400);
+///
+/// let epoch = (1970, 1, 1, 0, 0, 0);
+/// assert_eq!(
+///     duration_between_signed(epoch, (1_000_000_000_000, 1, 1, 0, 0, 0)),
+///     duration_between_signed(epoch, (MAX_YEAR, 12, 31, 0, 0, 0))
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns `end - start` in seconds as an `i64`.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_month_name(month: u64) -> &'static str {
-    const MONTHS: [&str; 12] = [
-        "January",
-        "February",
-        "March",
-        "April",
-        "May",
-        "June",
-        "July",
-        "August",
-        "September",
-        "October",
-        "November",
-        "December",
-    ];
-    MONTHS[(month - 1) as usize] // -1 to convert from 1-indexed to 0-indexed
+pub fn duration_between_signed(
+    start: (u64, u64, u64, u64, u64, u64),
+    end: (u64, u64, u64, u64, u64, u64),
+) -> i64 {
+    fn seconds((year, month, day, hour, minute, second): (u64, u64, u64, u64, u64, u64)) -> i64 {
+        let (year, month, day) = clamp_date((year, month, day));
+        let time = hour
+            .saturating_mul(3600)
+            .saturating_add(minute.saturating_mul(60))
+            .saturating_add(second);
+        (days_from_civil(year as i64, month, day) * 86_400)
+            .saturating_add(i64::try_from(time).unwrap_or(i64::MAX))
+    }
+    seconds(end).saturating_sub(seconds(start))
 }
 
-/// ### is_leap_year(year: u64) -> bool
+/// ### split_seconds(seconds: i64) -> (i64, i64, i64, i64)
 ///
-/// Determines if a given year is a leap year.
+/// Splits a signed number of seconds into days, hours, minutes and seconds.
 ///
-/// This function checks if the provided year is a leap year according to the rules:
-/// a year is a leap year if it is divisible by 4 but not divisible by 100, except for years
-/// that are divisible by 400.
+/// Every component carries the sign of `seconds`, so `-90` is `(0, 0, -1, -30)`. Together
+/// with [`duration_between_signed`] this gives the breakdown between two calendar points.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::is_leap_year;
-/// use wtime::utc::get_year;
+/// use wtime::calc::split_seconds;
 ///
-/// let year = 2024;
-/// println!("Is {} a leap year? {}", year, is_leap_year(year)); // true
-/// println!("Is {} a leap year? {}", year, is_leap_year(get_year()));
+/// assert_eq!(split_seconds(183_845), (2, 3, 4, 5));
+/// assert_eq!(split_seconds(-90), (0, 0, -1, -30));
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns `true` if the year is a leap year, otherwise returns `false`.
+/// Returns `(days, hours, minutes, seconds)` with hours below 24 and minutes and seconds
+/// below 60 in magnitude.
 ///
 /// <small>End Fun Doc</small>
-pub fn is_leap_year(year: u64) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+pub fn split_seconds(seconds: i64) -> (i64, i64, i64, i64) {
+    (
+        seconds / 86_400,
+        seconds % 86_400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    )
 }
 
-/// ### get_minute() -> u64
+/// ### time_until(target_ts: u64) -> (i64, i64, i64, i64)
 ///
-/// Retrieves the current minute of the hour.
+/// Calculates the time left until a UNIX timestamp in seconds, as days, hours, minutes and
+/// seconds.
 ///
-/// This function calculates the current minute based on the current UTC timestamp.
+/// Once `target_ts` has passed, every component is negative or zero, so the result also
+/// tells how long ago it was. See [`split_seconds`] and [`format_countdown`].
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_minute;
+/// use wtime::calc::time_until;
+/// use wtime::utc::utc_ts_sec;
 ///
-/// let minute = get_minute();
-/// println!("Current minute: {}", minute);
+/// let (days, hours, minutes, seconds) = time_until(utc_ts_sec() + 2 * 86_400 + 30);
+/// assert!(days == 2 && hours == 0 && minutes == 0 && seconds <= 30);
+///
+/// let (days, ..) = time_until(utc_ts_sec() - 3 * 86_400);
+/// assert_eq!(days, -3);
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the current minute of the hour as a `u64`.
+/// Returns `(days, hours, minutes, seconds)`, all with the sign of the remaining time.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_minute() -> u64 {
-    let minute = (utc_ts_sec() / 60) % 60;
-    minute
+#[cfg(feature = "std")]
+pub fn time_until(target_ts: u64) -> (i64, i64, i64, i64) {
+    split_seconds(target_ts as i64 - utc_ts_sec() as i64)
 }
 
-/// ### get_second() -> u64
+/// ### format_countdown(parts: (i64, i64, i64, i64)) -> String
 ///
-/// Retrieves the current second of the minute.
+/// Formats a `(days, hours, minutes, seconds)` breakdown as `2d 03:04:05`, for launch
+/// timers and expiry displays.
 ///
-/// This function calculates the current second based on the current UTC timestamp.
+/// A breakdown with any negative component, such as a passed [`time_until`] target, is
+/// written with a leading `-`.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_second;
+/// use wtime::calc::{format_countdown, split_seconds, time_until};
+/// use wtime::utc::utc_ts_sec;
 ///
-/// let second = get_second();
-/// println!("Current second: {}", second);
+/// assert_eq!(format_countdown((2, 3, 4, 5)), "2d 03:04:05");
+/// assert_eq!(format_countdown(split_seconds(-90)), "-0d 00:01:30");
+///
+/// println!("Expires in {}", format_countdown(time_until(utc_ts_sec() + 3600)));
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the current second of the minute as a `u64`.
+/// Returns the countdown as a `String`.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_second() -> u64 {
-    let second = utc_ts_sec() % 60;
-    second
+#[cfg(feature = "std")]
+pub fn format_countdown(parts: (i64, i64, i64, i64)) -> String {
+    let (days, hours, minutes, seconds) = parts;
+    let sign = if days < 0 || hours < 0 || minutes < 0 || seconds < 0 {
+        "-"
+    } else {
+        ""
+    };
+    format!(
+        "{}{}d {:02}:{:02}:{:02}",
+        sign,
+        days.unsigned_abs(),
+        hours.unsigned_abs(),
+        minutes.unsigned_abs(),
+        seconds.unsigned_abs()
+    )
 }
 
-/// ### get_millis() -> u64
+/// ### add_days(date: (u64, u64, u64), days: i64) -> (u64, u64, u64)
 ///
-/// Retrieves the current milliseconds based on the elapsed time since the UNIX epoch.
+/// Moves a `(year, month, day)` date forwards or backwards by a number of days.
 ///
-/// This function calculates the current elapsed milliseconds since the UNIX epoch
-/// and returns only the millisecond component (0-999).
+/// A negative `days` subtracts. Month and year boundaries, including February 29, are
+/// handled by the calendar. Results before 0000-01-01 saturate at that date, and results
+/// after the end of [`MAX_YEAR`] at its December 31.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_millis;
+/// use wtime::calc::{add_days, MAX_YEAR};
+///
+/// assert_eq!(add_days((2024, 2, 28), 1), (2024, 2, 29));
+/// assert_eq!(add_days((2024, 12, 31), 1), (2025, 1, 1));
+/// assert_eq!(add_days((2024, 3, 1), -1), (2024, 2, 29));
+/// assert_eq!(add_days((2024, 10, 14), 90), (2025, 1, 12));
+/// assert_eq!(add_days((2024, 1, 1), i64::MAX), (MAX_YEAR, 12, 31));
+/// assert_eq!(add_days((2024, 1, 1), i64::MIN), (0, 1, 1));
+/// ```
 ///
-/// let millis = get_millis();
-/// println!("Current milliseconds: {}", millis);
+/// ### Returns
+///
+/// Returns the resulting date as a `(year, month, day)` tuple.
+///
+/// <small>End Fun Doc</small>
+pub fn add_days(date: (u64, u64, u64), days: i64) -> (u64, u64, u64) {
+    let (year, month, day) = clamp_date(date);
+    let days =
+        days_from_civil(year as i64, month, day).saturating_add(days.clamp(-MAX_DAYS, MAX_DAYS));
+    saturate_date(civil_from_days(days))
+}
+
+/// ### add_months(date: (u64, u64, u64), months: i64) -> (u64, u64, u64)
+///
+/// Moves a `(year, month, day)` date forwards or backwards by a number of calendar months.
+///
+/// A negative `months` subtracts. When the day does not exist in the target month it is
+/// clamped to the last day of that month, so January 31 plus one month is February 28, or
+/// February 29 in a leap year. Results before 0000-01-01 saturate at that date, and results
+/// after the end of [`MAX_YEAR`] at its December 31.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::add_months;
+///
+/// assert_eq!(add_months((2024, 1, 31), 1), (2024, 2, 29));
+/// assert_eq!(add_months((2023, 1, 31), 1), (2023, 2, 28));
+/// assert_eq!(add_months((2024, 10, 14), 3), (2025, 1, 14));
+/// assert_eq!(add_months((2024, 3, 31), -1), (2024, 2, 29));
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the current milliseconds as a `u64`.
+/// Returns the resulting date as a `(year, month, day)` tuple.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_millis() -> u64 {
-    let millis = duration_since().as_millis() % 1000;
-    millis as u64
+pub fn add_months(date: (u64, u64, u64), months: i64) -> (u64, u64, u64) {
+    let (year, month, day) = clamp_date(date);
+    let months = months.clamp(-MAX_MONTHS, MAX_MONTHS);
+    saturate_date(shift_months(year as i64, month, day, months))
 }
 
-/// ### get_nanos() -> u64
+/// ### add_years(date: (u64, u64, u64), years: i64) -> (u64, u64, u64)
 ///
-/// Retrieves the current nanoseconds based on the elapsed time since the UNIX epoch.
+/// Moves a `(year, month, day)` date forwards or backwards by a number of years.
 ///
-/// This function calculates the current elapsed nanoseconds since the UNIX epoch
-/// and returns only the nanosecond component (0-999,999).
+/// A negative `years` subtracts. February 29 becomes February 28 when the target year is
+/// not a leap year. Results before 0000-01-01 saturate at that date, and results after the
+/// end of [`MAX_YEAR`] at its December 31.
 ///
 /// ### Example
 ///
 /// ```
-/// use wtime::calc::get_nanos;
+/// use wtime::calc::add_years;
 ///
-/// let nanos = get_nanos();
-/// println!("Current nanoseconds: {}", nanos);
+/// assert_eq!(add_years((2024, 10, 14), 1), (2025, 10, 14));
+/// assert_eq!(add_years((2024, 2, 29), 1), (2025, 2, 28));
+/// assert_eq!(add_years((2024, 2, 29), 4), (2028, 2, 29));
+/// assert_eq!(add_years((2024, 10, 14), -30), (1994, 10, 14));
 /// ```
 ///
 /// ### Returns
 ///
-/// Returns the current nanoseconds as a `u64`.
+/// Returns the resulting date as a `(year, month, day)` tuple.
 ///
 /// <small>End Fun Doc</small>
-pub fn get_nanos() -> u64 {
-    let nanos = duration_since().as_nanos() % 1_000_000;
-    nanos as u64
+pub fn add_years(date: (u64, u64, u64), years: i64) -> (u64, u64, u64) {
+    add_months(date, years.saturating_mul(12))
+}
+
+/// ### age_at(birth_date: (u64, u64, u64), on_date: (u64, u64, u64)) -> (u64, u64, u64)
+///
+/// Calculates the age on a date of someone or something born on another, in whole years,
+/// months and days.
+///
+/// Months are counted as with [`add_months`], so a month after January 31 is the end of
+/// February. Someone born on February 29 turns a year older on February 28 in non-leap
+/// years, as with [`add_years`]. The remaining days are counted from the last monthly
+/// anniversary.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::age_at;
+///
+/// assert_eq!(age_at((1990, 5, 17), (2024, 10, 14)), (34, 4, 27));
+/// assert_eq!(age_at((1990, 5, 17), (2024, 5, 17)), (34, 0, 0));
+/// assert_eq!(age_at((1990, 5, 17), (2024, 5, 16)), (33, 11, 29));
+///
+/// // Leap-day birthdays
+/// assert_eq!(age_at((2000, 2, 29), (2023, 2, 28)), (23, 0, 0));
+/// assert_eq!(age_at((2000, 2, 29), (2024, 2, 28)), (23, 11, 30));
+/// assert_eq!(age_at((2000, 2, 29), (2024, 2, 29)), (24, 0, 0));
+///
+/// assert_eq!(age_at((2024, 10, 14), (2024, 1, 1)), (0, 0, 0));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `(years, months, days)`, or `(0, 0, 0)` if `on_date` is before `birth_date`.
+///
+/// <small>End Fun Doc</small>
+pub fn age_at(birth_date: (u64, u64, u64), on_date: (u64, u64, u64)) -> (u64, u64, u64) {
+    if on_date <= birth_date {
+        return (0, 0, 0);
+    }
+    let (birth_date, on_date) = (clamp_date(birth_date), clamp_date(on_date));
+    let (birth_year, birth_month, _) = birth_date;
+    let (on_year, on_month, on_day) = on_date;
+    let mut months =
+        (on_year as i64 - birth_year as i64) * 12 + on_month as i64 - birth_month as i64;
+    if add_months(birth_date, months) > on_date {
+        months -= 1;
+    }
+    let (year, month, day) = add_months(birth_date, months);
+    let days = days_from_civil(on_year as i64, on_month, on_day)
+        - days_from_civil(year as i64, month, day);
+    (months as u64 / 12, months as u64 % 12, days as u64)
+}
+
+/// ### next_anniversary(date: (u64, u64, u64), after: (u64, u64, u64)) -> (u64, u64, u64)
+///
+/// Finds the first anniversary of a date that falls after another date.
+///
+/// Anniversaries of February 29 fall on February 28 in non-leap years and return to
+/// February 29 in leap years. If `after` is before `date`, the date itself is next.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::next_anniversary;
+///
+/// assert_eq!(next_anniversary((1990, 5, 17), (2024, 10, 14)), (2025, 5, 17));
+/// assert_eq!(next_anniversary((1990, 10, 20), (2024, 10, 14)), (2024, 10, 20));
+/// assert_eq!(next_anniversary((1990, 10, 14), (2024, 10, 14)), (2025, 10, 14));
+///
+/// // Leap-day anniversaries
+/// assert_eq!(next_anniversary((2000, 2, 29), (2024, 10, 14)), (2025, 2, 28));
+/// assert_eq!(next_anniversary((2000, 2, 29), (2027, 3, 1)), (2028, 2, 29));
+///
+/// assert_eq!(next_anniversary((2030, 1, 1), (2024, 10, 14)), (2030, 1, 1));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the anniversary as a `(year, month, day)` tuple, strictly after `after`.
+///
+/// <small>End Fun Doc</small>
+pub fn next_anniversary(date: (u64, u64, u64), after: (u64, u64, u64)) -> (u64, u64, u64) {
+    let (date, after) = (clamp_date(date), clamp_date(after));
+    if after < date {
+        return date;
+    }
+    let years = (after.0 - date.0) as i64;
+    let anniversary = add_years(date, years);
+    if anniversary > after {
+        anniversary
+    } else {
+        add_years(date, years + 1)
+    }
+}
+
+/// ### day_of_year(year: u64, month: u64, day: u64) -> u64
+///
+/// Calculates the ordinal day of a date within its year, from 1 for January 1 to 365, or
+/// 366 for December 31 of a leap year. Any `u64` year is supported.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::day_of_year;
+///
+/// assert_eq!(day_of_year(2024, 1, 1), 1);
+/// assert_eq!(day_of_year(2024, 10, 14), 288);
+/// assert_eq!(day_of_year(2024, 12, 31), 366);
+/// assert_eq!(day_of_year(2023, 12, 31), 365);
+/// assert_eq!(day_of_year(100_000_000_000_000_000, 3, 1), 61);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the day of the year as a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn day_of_year(year: u64, month: u64, day: u64) -> u64 {
+    // Leap years repeat every 400 years, so folding the year keeps any input in range
+    let year = (year % 400) as i64;
+    (days_from_civil(year, month, day) - days_from_civil(year, 1, 1) + 1) as u64
+}
+
+/// ### date_from_ordinal(year: u64, day_of_year: u64) -> Result<(u64, u64, u64), WTimeError>
+///
+/// Calculates the `(year, month, day)` of an ordinal day within a year.
+///
+/// This is the inverse of [`day_of_year`], for ordinal dates such as `2024-288`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::date_from_ordinal;
+///
+/// assert_eq!(date_from_ordinal(2024, 288), Ok((2024, 10, 14)));
+/// assert_eq!(date_from_ordinal(2024, 60), Ok((2024, 2, 29)));
+/// assert_eq!(date_from_ordinal(2023, 60), Ok((2023, 3, 1)));
+///
+/// assert!(date_from_ordinal(2023, 366).is_err());
+/// assert!(date_from_ordinal(2024, 0).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date as a `(year, month, day)` tuple.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the day is 0 or past the end of the year.
+///
+/// <small>End Fun Doc</small>
+pub fn date_from_ordinal(year: u64, day_of_year: u64) -> Result<(u64, u64, u64), WTimeError> {
+    let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+    if !(1..=days_in_year).contains(&day_of_year) {
+        return Err(WTimeError::OutOfRange("day of year"));
+    }
+    let (_, month, day) =
+        civil_from_days(days_from_civil((year % 400) as i64, 1, 1) + day_of_year as i64 - 1);
+    Ok((year, month, day))
+}
+
+/// ### to_julian_day(year: i64, month: u64, day: u64) -> i64
+///
+/// Converts a date into its Julian day number (JDN).
+///
+/// The JDN counts days since noon on January 1, 4713 BC in the Julian calendar, and is how
+/// astronomical and many scientific datasets store dates. The date is read in the proleptic
+/// Gregorian calendar, also before 1582. The components are not validated, and years beyond
+/// ±[`MAX_YEAR`] are clamped to it.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{to_julian_day, MAX_YEAR};
+///
+/// assert_eq!(to_julian_day(1970, 1, 1), 2_440_588);
+/// assert_eq!(to_julian_day(2000, 1, 1), 2_451_545); // the J2000.0 epoch
+/// assert_eq!(to_julian_day(-4713, 11, 24), 0);
+/// assert_eq!(to_julian_day(i64::MAX, 3, 1), to_julian_day(MAX_YEAR as i64, 3, 1));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the Julian day number of the day starting at noon UTC on that date.
+///
+/// <small>End Fun Doc</small>
+pub fn to_julian_day(year: i64, month: u64, day: u64) -> i64 {
+    let year = year.clamp(-(MAX_YEAR as i64), MAX_YEAR as i64);
+    days_from_civil(year, month, day) + UNIX_EPOCH_JULIAN_DAY
+}
+
+/// ### from_julian_day(julian_day: i64) -> (i64, u64, u64)
+///
+/// Converts a Julian day number (JDN) into a proleptic Gregorian `(year, month, day)`.
+///
+/// This is the inverse of [`to_julian_day`]. Year 0 is 1 BC, and years before it are
+/// negative.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{from_julian_day, to_julian_day};
+///
+/// assert_eq!(from_julian_day(2_460_598), (2024, 10, 14));
+/// assert_eq!(from_julian_day(to_julian_day(1582, 10, 15)), (1582, 10, 15));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a tuple containing the year, month, and day.
+///
+/// <small>End Fun Doc</small>
+pub fn from_julian_day(julian_day: i64) -> (i64, u64, u64) {
+    civil_from_days(julian_day - UNIX_EPOCH_JULIAN_DAY)
+}
+
+/// ### calc_week(date: (u64, u64, u64)) -> u64
+///
+/// Calculates the week number in the year based on a provided date.
+///
+/// The function takes a date in the format `(year, month, day)` and returns the week number
+/// according to the ISO 8601 standard (where the first week of the year is the week
+/// containing the first Thursday).
+///
+/// Around New Year the ISO week can belong to the neighbouring year: December 30, 2024 is
+/// in week 1 of 2025. Use [`iso_week_date`] to also get that week-based year, and
+/// [`calc_week_with`] for weeks starting on Sunday or Saturday: with
+/// `WeekConfig::default()` it follows the configured
+/// [`Config::week_start`](crate::config::Config::week_start).
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::calc::calc_week;
+///
+/// let week_number = calc_week((2024, 10, 14));
+/// println!("Week number: {}", week_number);
+/// assert_eq!(week_number, 42);
+///
+/// assert_eq!(calc_week((2024, 12, 30)), 1);
+/// assert_eq!(calc_week((2021, 1, 3)), 53);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the week number (1-53) as a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn calc_week(date: (u64, u64, u64)) -> u64 {
+    let (year, month, day) = date;
+    iso_week_date(year, month, day).1
+}
+
+/// ### iso_week_date(year: u64, month: u64, day: u64) -> (u64, u64, Weekday)
+///
+/// Calculates the ISO 8601 week date of a calendar date: the week-based year, the week
+/// number and the weekday.
+///
+/// ISO weeks start on Monday, and week 1 is the week containing the year's first Thursday.
+/// The days from December 29 to January 3 can therefore belong to a week of the
+/// neighbouring year, which is why the week-based year is returned alongside the week. The
+/// year must be at least 1.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{iso_week_date, Weekday};
+///
+/// assert_eq!(iso_week_date(2024, 10, 14), (2024, 42, Weekday::Monday));
+///
+/// // Monday, December 30, 2024 starts week 1 of 2025
+/// assert_eq!(iso_week_date(2024, 12, 29), (2024, 52, Weekday::Sunday));
+/// assert_eq!(iso_week_date(2024, 12, 30), (2025, 1, Weekday::Monday));
+///
+/// // January 1-3, 2021 are still in week 53 of 2020
+/// assert_eq!(iso_week_date(2020, 12, 31), (2020, 53, Weekday::Thursday));
+/// assert_eq!(iso_week_date(2021, 1, 3), (2020, 53, Weekday::Sunday));
+/// assert_eq!(iso_week_date(2021, 1, 4), (2021, 1, Weekday::Monday));
+///
+/// // A Thursday January 1 is always in week 1
+/// assert_eq!(iso_week_date(2026, 1, 1), (2026, 1, Weekday::Thursday));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `(week_year, week, weekday)`, with the week from 1 to 52 or 53.
+///
+/// <small>End Fun Doc</small>
+pub fn iso_week_date(year: u64, month: u64, day: u64) -> (u64, u64, Weekday) {
+    WeekConfig::ISO.week_date(year, month, day)
+}
+
+/// ### WeekConfig
+///
+/// The rules that number the weeks of a year: the first day of the week, and how many days
+/// of the new year the first week must contain.
+///
+/// ISO 8601 weeks start on Monday and week 1 has at least four days of the new year, so it
+/// is the week of the first Thursday. In the United States weeks start on Sunday and week 1
+/// is the week of January 1; much of the Middle East does the same with weeks starting on
+/// Saturday.
+///
+/// Each [`WeekStart`](crate::config::WeekStart) converts into the matching preset, and the
+/// default follows the configured [`Config::week_start`](crate::config::Config::week_start),
+/// so ISO weeks unless the application chose otherwise. Without the `std` feature there is
+/// no configuration and the default is ISO.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_week_with, WeekConfig, Weekday};
+///
+/// // Saturday, October 12, 2024 starts a new week in the Middle East only
+/// assert_eq!(calc_week_with((2024, 10, 12), WeekConfig::ISO), 41);
+/// assert_eq!(calc_week_with((2024, 10, 12), WeekConfig::US), 41);
+/// assert_eq!(calc_week_with((2024, 10, 12), WeekConfig::MIDDLE_EAST), 42);
+///
+/// // Saturday, January 1, 2022 is in the last ISO week of 2021
+/// assert_eq!(WeekConfig::ISO.week_date(2022, 1, 1), (2021, 52, Weekday::Saturday));
+/// assert_eq!(WeekConfig::US.week_date(2022, 1, 1), (2022, 1, Weekday::Saturday));
+///
+/// let custom = WeekConfig {
+///     first_day: Weekday::Sunday,
+///     min_days_in_first_week: 4,
+/// };
+/// assert_eq!(custom.week_date(2022, 1, 1), (2021, 52, Weekday::Saturday));
+///
+/// // Any year is supported
+/// assert_eq!(
+///     WeekConfig::ISO.week_date(100_000_000_000_000_000, 3, 1),
+///     (100_000_000_000_000_000, 9, Weekday::Wednesday)
+/// );
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::config::{self, Config, WeekStart};
+///
+/// assert_eq!(WeekConfig::from(WeekStart::Sunday), WeekConfig::US);
+/// assert_eq!(WeekConfig::default(), WeekConfig::ISO);
+///
+/// let sunday_weeks = Config {
+///     week_start: WeekStart::Sunday,
+///     ..Config::default()
+/// };
+/// config::scoped(sunday_weeks, || assert_eq!(WeekConfig::default(), WeekConfig::US));
+/// # }
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeekConfig {
+    /// The day each week starts on.
+    pub first_day: Weekday,
+    /// The number of days of the new year, from 1 to 7, that makes a week the first week.
+    /// Values outside that range are clamped to it.
+    pub min_days_in_first_week: u64,
+}
+
+impl WeekConfig {
+    /// ISO 8601 weeks: Monday to Sunday, week 1 containing the first Thursday.
+    pub const ISO: WeekConfig = WeekConfig {
+        first_day: Weekday::Monday,
+        min_days_in_first_week: 4,
+    };
+
+    /// United States weeks: Sunday to Saturday, week 1 containing January 1.
+    pub const US: WeekConfig = WeekConfig {
+        first_day: Weekday::Sunday,
+        min_days_in_first_week: 1,
+    };
+
+    /// Middle Eastern weeks: Saturday to Friday, week 1 containing January 1.
+    pub const MIDDLE_EAST: WeekConfig = WeekConfig {
+        first_day: Weekday::Saturday,
+        min_days_in_first_week: 1,
+    };
+
+    /// Returns the week-based year, the week number and the weekday of a calendar date.
+    pub fn week_date(&self, year: u64, month: u64, day: u64) -> (u64, u64, Weekday) {
+        // Week dates repeat every 400 years, so the calendar math runs on the folded year
+        let cycles = year - year % 400;
+        let days = days_from_civil((year % 400) as i64, month, day);
+        let weekday = Weekday::from_days(days);
+        let week_start = days - (weekday as i64 - self.first_day as i64).rem_euclid(7);
+        // The week belongs to the year of its day that leaves exactly the minimum number of
+        // days in that year, the Thursday for ISO weeks
+        let decisive = week_start + 7 - self.min_days_in_first_week.clamp(1, 7) as i64;
+        let (week_year, _, _) = civil_from_days(decisive);
+        let week = (decisive - days_from_civil(week_year, 1, 1)) / 7 + 1;
+        (
+            cycles.saturating_add_signed(week_year),
+            week as u64,
+            weekday,
+        )
+    }
+}
+
+impl Default for WeekConfig {
+    /// Returns the weeks of the configured week start, or ISO weeks without the `std`
+    /// feature.
+    fn default() -> Self {
+        #[cfg(feature = "std")]
+        {
+            config::get().week_start.into()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            WeekConfig::ISO
+        }
+    }
+}
+
+/// ### calc_week_with(date: (u64, u64, u64), config: WeekConfig) -> u64
+///
+/// Calculates the week number of a date under the given [`WeekConfig`].
+///
+/// This is [`calc_week`] for weeks that do not follow ISO 8601. As with ISO weeks, the
+/// first or last days of a year can belong to a week of the neighbouring year; use
+/// [`WeekConfig::week_date`] to also get that week-based year.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_week, calc_week_with, WeekConfig};
+///
+/// // Sunday, October 13, 2024 ends an ISO week but starts a US one
+/// assert_eq!(calc_week((2024, 10, 13)), 41);
+/// assert_eq!(calc_week_with((2024, 10, 13), WeekConfig::US), 42);
+/// assert_eq!(calc_week_with((2024, 10, 13), WeekConfig::ISO), 41);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the week number (1-53) as a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn calc_week_with(date: (u64, u64, u64), config: WeekConfig) -> u64 {
+    let (year, month, day) = date;
+    config.week_date(year, month, day).1
+}
+
+/// ### quarter(month: u64) -> u64
+///
+/// Returns the calendar quarter of a month: 1 for January to March, up to 4 for October to
+/// December.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::quarter;
+///
+/// assert_eq!(quarter(1), 1);
+/// assert_eq!(quarter(10), 4);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the quarter (1-4) as a `u64`.
+///
+/// ### Panics
+///
+/// Panics if `month` is not between 1 and 12. Use [`try_quarter`] to handle that case
+/// instead.
+///
+/// <small>End Fun Doc</small>
+pub fn quarter(month: u64) -> u64 {
+    assert!((1..=12).contains(&month), "month must be between 1 and 12");
+    (month - 1) / 3 + 1
+}
+
+/// ### try_quarter(month: u64) -> Result<u64, WTimeError>
+///
+/// Returns the calendar quarter of a month, or an error for a number outside 1-12.
+///
+/// This is the non-panicking form of [`quarter`] for month numbers that come from user
+/// input.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::try_quarter;
+/// use wtime::error::WTimeError;
+///
+/// assert_eq!(try_quarter(10), Ok(4));
+/// assert_eq!(try_quarter(13), Err(WTimeError::OutOfRange("month")));
+/// assert_eq!(try_quarter(0), Err(WTimeError::OutOfRange("month")));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the quarter (1-4) as a `u64`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn try_quarter(month: u64) -> Result<u64, WTimeError> {
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    Ok(quarter(month))
+}
+
+/// ### start_of_quarter(date: (u64, u64, u64)) -> (u64, u64, u64)
+///
+/// Returns the first day of the calendar quarter containing a date.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::start_of_quarter;
+///
+/// assert_eq!(start_of_quarter((2024, 11, 20)), (2024, 10, 1));
+/// assert_eq!(start_of_quarter((2024, 3, 31)), (2024, 1, 1));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date of January 1, April 1, July 1 or October 1.
+///
+/// ### Panics
+///
+/// Panics if the month of `date` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_quarter(date: (u64, u64, u64)) -> (u64, u64, u64) {
+    let (year, month, _) = date;
+    (year, (quarter(month) - 1) * 3 + 1, 1)
+}
+
+/// ### first_day_of_month(date: (u64, u64, u64)) -> (u64, u64, u64)
+///
+/// Returns the first day of the month containing a date.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::first_day_of_month;
+///
+/// assert_eq!(first_day_of_month((2024, 2, 17)), (2024, 2, 1));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date of the first of the month.
+///
+/// <small>End Fun Doc</small>
+pub fn first_day_of_month(date: (u64, u64, u64)) -> (u64, u64, u64) {
+    let (year, month, _) = date;
+    (year, month, 1)
+}
+
+/// ### last_day_of_month(year: u64, month: u64) -> u64
+///
+/// Returns the day number of the last day of a month, taking leap years into account.
+///
+/// This is an alias of [`days_in_month`], named for code that asks for the end of a month
+/// rather than its length.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::last_day_of_month;
+///
+/// assert_eq!(last_day_of_month(2024, 2), 29);
+/// assert_eq!(last_day_of_month(2023, 2), 28);
+/// assert_eq!(last_day_of_month(1900, 2), 28);
+/// assert_eq!(last_day_of_month(2024, 4), 30);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the last day (28-31), or 0 if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn last_day_of_month(year: u64, month: u64) -> u64 {
+    days_in_month(year, month)
+}
+
+/// ### month_bounds(ts: u64) -> (u64, u64)
+///
+/// Returns the UTC calendar month containing a UNIX timestamp, as the half-open range
+/// `[start, end)` of timestamps in seconds.
+///
+/// `start` is midnight on the first of the month and `end` is midnight on the first of the
+/// next month, so a billing period is `start <= ts && ts < end` and its length is
+/// `end - start` whatever the month or year. Timestamps after [`MAX_TS`] give the last month
+/// of [`MAX_YEAR`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_timestamp, month_bounds};
+///
+/// let ts = 1709251199; // 2024-02-29 23:59:59 UTC
+/// let (start, end) = month_bounds(ts);
+/// assert_eq!(start, calc_timestamp(2024, 2, 1, 0, 0, 0).unwrap());
+/// assert_eq!(end, calc_timestamp(2024, 3, 1, 0, 0, 0).unwrap());
+/// assert_eq!((end - start) / 86_400, 29);
+///
+/// assert_eq!(month_bounds(end), (end, calc_timestamp(2024, 4, 1, 0, 0, 0).unwrap()));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `(start, end)`, both UNIX timestamps in seconds.
+///
+/// <small>End Fun Doc</small>
+pub fn month_bounds(ts: u64) -> (u64, u64) {
+    let (start, end) = unit_bounds(ts.min(MAX_TS) as i64, Unit::Month, Weekday::Monday);
+    (start as u64, end as u64)
+}
+
+/// ### FiscalYear
+///
+/// A fiscal year that starts on the first day of a given month, such as October for the
+/// United States federal government or April in Japan and India.
+///
+/// Fiscal years are named after the calendar year they end in, so the fiscal year starting
+/// in October 2024 is fiscal 2025. A fiscal year starting in January is the calendar year.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::FiscalYear;
+///
+/// let federal = FiscalYear::new(10).unwrap();
+/// assert_eq!(federal.fiscal_year((2024, 10, 14)), 2025);
+/// assert_eq!(federal.fiscal_quarter((2024, 10, 14)), 1);
+/// assert_eq!(federal.fiscal_year((2024, 9, 30)), 2024);
+/// assert_eq!(federal.fiscal_quarter((2024, 9, 30)), 4);
+/// assert_eq!(federal.start_of_fiscal_year((2024, 9, 30)), (2023, 10, 1));
+/// assert_eq!(federal.start_of_fiscal_year((2024, 10, 14)), (2024, 10, 1));
+/// assert_eq!(federal.start_of_fiscal_year((0, 1, 1)), (0, 1, 1));
+///
+/// let april = FiscalYear::new(4).unwrap();
+/// assert_eq!(april.fiscal_quarter((2024, 10, 14)), 3);
+///
+/// assert!(FiscalYear::new(13).is_err());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FiscalYear {
+    start_month: u64,
+}
+
+impl FiscalYear {
+    /// Creates a fiscal year starting on the first day of `start_month`, from 1 to 12.
+    pub fn new(start_month: u64) -> Result<Self, WTimeError> {
+        if !(1..=12).contains(&start_month) {
+            return Err(WTimeError::OutOfRange("month"));
+        }
+        Ok(FiscalYear { start_month })
+    }
+
+    /// Returns the month the fiscal year starts in.
+    pub fn start_month(&self) -> u64 {
+        self.start_month
+    }
+
+    /// Returns the fiscal year containing a date, named after the calendar year it ends in.
+    pub fn fiscal_year(&self, date: (u64, u64, u64)) -> u64 {
+        let (year, month, _) = date;
+        if self.start_month > 1 && month >= self.start_month {
+            year.saturating_add(1)
+        } else {
+            year
+        }
+    }
+
+    /// Returns the fiscal quarter (1-4) containing a date.
+    pub fn fiscal_quarter(&self, date: (u64, u64, u64)) -> u64 {
+        let (_, month, _) = date;
+        (month + 12 - self.start_month) % 12 / 3 + 1
+    }
+
+    /// Returns the first day of the fiscal year containing a date. A fiscal year that
+    /// started before year 0 saturates at 0000-01-01.
+    pub fn start_of_fiscal_year(&self, date: (u64, u64, u64)) -> (u64, u64, u64) {
+        let (year, month, _) = date;
+        // Before the start month, the fiscal year started in the previous calendar year
+        match year.checked_sub(u64::from(month < self.start_month)) {
+            Some(start_year) => (start_year, self.start_month, 1),
+            None => (0, 1, 1),
+        }
+    }
+}
+
+impl Default for FiscalYear {
+    fn default() -> Self {
+        FiscalYear { start_month: 1 }
+    }
+}
+
+/// ### duration_since()
+///
+/// Returns the duration from the UNIX epoch to the current time.
+///
+/// This function calculates the amount of time that has elapsed since the UNIX epoch
+/// (January 1, 1970) and returns it as a `Duration`. This can be useful for calculations
+/// involving specific time intervals.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::duration_since;
+///
+/// let duration = duration_since();
+/// println!("Seconds since UNIX epoch: {:?}", duration.as_secs());
+/// println!("Milli-seconds since UNIX epoch: {:?}", duration_since().as_millis());
+/// ```
+///
+/// ### Panics
+///
+/// This function will panic if the current system time is before the UNIX epoch. Use
+/// [`try_duration_since`] to handle that case instead.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn duration_since() -> Duration {
+    utc_now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+}
+
+/// ### try_duration_since() -> Result<Duration, WTimeError>
+///
+/// Returns the duration from the UNIX epoch to the current time, or an error if the system
+/// clock is set before the epoch.
+///
+/// This is the non-panicking form of [`duration_since`] for libraries that should not bring
+/// down their caller because of a misconfigured clock.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::try_duration_since;
+///
+/// match try_duration_since() {
+///     Ok(duration) => println!("Seconds since UNIX epoch: {}", duration.as_secs()),
+///     Err(err) => eprintln!("Clock error: {}", err),
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the elapsed time as a `Duration`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the system time is before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn try_duration_since() -> Result<Duration, WTimeError> {
+    utc_now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| WTimeError::OutOfRange("system time"))
+}
+
+/// ### get_day_name(total_seconds: u64) -> &'static str
+///
+/// Returns the name of the day of the week corresponding to the total seconds since the UNIX epoch.
+///
+/// This function calculates the day name based on the total seconds since the UNIX epoch.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_day_name;
+///
+/// let day_name = get_day_name(1_670_000_000);
+/// println!("Day name: {}", day_name); // Day name: Friday
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::utc::utc_ts_sec;
+///
+/// println!("Today is {}", get_day_name(utc_ts_sec()));
+/// # }
+/// ```
+///
+/// The argument is a timestamp, not a day of the month: use [`day_name_of`] to name the
+/// weekday of a calendar date.
+///
+/// ### Returns
+///
+/// Returns the name of the day as a static string reference.
+///
+/// <small>End Fun Doc</small>
+pub fn get_day_name(total_seconds: u64) -> &'static str {
+    const DAYS: [&str; 7] = [
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+    ]; // Starting from 1970-01-01
+    let days_since_epoch = (total_seconds / 86_400) % 7; // Total days since UNIX epoch modulo 7
+    DAYS[days_since_epoch as usize]
+}
+
+/// ### get_day_abbr(total_seconds: u64) -> &'static str
+///
+/// Returns the three-letter English abbreviation of the weekday of a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_day_abbr;
+///
+/// assert_eq!(get_day_abbr(1728933069), "Mon");
+/// assert_eq!(get_day_abbr(0), "Thu");
+/// ```
+///
+/// ### Returns
+///
+/// Returns an abbreviation from `"Mon"` to `"Sun"`.
+///
+/// <small>End Fun Doc</small>
+pub fn get_day_abbr(total_seconds: u64) -> &'static str {
+    &get_day_name(total_seconds)[..3]
+}
+
+/// ### day_name_of(year: u64, month: u64, day: u64) -> &'static str
+///
+/// Returns the English name of the weekday of a calendar date.
+///
+/// Unlike [`get_day_name`], which takes a UNIX timestamp, this takes the date itself, so it
+/// also works for dates before 1970.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::day_name_of;
+///
+/// assert_eq!(day_name_of(2024, 10, 14), "Monday");
+/// assert_eq!(day_name_of(1969, 7, 20), "Sunday");
+/// ```
+///
+/// ### Returns
+///
+/// Returns the name of the day as a static string reference.
+///
+/// <small>End Fun Doc</small>
+pub fn day_name_of(year: u64, month: u64, day: u64) -> &'static str {
+    weekday_of(year, month, day).name()
+}
+
+/// ### get_month_name(month: u64) -> &'static str
+///
+/// Returns the name of the month corresponding to the provided month number.
+///
+/// This function takes a month number (from 1 to 12) and returns the corresponding month name.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_month_name;
+///
+/// let month_name = get_month_name(4);
+/// println!("Month name: {}", month_name); // "April"
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::utc::get_month;
+///
+/// let month = get_month();
+/// println!("Current month: {}", month);
+/// println!("Month name: {}", get_month_name(month));
+/// # }
+/// ```
+///
+/// ### Panics
+///
+/// This function will panic if provided with an invalid month number (not between 1 and 12).
+/// Use [`try_get_month_name`] to handle that case instead.
+///
+/// <small>End Fun Doc</small>
+pub fn get_month_name(month: u64) -> &'static str {
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    MONTHS[(month - 1) as usize] // -1 to convert from 1-indexed to 0-indexed
+}
+
+/// ### get_month_abbr(month: u64) -> &'static str
+///
+/// Returns the three-letter English abbreviation of a month number.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_month_abbr;
+///
+/// assert_eq!(get_month_abbr(1), "Jan");
+/// assert_eq!(get_month_abbr(10), "Oct");
+/// ```
+///
+/// ### Returns
+///
+/// Returns an abbreviation from `"Jan"` to `"Dec"`.
+///
+/// ### Panics
+///
+/// Panics if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn get_month_abbr(month: u64) -> &'static str {
+    &get_month_name(month)[..3]
+}
+
+/// ### try_get_month_name(month: u64) -> Result<&'static str, WTimeError>
+///
+/// Retrieves the English name of a month, or an error for a number outside 1-12.
+///
+/// This is the non-panicking form of [`get_month_name`] for month numbers that come from
+/// user input.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::try_get_month_name;
+/// use wtime::error::WTimeError;
+///
+/// assert_eq!(try_get_month_name(10), Ok("October"));
+/// assert_eq!(try_get_month_name(13), Err(WTimeError::OutOfRange("month")));
+/// assert_eq!(try_get_month_name(0), Err(WTimeError::OutOfRange("month")));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the month name as a `&'static str`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn try_get_month_name(month: u64) -> Result<&'static str, WTimeError> {
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    Ok(get_month_name(month))
+}
+
+/// ### get_month_name_in<L: CalendarNames + ?Sized>(month: u64, locale: &L) -> Result<&str, WTimeError>
+///
+/// Returns the name of a month in the language of `locale`.
+///
+/// This is [`try_get_month_name`] for languages other than English.
+///
+/// ### Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::calc::get_month_name_in;
+/// use wtime::config::Locale;
+/// use wtime::error::WTimeError;
+///
+/// assert_eq!(get_month_name_in(10, &Locale::English), Ok("October"));
+/// assert_eq!(get_month_name_in(10, &Locale::Arabic), Ok("أكتوبر"));
+/// assert_eq!(get_month_name_in(13, &Locale::English), Err(WTimeError::OutOfRange("month")));
+/// # }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the month name as a `&str` borrowed from `locale`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn get_month_name_in<L: CalendarNames + ?Sized>(
+    month: u64,
+    locale: &L,
+) -> Result<&str, WTimeError> {
+    Ok(locale.month_name(Month::try_from(month)?))
+}
+
+/// ### get_day_name_in<L: CalendarNames + ?Sized>(total_seconds: u64, locale: &L) -> &str
+///
+/// Returns the name of the weekday of a UNIX timestamp in the language of `locale`.
+///
+/// This is [`get_day_name`] for languages other than English.
+///
+/// ### Example
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::calc::get_day_name_in;
+/// use wtime::config::Locale;
+///
+/// assert_eq!(get_day_name_in(1728933069, &Locale::English), "Monday");
+/// assert_eq!(get_day_name_in(1728933069, &Locale::Arabic), "الاثنين");
+/// # }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the weekday name as a `&str` borrowed from `locale`.
+///
+/// <small>End Fun Doc</small>
+pub fn get_day_name_in<L: CalendarNames + ?Sized>(total_seconds: u64, locale: &L) -> &str {
+    locale.day_name(Weekday::from_days((total_seconds / 86_400) as i64))
+}
+
+/// ### is_leap_year(year: u64) -> bool
+///
+/// Determines if a given year is a leap year.
+///
+/// This function checks if the provided year is a leap year according to the rules:
+/// a year is a leap year if it is divisible by 4 but not divisible by 100, except for years
+/// that are divisible by 400.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::is_leap_year;
+///
+/// let year = 2024;
+/// println!("Is {} a leap year? {}", year, is_leap_year(year)); // true
+/// # #[cfg(feature = "std")]
+/// # {
+/// use wtime::utc::get_year;
+///
+/// println!("Is {} a leap year? {}", year, is_leap_year(get_year()));
+/// # }
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if the year is a leap year, otherwise returns `false`.
+///
+/// <small>End Fun Doc</small>
+pub fn is_leap_year(year: u64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+/// ### days_in_month(year: u64, month: u64) -> u64
+///
+/// Returns the number of days in a month of a given year.
+///
+/// February has 29 days in leap years and 28 otherwise.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::days_in_month;
+///
+/// assert_eq!(days_in_month(2024, 2), 29);
+/// assert_eq!(days_in_month(2023, 2), 28);
+/// assert_eq!(days_in_month(2024, 10), 31);
+/// assert_eq!(days_in_month(2024, 13), 0);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the number of days (28-31), or 0 if `month` is not between 1 and 12.
+///
+/// <small>End Fun Doc</small>
+pub fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// ### is_valid_date(year: u64, month: u64, day: u64) -> bool
+///
+/// Checks whether `(year, month, day)` is a real calendar date.
+///
+/// Use it to validate user input before passing it to functions such as
+/// [`calc_timestamp`], which reject invalid dates, or [`add_days`], which do not check them.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::is_valid_date;
+///
+/// assert!(is_valid_date(2024, 2, 29));
+/// assert!(!is_valid_date(2023, 2, 29));
+/// assert!(!is_valid_date(2024, 4, 31));
+/// assert!(!is_valid_date(2024, 0, 1));
+/// assert!(!is_valid_date(2024, 1, 0));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if the month is between 1 and 12 and the day exists in that month.
+///
+/// <small>End Fun Doc</small>
+pub fn is_valid_date(year: u64, month: u64, day: u64) -> bool {
+    (1..=days_in_month(year, month)).contains(&day)
+}
+
+/// ### get_minute() -> u64
+///
+/// Retrieves the current minute of the hour.
+///
+/// This function calculates the current minute based on the current UTC timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_minute;
+///
+/// let minute = get_minute();
+/// println!("Current minute: {}", minute);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current minute of the hour as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn get_minute() -> u64 {
+    (utc_ts_sec() / 60) % 60
+}
+
+/// ### get_second() -> u64
+///
+/// Retrieves the current second of the minute.
+///
+/// This function calculates the current second based on the current UTC timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_second;
+///
+/// let second = get_second();
+/// println!("Current second: {}", second);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current second of the minute as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn get_second() -> u64 {
+    utc_ts_sec() % 60
+}
+
+/// ### get_millis() -> u64
+///
+/// Retrieves the current milliseconds based on the elapsed time since the UNIX epoch.
+///
+/// This function calculates the current elapsed milliseconds since the UNIX epoch
+/// and returns only the millisecond component (0-999).
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_millis;
+///
+/// let millis = get_millis();
+/// println!("Current milliseconds: {}", millis);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current milliseconds as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn get_millis() -> u64 {
+    let millis = duration_since().as_millis() % 1000;
+    millis as u64
+}
+
+/// ### get_nanos() -> u64
+///
+/// Retrieves the nanoseconds elapsed within the current millisecond.
+///
+/// This is not the full sub-second part: it is the remainder after [`get_millis`], in the
+/// range 0-999,999, as written in the last field of `format_utc_ts`. Use [`subsec_nanos`]
+/// for the nanoseconds within the current second, or [`subsec_micros`] for microseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::get_nanos;
+///
+/// let nanos = get_nanos();
+/// println!("Nanoseconds within the millisecond: {}", nanos);
+/// assert!(nanos < 1_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the nanoseconds within the current millisecond as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn get_nanos() -> u64 {
+    let nanos = duration_since().as_nanos() % 1_000_000;
+    nanos as u64
+}
+
+/// ### subsec_nanos() -> u64
+///
+/// Retrieves the nanoseconds elapsed within the current second.
+///
+/// Unlike [`get_nanos`], this is the whole fractional part of the second, in the range
+/// 0-999,999,999, so `subsec_nanos() / 1_000_000` is the millisecond and
+/// `subsec_nanos() % 1_000_000` the remainder that `get_nanos` returns.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::subsec_nanos;
+///
+/// let nanos = subsec_nanos();
+/// println!("Nanoseconds within the second: {}", nanos);
+/// assert!(nanos < 1_000_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the nanoseconds within the current second as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn subsec_nanos() -> u64 {
+    u64::from(duration_since().subsec_nanos())
+}
+
+/// ### subsec_micros() -> u64
+///
+/// Retrieves the microseconds elapsed within the current second.
+///
+/// This is [`subsec_nanos`] truncated to microseconds, in the range 0-999,999.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::subsec_micros;
+///
+/// let micros = subsec_micros();
+/// println!("Microseconds within the second: {}", micros);
+/// assert!(micros < 1_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the microseconds within the current second as a `u64`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "std")]
+pub fn subsec_micros() -> u64 {
+    u64::from(duration_since().subsec_micros())
+}
+
+/// ### Snapshot
+///
+/// Every component of one instant, read from the clock a single time.
+///
+/// Calling `get_minute()` and then `get_second()` reads the clock twice, so near a minute
+/// boundary the two can come from different instants and combine into a time that never
+/// happened. A `Snapshot` reads the clock once and derives all of its components from that
+/// reading, so they always agree. Its `Display` implementation writes the crate's
+/// `year-month-day-hour-minute-second-millis-nanos` layout.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::calc::Snapshot;
+///
+/// # #[cfg(feature = "std")]
+/// # {
+/// let now = Snapshot::now();
+/// println!("{:02}:{:02}:{:02}.{:03}", now.hour(), now.minute(), now.second(), now.millis());
+/// # }
+///
+/// let snapshot = Snapshot::from_duration(Duration::new(1728933069, 123_456_789), 330);
+/// assert_eq!(snapshot.date(), (2024, 10, 15));
+/// assert_eq!(snapshot.time(), (0, 41, 9));
+/// assert_eq!(snapshot.millis(), 123);
+/// assert_eq!(snapshot.nanos(), 456_789);
+/// assert_eq!(snapshot.subsec_nanos(), 123_456_789);
+/// assert_eq!(snapshot.to_string(), "2024-10-15-00-41-09-123-456789");
+///
+/// // Before 1970 at this offset: clamped to the start of 1970, in the fields and the text
+/// let epoch = Snapshot::from_duration(Duration::ZERO, -60);
+/// assert_eq!(epoch.date(), (1970, 1, 1));
+/// assert_eq!(epoch.to_string(), "1970-01-01-00-00-00-000-000000");
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    since: Duration,
+    offset_minutes: i64,
+    date: (u64, u64, u64),
+    secs_of_day: u64,
+}
+
+impl Snapshot {
+    /// Reads the system clock once, in UTC.
+    #[cfg(feature = "std")]
+    pub fn now() -> Self {
+        Snapshot::from_duration(duration_since(), 0)
+    }
+
+    /// Captures the time `since` the UNIX epoch, shown at a UTC offset in minutes. Wall-clock
+    /// times before 1970 are clamped to its start, and times after [`MAX_TS`] to that instant.
+    pub fn from_duration(since: Duration, offset_minutes: i64) -> Self {
+        let wall = Snapshot::wall(since, offset_minutes);
+        Snapshot {
+            since,
+            offset_minutes,
+            date: calc_date(wall),
+            secs_of_day: wall % 86_400,
+        }
+    }
+
+    /// The wall-clock seconds since the epoch, with the offset clamped to less than a day.
+    fn wall(since: Duration, offset_minutes: i64) -> u64 {
+        since
+            .as_secs()
+            .min(MAX_TS)
+            .saturating_add_signed(clamp_offset(offset_minutes) * 60)
+    }
+
+    /// Returns the time since the UNIX epoch.
+    pub fn since_epoch(&self) -> Duration {
+        self.since
+    }
+
+    /// Returns the UNIX timestamp in seconds.
+    pub fn ts(&self) -> u64 {
+        self.since.as_secs()
+    }
+
+    /// Returns the UTC offset in minutes.
+    pub fn offset_minutes(&self) -> i64 {
+        self.offset_minutes
+    }
+
+    /// Returns the `(year, month, day)`.
+    pub fn date(&self) -> (u64, u64, u64) {
+        self.date
+    }
+
+    /// Returns the year.
+    pub fn year(&self) -> u64 {
+        self.date.0
+    }
+
+    /// Returns the month, 1-12.
+    pub fn month(&self) -> u64 {
+        self.date.1
+    }
+
+    /// Returns the day of the month.
+    pub fn day(&self) -> u64 {
+        self.date.2
+    }
+
+    /// Returns the day of the week.
+    pub fn weekday(&self) -> Weekday {
+        let (year, month, day) = self.date;
+        weekday_of(year, month, day)
+    }
+
+    /// Returns the ISO 8601 week number, 1-53.
+    pub fn week(&self) -> u64 {
+        calc_week(self.date)
+    }
+
+    /// Returns the `(hour, minute, second)`.
+    pub fn time(&self) -> (u64, u64, u64) {
+        (self.hour(), self.minute(), self.second())
+    }
+
+    /// Returns the hour, 0-23.
+    pub fn hour(&self) -> u64 {
+        self.secs_of_day / 3600
+    }
+
+    /// Returns the minute, 0-59.
+    pub fn minute(&self) -> u64 {
+        self.secs_of_day / 60 % 60
+    }
+
+    /// Returns the second, 0-59.
+    pub fn second(&self) -> u64 {
+        self.secs_of_day % 60
+    }
+
+    /// Returns the millisecond within the second, 0-999.
+    pub fn millis(&self) -> u64 {
+        u64::from(self.since.subsec_millis())
+    }
+
+    /// Returns the nanoseconds within the millisecond, 0-999,999, like `get_nanos`.
+    pub fn nanos(&self) -> u64 {
+        u64::from(self.since.subsec_nanos() % 1_000_000)
+    }
+
+    /// Returns the microseconds within the second, 0-999,999.
+    pub fn subsec_micros(&self) -> u64 {
+        u64::from(self.since.subsec_micros())
+    }
+
+    /// Returns the nanoseconds within the second, 0-999,999,999.
+    pub fn subsec_nanos(&self) -> u64 {
+        u64::from(self.since.subsec_nanos())
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The offset that lands on the clamped wall-clock time the fields were derived from
+        let wall = Snapshot::wall(self.since, self.offset_minutes) as i64;
+        let offset_secs = wall - self.since.as_secs().min(MAX_TS) as i64;
+        write_wtime_ts(f, self.since, offset_secs)
+    }
+}
+
+/// ### Weekday
+///
+/// A day of the week.
+///
+/// The numeric conversions follow ISO 8601, where Monday is day 1 and Sunday day 7, so
+/// `Weekday::try_from(1)` is `Monday`. Use [`Weekday::number_from_sunday`] for the
+/// Sunday-first numbering common in the United States.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{weekday_of, Weekday};
+///
+/// let day = weekday_of(2024, 10, 14);
+/// assert_eq!(day, Weekday::Monday);
+/// assert_eq!(day.name(), "Monday");
+/// assert_eq!(day.short_name(), "Mon");
+/// assert_eq!(day.number_from_monday(), 1);
+/// assert_eq!(day.number_from_sunday(), 2);
+/// assert_eq!(Weekday::try_from(7), Ok(Weekday::Sunday));
+/// assert!(Weekday::try_from(8).is_err());
+///
+/// match weekday_of(2024, 10, 19) {
+///     Weekday::Saturday | Weekday::Sunday => println!("Weekend"),
+///     _ => println!("Working day"),
+/// }
+/// ```
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// All weekdays in order, starting with Monday.
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Returns the weekday of a count of days since 1970-01-01, which was a Thursday.
+    pub(crate) fn from_days(days: i64) -> Self {
+        Weekday::ALL[(days + 3).rem_euclid(7) as usize]
+    }
+
+    /// Returns the English name, e.g. `"Monday"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    /// Returns the three-letter English abbreviation, e.g. `"Mon"`.
+    pub fn short_name(&self) -> &'static str {
+        &self.name()[..3]
+    }
+
+    /// Returns the ISO 8601 day number, from 1 for Monday to 7 for Sunday.
+    pub fn number_from_monday(&self) -> u64 {
+        *self as u64 + 1
+    }
+
+    /// Returns the day number counting from 1 for Sunday to 7 for Saturday.
+    pub fn number_from_sunday(&self) -> u64 {
+        (*self as u64 + 1) % 7 + 1
+    }
+
+    /// Returns the following day, wrapping from Sunday to Monday.
+    pub fn next(&self) -> Self {
+        Weekday::ALL[(*self as usize + 1) % 7]
+    }
+
+    /// Returns the preceding day, wrapping from Monday to Sunday.
+    pub fn previous(&self) -> Self {
+        Weekday::ALL[(*self as usize + 6) % 7]
+    }
+}
+
+impl TryFrom<u64> for Weekday {
+    type Error = WTimeError;
+
+    /// Converts an ISO 8601 day number, 1 for Monday to 7 for Sunday.
+    fn try_from(number: u64) -> Result<Self, Self::Error> {
+        match number {
+            1..=7 => Ok(Weekday::ALL[number as usize - 1]),
+            _ => Err(WTimeError::OutOfRange("weekday")),
+        }
+    }
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// ### weekday_of(year: u64, month: u64, day: u64) -> Weekday
+///
+/// Calculates the day of the week of a calendar date.
+///
+/// The proleptic Gregorian calendar is used for every year. The date is not validated; an
+/// impossible date such as February 30 is counted forward from the start of the month.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{weekday_of, Weekday};
+///
+/// assert_eq!(weekday_of(2024, 10, 14), Weekday::Monday);
+/// assert_eq!(weekday_of(1970, 1, 1), Weekday::Thursday);
+/// assert_eq!(weekday_of(2000, 2, 29), Weekday::Tuesday);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the [`Weekday`] of the date.
+///
+/// <small>End Fun Doc</small>
+pub fn weekday_of(year: u64, month: u64, day: u64) -> Weekday {
+    // Weekdays repeat every 400 years, so folding the year keeps any input in range
+    Weekday::from_days(days_from_civil((year % 400) as i64, month, day))
+}
+
+/// ### next_weekday(after_date: (u64, u64, u64), weekday: Weekday) -> (u64, u64, u64)
+///
+/// Finds the first date after `after_date` that falls on a given weekday.
+///
+/// The search starts the day after, so the result is one to seven days later: the next
+/// Monday after a Monday is a week away. Results after the end of [`MAX_YEAR`] saturate at
+/// its December 31.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{next_weekday, Weekday};
+///
+/// let monday = (2024, 10, 14);
+/// assert_eq!(next_weekday(monday, Weekday::Friday), (2024, 10, 18));
+/// assert_eq!(next_weekday(monday, Weekday::Monday), (2024, 10, 21));
+/// assert_eq!(next_weekday((2024, 12, 31), Weekday::Thursday), (2025, 1, 2));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date as a `(year, month, day)` tuple.
+///
+/// <small>End Fun Doc</small>
+pub fn next_weekday(after_date: (u64, u64, u64), weekday: Weekday) -> (u64, u64, u64) {
+    let after_date = clamp_date(after_date);
+    let (year, month, day) = after_date;
+    let current = weekday_of(year, month, day) as i64;
+    add_days(after_date, (weekday as i64 - current - 1).rem_euclid(7) + 1)
+}
+
+/// ### previous_weekday(before_date: (u64, u64, u64), weekday: Weekday) -> (u64, u64, u64)
+///
+/// Finds the last date before `before_date` that falls on a given weekday.
+///
+/// The search starts the day before, so the result is one to seven days earlier. Results
+/// before 0000-01-01 saturate at that date, and dates after the end of [`MAX_YEAR`] are
+/// treated as its December 31.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{previous_weekday, Weekday};
+///
+/// let monday = (2024, 10, 14);
+/// assert_eq!(previous_weekday(monday, Weekday::Friday), (2024, 10, 11));
+/// assert_eq!(previous_weekday(monday, Weekday::Monday), (2024, 10, 7));
+/// assert_eq!(previous_weekday((2025, 1, 2), Weekday::Sunday), (2024, 12, 29));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date as a `(year, month, day)` tuple.
+///
+/// <small>End Fun Doc</small>
+pub fn previous_weekday(before_date: (u64, u64, u64), weekday: Weekday) -> (u64, u64, u64) {
+    let before_date = clamp_date(before_date);
+    let (year, month, day) = before_date;
+    let current = weekday_of(year, month, day) as i64;
+    add_days(
+        before_date,
+        -((current - weekday as i64 - 1).rem_euclid(7) + 1),
+    )
+}
+
+/// ### nth_weekday_of_month(year: u64, month: u64, n: i64, weekday: Weekday) -> Result<(u64, u64, u64), WTimeError>
+///
+/// Finds the `n`th occurrence of a weekday in a month, such as the second Tuesday.
+///
+/// `n` counts from 1 at the start of the month, or from -1 at the end, so `-1` is the last
+/// such weekday of the month. This is how floating holidays and recurring meetings are
+/// usually defined: US Thanksgiving is the fourth Thursday of November and Memorial Day the
+/// last Monday of May.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{nth_weekday_of_month, Weekday};
+///
+/// assert_eq!(nth_weekday_of_month(2024, 10, 2, Weekday::Tuesday), Ok((2024, 10, 8)));
+/// assert_eq!(nth_weekday_of_month(2024, 11, 4, Weekday::Thursday), Ok((2024, 11, 28)));
+/// assert_eq!(nth_weekday_of_month(2024, 5, -1, Weekday::Monday), Ok((2024, 5, 27)));
+///
+/// // February 2024 has five Thursdays but only four Fridays
+/// assert_eq!(nth_weekday_of_month(2024, 2, 5, Weekday::Thursday), Ok((2024, 2, 29)));
+/// assert!(nth_weekday_of_month(2024, 2, 5, Weekday::Friday).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date as a `(year, month, day)` tuple.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for a month outside 1-12, for an `n` of zero, and when
+/// the month has fewer than `n` such weekdays.
+///
+/// <small>End Fun Doc</small>
+pub fn nth_weekday_of_month(
+    year: u64,
+    month: u64,
+    n: i64,
+    weekday: Weekday,
+) -> Result<(u64, u64, u64), WTimeError> {
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    if n == 0 || n.unsigned_abs() > 5 {
+        return Err(WTimeError::OutOfRange("n"));
+    }
+    let last = days_in_month(year, month) as i64;
+    let day = if n > 0 {
+        let first = weekday_of(year, month, 1) as i64;
+        1 + (weekday as i64 - first).rem_euclid(7) + 7 * (n - 1)
+    } else {
+        let final_weekday = weekday_of(year, month, last as u64) as i64;
+        last - (final_weekday - weekday as i64).rem_euclid(7) - 7 * (-n - 1)
+    };
+    if !(1..=last).contains(&day) {
+        return Err(WTimeError::OutOfRange("n"));
+    }
+    Ok((year, month, day as u64))
+}
+
+/// ### Month
+///
+/// A month of the year.
+///
+/// Converting from a month number with `TryFrom<u64>` checks the range once, after which
+/// every method is infallible, unlike indexing an array with an unchecked integer.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::Month;
+///
+/// let month = Month::try_from(2).unwrap();
+/// assert_eq!(month, Month::February);
+/// assert_eq!(month.to_string(), "February");
+/// assert_eq!(month.short_name(), "Feb");
+/// assert_eq!(month.number(), 2);
+/// assert_eq!(month.days_in(2024), 29);
+/// assert_eq!(month.days_in(2023), 28);
+/// assert!(Month::try_from(13).is_err());
+/// ```
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Month {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl Month {
+    /// All months in order, starting with January.
+    pub const ALL: [Month; 12] = [
+        Month::January,
+        Month::February,
+        Month::March,
+        Month::April,
+        Month::May,
+        Month::June,
+        Month::July,
+        Month::August,
+        Month::September,
+        Month::October,
+        Month::November,
+        Month::December,
+    ];
+
+    /// Returns the English name, e.g. `"January"`.
+    pub fn name(&self) -> &'static str {
+        get_month_name(self.number())
+    }
+
+    /// Returns the three-letter English abbreviation, e.g. `"Jan"`.
+    pub fn short_name(&self) -> &'static str {
+        &self.name()[..3]
+    }
+
+    /// Returns the month number, from 1 for January to 12 for December.
+    pub fn number(&self) -> u64 {
+        *self as u64 + 1
+    }
+
+    /// Returns the number of days in the month of the given year.
+    pub fn days_in(&self, year: u64) -> u64 {
+        days_in_month(year, self.number())
+    }
+
+    /// Returns the following month, wrapping from December to January.
+    pub fn next(&self) -> Self {
+        Month::ALL[(*self as usize + 1) % 12]
+    }
+
+    /// Returns the preceding month, wrapping from January to December.
+    pub fn previous(&self) -> Self {
+        Month::ALL[(*self as usize + 11) % 12]
+    }
+}
+
+impl TryFrom<u64> for Month {
+    type Error = WTimeError;
+
+    /// Converts a month number, 1 for January to 12 for December.
+    fn try_from(number: u64) -> Result<Self, Self::Error> {
+        match number {
+            1..=12 => Ok(Month::ALL[number as usize - 1]),
+            _ => Err(WTimeError::OutOfRange("month")),
+        }
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// ### CalendarNames
+///
+/// Month and weekday names in one language.
+///
+/// [`Locale`](crate::config::Locale) implements this trait for the languages that ship with
+/// the crate; applications add a language by implementing it for their own type, and pass
+/// either to [`get_month_name_in`] and [`get_day_name_in`]. The abbreviations default to the
+/// full names, which suits languages that do not abbreviate them.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{get_month_name_in, CalendarNames, Month, Weekday};
+///
+/// struct French;
+///
+/// impl CalendarNames for French {
+///     fn month_name(&self, month: Month) -> &str {
+///         const MONTHS: [&str; 12] = [
+///             "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août",
+///             "septembre", "octobre", "novembre", "décembre",
+///         ];
+///         MONTHS[month as usize]
+///     }
+///
+///     fn day_name(&self, weekday: Weekday) -> &str {
+///         const DAYS: [&str; 7] =
+///             ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"];
+///         DAYS[weekday as usize]
+///     }
+/// }
+///
+/// assert_eq!(get_month_name_in(8, &French), Ok("août"));
+/// assert_eq!(French.day_abbr(Weekday::Monday), "lundi");
+/// ```
+///
+/// <small>End Trait Doc</small>
+pub trait CalendarNames {
+    /// Returns the full name of a month.
+    fn month_name(&self, month: Month) -> &str;
+
+    /// Returns the abbreviated name of a month, the full name unless overridden.
+    fn month_abbr(&self, month: Month) -> &str {
+        self.month_name(month)
+    }
+
+    /// Returns the full name of a weekday.
+    fn day_name(&self, weekday: Weekday) -> &str;
+
+    /// Returns the abbreviated name of a weekday, the full name unless overridden.
+    fn day_abbr(&self, weekday: Weekday) -> &str {
+        self.day_name(weekday)
+    }
+}
+
+/// ### Weekend
+///
+/// The set of weekdays that are not business days.
+///
+/// The default is Saturday and Sunday. Other conventions, such as the Friday-Saturday
+/// weekend of much of the Middle East, are built with [`Weekend::new`], and their methods
+/// mirror [`is_business_day`], [`add_business_days`] and [`business_days_between`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{Weekday, Weekend};
+///
+/// let weekend = Weekend::new(&[Weekday::Friday, Weekday::Saturday]);
+/// assert!(weekend.contains(Weekday::Friday));
+/// assert!(weekend.is_business_day((2024, 10, 13))); // a Sunday
+/// assert_eq!(weekend.add_business_days((2024, 10, 17), 1), (2024, 10, 20));
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Weekend {
+    /// One bit per weekday, Monday in the lowest bit.
+    days: u8,
+}
+
+impl Weekend {
+    /// Saturday and Sunday, the default.
+    pub const SATURDAY_SUNDAY: Weekend = Weekend::new(&[Weekday::Saturday, Weekday::Sunday]);
+
+    /// Creates a weekend from its days.
+    pub const fn new(days: &[Weekday]) -> Self {
+        let mut bits = 0;
+        let mut i = 0;
+        while i < days.len() {
+            bits |= 1 << (days[i] as u8);
+            i += 1;
+        }
+        Weekend { days: bits }
+    }
+
+    /// Returns `true` if `weekday` is part of the weekend.
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.days & (1 << (weekday as u8)) != 0
+    }
+
+    /// Returns `true` if the date is a business day for this weekend.
+    pub fn is_business_day(&self, date: (u64, u64, u64)) -> bool {
+        let (year, month, day) = date;
+        !self.contains(weekday_of(year, month, day))
+    }
+
+    /// Moves a date by `n` business days for this weekend; see [`add_business_days`].
+    pub fn add_business_days(&self, date: (u64, u64, u64), n: i64) -> (u64, u64, u64) {
+        let per_week = self.business_days_per_week();
+        let date = clamp_date(date);
+        if n == 0 || per_week == 0 {
+            return date;
+        }
+        let (year, month, day) = date;
+        let mut days = days_from_civil(year as i64, month, day);
+        let n = n.clamp(-MAX_DAYS, MAX_DAYS);
+        // Skip whole weeks at once, leaving at least one business day to step to so a
+        // weekend start still lands on a business day
+        let step = n.signum();
+        let mut weeks = n.abs() / per_week;
+        let mut remaining = n.abs() % per_week;
+        if remaining == 0 {
+            weeks -= 1;
+            remaining = per_week;
+        }
+        days = days.saturating_add(step * weeks * 7);
+        while remaining > 0 {
+            days += step;
+            if !self.contains(Weekday::from_days(days)) {
+                remaining -= 1;
+            }
+        }
+        saturate_date(civil_from_days(days))
+    }
+
+    /// Counts the business days after `start` up to and including `end` for this weekend;
+    /// see [`business_days_between`].
+    pub fn business_days_between(&self, start: (u64, u64, u64), end: (u64, u64, u64)) -> i64 {
+        let (start_year, start_month, start_day) = clamp_date(start);
+        let (end_year, end_month, end_day) = clamp_date(end);
+        let start = days_from_civil(start_year as i64, start_month, start_day);
+        let end = days_from_civil(end_year as i64, end_month, end_day);
+        self.business_days_before(end + 1) - self.business_days_before(start + 1)
+    }
+
+    fn business_days_per_week(&self) -> i64 {
+        7 - i64::from(self.days.count_ones())
+    }
+
+    /// Counts the business days from the Monday 1969-12-29 up to, excluding, day `days`.
+    fn business_days_before(&self, days: i64) -> i64 {
+        let since_monday = days + 3;
+        let weeks = since_monday.div_euclid(7);
+        let partial = (0..since_monday.rem_euclid(7))
+            .filter(|weekday| self.days & (1 << weekday) == 0)
+            .count() as i64;
+        weeks * self.business_days_per_week() + partial
+    }
+}
+
+impl Default for Weekend {
+    fn default() -> Self {
+        Weekend::SATURDAY_SUNDAY
+    }
+}
+
+/// ### is_business_day(date: (u64, u64, u64)) -> bool
+///
+/// Checks whether a `(year, month, day)` date falls on a business day, Monday to Friday.
+///
+/// Public holidays are not known to the crate. Use [`Weekend`] for other weekend days.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::is_business_day;
+///
+/// assert!(is_business_day((2024, 10, 14))); // Monday
+/// assert!(!is_business_day((2024, 10, 19))); // Saturday
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` for Monday to Friday.
+///
+/// <small>End Fun Doc</small>
+pub fn is_business_day(date: (u64, u64, u64)) -> bool {
+    Weekend::default().is_business_day(date)
+}
+
+/// ### is_weekend(year: u64, month: u64, day: u64) -> bool
+///
+/// Checks whether a date falls on a Saturday or a Sunday.
+///
+/// Use [`Weekend::contains`] with [`weekday_of`] for other weekend days.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::is_weekend;
+///
+/// assert!(is_weekend(2024, 10, 19)); // Saturday
+/// assert!(is_weekend(2024, 10, 20)); // Sunday
+/// assert!(!is_weekend(2024, 10, 14)); // Monday
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` for Saturday and Sunday.
+///
+/// <small>End Fun Doc</small>
+pub fn is_weekend(year: u64, month: u64, day: u64) -> bool {
+    !is_business_day((year, month, day))
+}
+
+/// ### is_same_day(ts_a: u64, ts_b: u64) -> bool
+///
+/// Checks whether two UNIX timestamps in seconds fall on the same UTC calendar day.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::is_same_day;
+///
+/// assert!(is_same_day(1728864000, 1728950399)); // 2024-10-14 00:00:00 and 23:59:59
+/// assert!(!is_same_day(1728950399, 1728950400)); // one second apart, across midnight
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if both timestamps share a UTC date.
+///
+/// <small>End Fun Doc</small>
+pub fn is_same_day(ts_a: u64, ts_b: u64) -> bool {
+    ts_a / 86_400 == ts_b / 86_400
+}
+
+/// ### add_business_days(date: (u64, u64, u64), n: i64) -> (u64, u64, u64)
+///
+/// Moves a `(year, month, day)` date forwards or backwards by a number of business days,
+/// Monday to Friday.
+///
+/// Weekend days are skipped, so the result is always a business day unless `n` is 0, in
+/// which case the date is returned unchanged. Starting on a weekend counts from that day:
+/// one business day after a Saturday is the next Monday. A negative `n` moves backwards.
+/// Whole weeks are skipped at once, so large values are cheap. Use [`Weekend`] for other
+/// weekend days.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::add_business_days;
+///
+/// // Friday + 1 business day = Monday
+/// assert_eq!(add_business_days((2024, 10, 18), 1), (2024, 10, 21));
+/// // SLA of 10 business days from Wednesday, October 9
+/// assert_eq!(add_business_days((2024, 10, 9), 10), (2024, 10, 23));
+/// assert_eq!(add_business_days((2024, 10, 19), 1), (2024, 10, 21));
+/// assert_eq!(add_business_days((2024, 10, 21), -1), (2024, 10, 18));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the resulting date as a `(year, month, day)` tuple.
+///
+/// <small>End Fun Doc</small>
+pub fn add_business_days(date: (u64, u64, u64), n: i64) -> (u64, u64, u64) {
+    Weekend::default().add_business_days(date, n)
+}
+
+/// ### business_days_between(start: (u64, u64, u64), end: (u64, u64, u64)) -> i64
+///
+/// Counts the business days, Monday to Friday, after `start` up to and including `end`.
+///
+/// This is the inverse of [`add_business_days`]: when `end` is a business day,
+/// `add_business_days(start, business_days_between(start, end)) == end`. The count is
+/// negative when `end` is before `start`. Dates after [`MAX_YEAR`] count as its December 31.
+/// Use [`Weekend`] for other weekend days.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{add_business_days, business_days_between, MAX_YEAR};
+///
+/// // Monday to the next Monday
+/// assert_eq!(business_days_between((2024, 10, 14), (2024, 10, 21)), 5);
+/// // Friday to Monday
+/// assert_eq!(business_days_between((2024, 10, 18), (2024, 10, 21)), 1);
+/// assert_eq!(business_days_between((2024, 10, 21), (2024, 10, 18)), -1);
+///
+/// let start = (2024, 10, 9);
+/// let due = add_business_days(start, 10);
+/// assert_eq!(business_days_between(start, due), 10);
+///
+/// assert_eq!(
+///     business_days_between((2024, 1, 1), (u64::MAX, 1, 1)),
+///     business_days_between((2024, 1, 1), (MAX_YEAR, 12, 31))
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns the number of business days as an `i64`.
+///
+/// <small>End Fun Doc</small>
+pub fn business_days_between(start: (u64, u64, u64), end: (u64, u64, u64)) -> i64 {
+    Weekend::default().business_days_between(start, end)
+}
+
+/// ### Unit
+///
+/// A calendar unit that timestamps are truncated or rounded to by [`truncate_to`] and
+/// [`round_to`].
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Unit {
+    Minute,
+    Hour,
+    Day,
+    /// An ISO 8601 week, starting on Monday. `utc::start_of_week` and
+    /// `local::start_of_local_week` truncate to weeks starting on another day, such as the
+    /// configured `WeekStart::default()`.
+    Week,
+    Month,
+    Year,
+}
+
+/// ### truncate_to(ts: u64, unit: Unit) -> u64
+///
+/// Rounds a UNIX timestamp down to the start of its minute, hour, day, week, month or year,
+/// in UTC.
+///
+/// Weeks start on Monday, as in ISO 8601; `utc::start_of_week(ts, WeekStart::default())`
+/// truncates to the configured week start instead. The week of 1970-01-01, which started in
+/// 1969, is truncated to the epoch itself.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{truncate_to, Unit};
+///
+/// let ts = 1728933069; // Monday 2024-10-14 19:11:09 UTC
+/// assert_eq!(truncate_to(ts, Unit::Hour), 1728932400); // 19:00
+/// assert_eq!(truncate_to(ts, Unit::Day), 1728864000); // 2024-10-14
+/// assert_eq!(truncate_to(ts, Unit::Week), 1728864000); // the same Monday
+/// assert_eq!(truncate_to(ts, Unit::Month), 1727740800); // 2024-10-01
+/// assert_eq!(truncate_to(ts, Unit::Year), 1704067200); // 2024-01-01
+///
+/// // Bucketing events into hourly windows
+/// let events = [1728932400, 1728933069, 1728936001];
+/// let buckets: Vec<_> = events.iter().map(|&ts| truncate_to(ts, Unit::Hour)).collect();
+/// assert_eq!(buckets, [1728932400, 1728932400, 1728936000]);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of the start of the unit.
+///
+/// <small>End Fun Doc</small>
+pub fn truncate_to(ts: u64, unit: Unit) -> u64 {
+    unit_bounds(ts.min(MAX_TS) as i64, unit, Weekday::Monday)
+        .0
+        .max(0) as u64
+}
+
+/// ### round_to(ts: u64, unit: Unit) -> u64
+///
+/// Rounds a UNIX timestamp to the nearest start of a minute, hour, day, week, month or year,
+/// in UTC.
+///
+/// A timestamp exactly halfway between two starts is rounded up. Months and years are
+/// rounded by their actual length, so the halfway point of February 2024 is noon on
+/// February 15.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{round_to, Unit};
+///
+/// let ts = 1728933069; // Monday 2024-10-14 19:11:09 UTC
+/// assert_eq!(round_to(ts, Unit::Minute), 1728933060); // 19:11
+/// assert_eq!(round_to(ts, Unit::Hour), 1728932400); // 19:00
+/// assert_eq!(round_to(ts, Unit::Day), 1728950400); // 2024-10-15
+/// assert_eq!(round_to(ts, Unit::Month), 1727740800); // 2024-10-01
+/// assert_eq!(round_to(ts, Unit::Year), 1735689600); // 2025-01-01
+/// assert_eq!(round_to(1728932400 + 1800, Unit::Hour), 1728936000); // halfway rounds up
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of the nearest start of the unit.
+///
+/// <small>End Fun Doc</small>
+pub fn round_to(ts: u64, unit: Unit) -> u64 {
+    let ts = ts.min(MAX_TS) as i64;
+    let (start, end) = unit_bounds(ts, unit, Weekday::Monday);
+    let nearest = if ts - start >= end - ts { end } else { start };
+    nearest.max(0) as u64
+}
+
+/// ### write_rfc3339<W: Write>(out: &mut W, ts: u64, offset_minutes: i64, nanos: u64, digits: usize) -> fmt::Result
+///
+/// Writes a UNIX timestamp as an RFC 3339 / ISO 8601 timestamp into a caller-supplied buffer.
+///
+/// Nothing is allocated, so hot logging paths can format millions of timestamps into one
+/// reused buffer, or into a fixed array on `no_std` targets. `nanos` is the sub-second part,
+/// of which the first `digits` (at most 9) digits are written; `0` writes no fraction. The
+/// offset is written as `Z` when it is zero and as `+HH:MM` or `-HH:MM` otherwise, and
+/// saturates at 23:59 either way. Timestamps after [`MAX_TS`] are written as that instant.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::write_rfc3339;
+///
+/// let mut line = String::with_capacity(64);
+/// for (ts, nanos) in [(1728933069, 123_456_789), (1728933070, 0)] {
+///     line.clear();
+///     write_rfc3339(&mut line, ts, 0, nanos, 3).unwrap();
+///     line.push_str(" INFO request served");
+/// }
+/// assert_eq!(line, "2024-10-14T19:11:10.000Z INFO request served");
+///
+/// let mut stamp = String::new();
+/// write_rfc3339(&mut stamp, 1728933069, 120, 0, 0).unwrap();
+/// assert_eq!(stamp, "2024-10-14T21:11:09+02:00");
+///
+/// stamp.clear();
+/// write_rfc3339(&mut stamp, 0, i64::MAX, 0, 0).unwrap();
+/// assert_eq!(stamp, "1970-01-01T23:59:00+23:59");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn write_rfc3339<W: Write>(
+    out: &mut W,
+    ts: u64,
+    offset_minutes: i64,
+    nanos: u64,
+    digits: usize,
+) -> fmt::Result {
+    let offset_minutes = clamp_offset(offset_minutes);
+    let wall = ts.min(MAX_TS) as i64 + offset_minutes * 60;
+    let (year, month, day) = civil_from_days(wall.div_euclid(86_400));
+    let secs_of_day = wall.rem_euclid(86_400);
+    write!(
+        out,
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )?;
+    if digits > 0 {
+        out.write_char('.')?;
+        write_fraction(out, nanos, digits)?;
+    }
+    write_offset_suffix(out, offset_minutes)
+}
+
+/// The Julian day number of 1970-01-01.
+const UNIX_EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+/// Returns the number of days in `month` (1-12) of a signed `year`, or 0 for an invalid month.
+pub(crate) fn month_length(year: i64, month: u64) -> u64 {
+    // Leap years repeat every 400 years, so any year maps onto a non-negative one
+    days_in_month(year.rem_euclid(400) as u64, month)
+}
+
+/// Converts a proleptic Gregorian date into days since 1970-01-01.
+///
+/// Constant-time algorithm after Howard Hinnant's `days_from_civil`.
+pub(crate) fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400); // [0, 399]
+    let month_index = (month as i64 + 9) % 12; // March = 0, ..., February = 11
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1; // [0, 365]
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year; // [0, 146096]
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts days since 1970-01-01 into a proleptic Gregorian `(year, month, day)`.
+///
+/// Constant-time algorithm after Howard Hinnant's `civil_from_days`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u64, u64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097); // [0, 146096]
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365; // [0, 399]
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100); // [0, 365]
+    let month_index = (5 * day_of_year + 2) / 153; // March = 0, ..., February = 11
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u64;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u64;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Returns the English weekday name for a count of days since 1970-01-01.
+#[cfg_attr(not(feature = "format"), allow(dead_code))]
+pub(crate) fn day_name_from_days(days: i64) -> &'static str {
+    Weekday::from_days(days).name()
+}
+
+/// Moves a date by a number of calendar months, clamping the day to the end of the target
+/// month (January 31 + 1 month = February 28/29).
+pub(crate) fn shift_months(year: i64, month: u64, day: u64, months: i64) -> (i64, u64, u64) {
+    let month_index = year * 12 + month as i64 - 1 + months;
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) as u64 + 1;
+    (year, month, day.min(month_length(year, month)))
+}
+
+/// Converts a signed date into the public unsigned form, saturating at 0000-01-01 and at the
+/// last day of [`MAX_YEAR`].
+fn saturate_date((year, month, day): (i64, u64, u64)) -> (u64, u64, u64) {
+    match u64::try_from(year) {
+        Ok(year) if year > MAX_YEAR => (MAX_YEAR, 12, 31),
+        Ok(year) => (year, month, day),
+        Err(_) => (0, 1, 1),
+    }
+}
+
+/// Moves a date after [`MAX_YEAR`] back to the last day of that year, so that the civil
+/// conversions cannot overflow.
+fn clamp_date(date: (u64, u64, u64)) -> (u64, u64, u64) {
+    if date.0 > MAX_YEAR {
+        (MAX_YEAR, 12, 31)
+    } else {
+        date
+    }
+}
+
+/// Returns the start of the `unit` containing `wall` and the start of the next one, with weeks
+/// starting on `first_day`. All three count seconds since 1970-01-01 00:00 of the same clock,
+/// UTC or a local wall clock, and the bounds may lie before 1970.
+///
+/// This is the one implementation of calendar periods: truncation and rounding, the start
+/// of period functions of `utc` and `local`, range presets and date math all use it.
+pub(crate) fn unit_bounds(wall: i64, unit: Unit, first_day: Weekday) -> (i64, i64) {
+    let days = wall.div_euclid(86_400);
+    let (year, month, _) = civil_from_days(days);
+    let (start_days, end_days) = match unit {
+        Unit::Minute => return (wall - wall.rem_euclid(60), wall - wall.rem_euclid(60) + 60),
+        Unit::Hour => {
+            return (
+                wall - wall.rem_euclid(3600),
+                wall - wall.rem_euclid(3600) + 3600,
+            )
+        }
+        Unit::Day => (days, days + 1),
+        Unit::Week => {
+            let start = days - (Weekday::from_days(days) as i64 - first_day as i64).rem_euclid(7);
+            (start, start + 7)
+        }
+        Unit::Month => {
+            let (next_year, next_month, _) = shift_months(year, month, 1, 1);
+            (
+                days_from_civil(year, month, 1),
+                days_from_civil(next_year, next_month, 1),
+            )
+        }
+        Unit::Year => (days_from_civil(year, 1, 1), days_from_civil(year + 1, 1, 1)),
+    };
+    (start_days * 86_400, end_days * 86_400)
+}
+
+/// Writes the crate's `year-month-day-hour-minute-second-millis-nanos` layout for a time since
+/// the UNIX epoch, shifted by `offset_secs`, from a single reading of the clock. The `nanos`
+/// field holds the six digits of the sub-second part that follow the milliseconds.
+pub(crate) fn write_wtime_ts<W: Write>(
+    out: &mut W,
+    since: Duration,
+    offset_secs: i64,
+) -> fmt::Result {
+    write_wtime_fields(out, since, offset_secs, 9, '-', '-')
+}
+
+/// Writes the fields of the crate's timestamp layout with `subsec_digits` (0, 3, 6 or 9)
+/// digits of the sub-second part, `separator` between the fields and `date_time_separator`
+/// between the day and the hour. The milliseconds are always their own field, followed by
+/// the rest of the digits. Times after [`MAX_TS`] are written as that instant.
+///
+/// This is the one writer behind `format_utc_ts`, `format_local_ts` and their variants with
+/// `utc::FormatOptions`, `format::format_ts` and the `Display` of `Snapshot`.
+pub(crate) fn write_wtime_fields<W: Write>(
+    out: &mut W,
+    since: Duration,
+    offset_secs: i64,
+    subsec_digits: u32,
+    separator: char,
+    date_time_separator: char,
+) -> fmt::Result {
+    let (sep, dt_sep) = (separator, date_time_separator);
+    let wall = since.as_secs().min(MAX_TS) as i64 + offset_secs;
+    let (year, month, day) = civil_from_days(wall.div_euclid(86_400));
+    let secs_of_day = wall.rem_euclid(86_400);
+    let nanos = since.subsec_nanos();
+    write!(
+        out,
+        "{:04}{sep}{:02}{sep}{:02}{dt_sep}{:02}{sep}{:02}{sep}{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )?;
+    match subsec_digits {
+        0 => Ok(()),
+        3 => write!(out, "{sep}{:03}", nanos / 1_000_000),
+        6 => write!(
+            out,
+            "{sep}{:03}{sep}{:03}",
+            nanos / 1_000_000,
+            nanos / 1_000 % 1_000
+        ),
+        _ => write!(
+            out,
+            "{sep}{:03}{sep}{:06}",
+            nanos / 1_000_000,
+            nanos % 1_000_000
+        ),
+    }
+}
+
+/// Writes a UTC offset in minutes as `+HHMM` (or `+HH:MM` with `separator = ":"`).
+pub(crate) fn write_offset<W: Write>(
+    out: &mut W,
+    offset_minutes: i64,
+    separator: &str,
+) -> fmt::Result {
+    let offset_minutes = clamp_offset(offset_minutes);
+    let sign = if offset_minutes < 0 { '-' } else { '+' };
+    let minutes = offset_minutes.unsigned_abs();
+    write!(
+        out,
+        "{}{:02}{}{:02}",
+        sign,
+        minutes / 60,
+        separator,
+        minutes % 60
+    )
+}
+
+/// Limits a UTC offset in minutes to less than 24 hours either way, the range every offset
+/// format can represent.
+pub(crate) fn clamp_offset(offset_minutes: i64) -> i64 {
+    offset_minutes.clamp(-(24 * 60 - 1), 24 * 60 - 1)
+}
+
+/// Writes the offset suffix of RFC 3339: `Z` for UTC, and `+HH:MM` or `-HH:MM` otherwise.
+pub(crate) fn write_offset_suffix<W: Write>(out: &mut W, offset_minutes: i64) -> fmt::Result {
+    if offset_minutes == 0 {
+        out.write_char('Z')
+    } else {
+        write_offset(out, offset_minutes, ":")
+    }
+}
+
+/// Writes the first `digits` (at most 9) decimal digits of a sub-second part in nanoseconds.
+pub(crate) fn write_fraction<W: Write>(out: &mut W, nanos: u64, digits: usize) -> fmt::Result {
+    let digits = digits.min(9);
+    let scaled = nanos % 1_000_000_000 / 10u64.pow(9 - digits as u32);
+    write!(out, "{:0width$}", scaled, width = digits)
+}
+
+/// A `fmt::Write` sink over a fixed-size byte buffer, used by the array-returning formatters.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) struct ArrayWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl<'a> ArrayWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        ArrayWriter { buf, len: 0 }
+    }
+}
+
+impl Write for ArrayWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
 }
@@ -0,0 +1,206 @@
+use super::{error::WTimeError, format::atom_date, parse::parse_rfc3339_parts};
+
+/// CBOR tag for a standard date/time string (RFC 3339).
+pub const TAG_DATETIME_STRING: u64 = 0;
+/// CBOR tag for an epoch-based date/time (seconds since the UNIX epoch).
+pub const TAG_EPOCH_DATETIME: u64 = 1;
+
+/// Writes the initial byte(s) of a CBOR data item with the shortest argument encoding.
+fn write_head(major_type: u8, argument: u64, out: &mut Vec<u8>) {
+    let major_type = major_type << 5;
+    match argument {
+        0..=23 => out.push(major_type | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major_type | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major_type | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major_type | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major_type | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+/// Reads the initial byte(s) of a CBOR data item, returning the major type, the
+/// additional information, the argument and the remaining bytes.
+fn read_head(bytes: &[u8]) -> Result<(u8, u8, u64, &[u8]), WTimeError> {
+    const EXPECTED: &str = "a well-formed CBOR data item";
+
+    let (&initial, rest) = bytes
+        .split_first()
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let (major_type, info) = (initial >> 5, initial & 0x1f);
+    let width = match info {
+        0..=23 => return Ok((major_type, info, info as u64, rest)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+    };
+    if rest.len() < width {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let argument = rest[..width]
+        .iter()
+        .fold(0u64, |argument, &byte| argument << 8 | byte as u64);
+    Ok((major_type, info, argument, &rest[width..]))
+}
+
+/// ### encode_tag0(ts: u64, offset_minutes: i64) -> Vec<u8>
+///
+/// Encodes a UNIX timestamp as a CBOR tag 0 data item: an RFC 3339 date/time string.
+///
+/// The string shows the wall-clock time in the given UTC offset, `Z` for UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::cbor::encode_tag0;
+///
+/// let encoded = encode_tag0(1728933069, 0);
+/// assert_eq!(encoded[..2], [0xc0, 0x74]); // tag 0, text string of 20 bytes
+/// assert_eq!(&encoded[2..], b"2024-10-14T19:11:09Z");
+/// ```
+///
+/// ### Returns
+///
+/// Returns the encoded bytes.
+///
+/// <small>End Fun Doc</small>
+pub fn encode_tag0(ts: u64, offset_minutes: i64) -> Vec<u8> {
+    let text = atom_date(ts, offset_minutes);
+    let mut out = Vec::with_capacity(text.len() + 3);
+    write_head(6, TAG_DATETIME_STRING, &mut out);
+    write_head(3, text.len() as u64, &mut out);
+    out.extend_from_slice(text.as_bytes());
+    out
+}
+
+/// ### encode_tag1(ts: u64) -> Vec<u8>
+///
+/// Encodes a UNIX timestamp as a CBOR tag 1 data item holding integer epoch seconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::cbor::encode_tag1;
+///
+/// assert_eq!(encode_tag1(1728933069), [0xc1, 0x1a, 0x67, 0x0d, 0x6c, 0xcd]);
+/// assert_eq!(encode_tag1(0), [0xc1, 0x00]);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the encoded bytes.
+///
+/// <small>End Fun Doc</small>
+pub fn encode_tag1(ts: u64) -> Vec<u8> {
+    let mut out = Vec::with_capacity(10);
+    write_head(6, TAG_EPOCH_DATETIME, &mut out);
+    write_head(0, ts, &mut out);
+    out
+}
+
+/// ### encode_tag1_float(ts: u64, nanos: u64) -> Vec<u8>
+///
+/// Encodes a UNIX timestamp as a CBOR tag 1 data item holding floating-point epoch seconds.
+///
+/// The value is written as a 64-bit float, which keeps microsecond precision for current
+/// dates. Use [`encode_tag1`] when there is no sub-second part.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::cbor::encode_tag1_float;
+///
+/// let encoded = encode_tag1_float(1728933069, 500_000_000);
+/// assert_eq!(encoded[..2], [0xc1, 0xfb]);
+/// assert_eq!(encoded[2..], 1728933069.5f64.to_be_bytes());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the encoded bytes.
+///
+/// <small>End Fun Doc</small>
+pub fn encode_tag1_float(ts: u64, nanos: u64) -> Vec<u8> {
+    let seconds = ts as f64 + (nanos % 1_000_000_000) as f64 / 1e9;
+    let mut out = Vec::with_capacity(10);
+    write_head(6, TAG_EPOCH_DATETIME, &mut out);
+    out.push(0xfb);
+    out.extend_from_slice(&seconds.to_be_bytes());
+    out
+}
+
+/// ### decode_datetime(bytes: &[u8]) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes a CBOR tag 0 or tag 1 date/time data item.
+///
+/// Tag 0 must enclose an RFC 3339 text string. Tag 1 may enclose an unsigned integer or a
+/// 32- or 64-bit float; the fraction of a float is rounded to the nearest nanosecond.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::cbor::{decode_datetime, encode_tag0, encode_tag1, encode_tag1_float};
+///
+/// assert_eq!(decode_datetime(&encode_tag0(1728933069, 120)), Ok((1728933069, 0)));
+/// assert_eq!(decode_datetime(&encode_tag1(1728933069)), Ok((1728933069, 0)));
+/// assert_eq!(
+///     decode_datetime(&encode_tag1_float(1728933069, 250_000_000)),
+///     Ok((1728933069, 250_000_000))
+/// );
+/// assert!(decode_datetime(&[0xc1, 0x20]).is_err()); // tag 1 of -1
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the bytes are not exactly one tag 0 or tag 1
+/// item, or [`WTimeError::OutOfRange`] for a date before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn decode_datetime(bytes: &[u8]) -> Result<(u64, u64), WTimeError> {
+    const EXPECTED: &str = "a CBOR tag 0 or tag 1 date/time";
+
+    let (major_type, _, tag, content) = read_head(bytes)?;
+    if major_type != 6 {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let (major_type, info, argument, rest) = read_head(content)?;
+
+    match (tag, major_type) {
+        (TAG_DATETIME_STRING, 3) if rest.len() as u64 == argument => {
+            let text =
+                std::str::from_utf8(rest).map_err(|_| WTimeError::InvalidFormat(EXPECTED))?;
+            let (ts, nanos, _) = parse_rfc3339_parts(text)?;
+            Ok((ts, nanos))
+        }
+        (TAG_EPOCH_DATETIME, 0) if rest.is_empty() => Ok((argument, 0)),
+        (TAG_EPOCH_DATETIME, 1) if rest.is_empty() => Err(WTimeError::OutOfRange("timestamp")),
+        (TAG_EPOCH_DATETIME, 7) if rest.is_empty() && (info == 26 || info == 27) => {
+            let seconds = if info == 26 {
+                f32::from_bits(argument as u32) as f64
+            } else {
+                f64::from_bits(argument)
+            };
+            if !seconds.is_finite() || seconds < 0.0 || seconds >= u64::MAX as f64 {
+                return Err(WTimeError::OutOfRange("timestamp"));
+            }
+            let ts = seconds.trunc() as u64;
+            let nanos = ((seconds - seconds.trunc()) * 1e9).round() as u64;
+            // Rounding can carry into the next whole second
+            Ok((ts + nanos / 1_000_000_000, nanos % 1_000_000_000))
+        }
+        _ => Err(WTimeError::InvalidFormat(EXPECTED)),
+    }
+}
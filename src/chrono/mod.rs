@@ -0,0 +1,168 @@
+use super::{datetime::DateTime, error::WTimeError};
+use ::chrono::{FixedOffset, Local, Offset, TimeZone, Utc};
+
+/// Converts a UNIX timestamp and sub-second part into chrono's UTC date-time. Instants beyond
+/// chrono's range (about 262,000 years) are clamped to its upper limit.
+fn chrono_utc(ts: u64, nanos: u64) -> ::chrono::DateTime<Utc> {
+    let secs = (ts + nanos / 1_000_000_000).min(i64::MAX as u64) as i64;
+    ::chrono::DateTime::from_timestamp(secs, (nanos % 1_000_000_000) as u32)
+        .unwrap_or(::chrono::DateTime::<Utc>::MAX_UTC)
+}
+
+/// Converts to chrono's UTC date-time, dropping the offset.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 123_000_000, 120);
+/// let utc: chrono::DateTime<chrono::Utc> = dt.into();
+/// assert_eq!(utc.to_rfc3339(), "2024-10-14T19:11:09.123+00:00");
+/// ```
+impl From<DateTime> for ::chrono::DateTime<Utc> {
+    fn from(dt: DateTime) -> Self {
+        chrono_utc(dt.ts, dt.nanos)
+    }
+}
+
+/// Converts to chrono's date-time with a fixed offset, keeping the offset. An offset of a
+/// day or more, which chrono cannot represent, becomes UTC.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::FixedOffset;
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 0, 120);
+/// let fixed: chrono::DateTime<FixedOffset> = dt.into();
+/// assert_eq!(fixed.to_rfc3339(), "2024-10-14T21:11:09+02:00");
+/// ```
+impl From<DateTime> for ::chrono::DateTime<FixedOffset> {
+    fn from(dt: DateTime) -> Self {
+        let offset = i32::try_from(dt.offset_minutes * 60)
+            .ok()
+            .and_then(FixedOffset::east_opt)
+            .unwrap_or(Utc.fix());
+        chrono_utc(dt.ts, dt.nanos).with_timezone(&offset)
+    }
+}
+
+/// Converts to chrono's local date-time, in the offset chrono detects for the instant.
+impl From<DateTime> for ::chrono::DateTime<Local> {
+    fn from(dt: DateTime) -> Self {
+        chrono_utc(dt.ts, dt.nanos).with_timezone(&Local)
+    }
+}
+
+/// Converts from any chrono date-time, keeping its offset for the instant.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::{FixedOffset, TimeZone};
+/// use wtime::datetime::DateTime;
+///
+/// let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+/// let chrono_dt = tokyo.with_ymd_and_hms(2024, 10, 15, 4, 11, 9).unwrap();
+/// assert_eq!(DateTime::try_from(chrono_dt), Ok(DateTime::new(1728933069, 0, 540)));
+///
+/// let before_epoch = chrono::Utc.with_ymd_and_hms(1969, 12, 31, 0, 0, 0).unwrap();
+/// assert!(DateTime::try_from(before_epoch).is_err());
+/// ```
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for instants before the UNIX epoch, which a `u64`
+/// cannot represent.
+impl<Tz: TimeZone> TryFrom<::chrono::DateTime<Tz>> for DateTime {
+    type Error = WTimeError;
+
+    fn try_from(dt: ::chrono::DateTime<Tz>) -> Result<Self, WTimeError> {
+        let ts = from_chrono(&dt)?;
+        let offset_minutes = dt.offset().fix().local_minus_utc() as i64 / 60;
+        // A leap second has a sub-second part of one second or more, which `new` carries over
+        Ok(DateTime::new(
+            ts,
+            u64::from(dt.timestamp_subsec_nanos()),
+            offset_minutes,
+        ))
+    }
+}
+
+/// ### to_chrono(ts: u64) -> chrono::DateTime<Utc>
+///
+/// Converts a UNIX timestamp into chrono's UTC date-time.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::{Datelike, Timelike};
+/// use wtime::chrono::to_chrono;
+///
+/// let dt = to_chrono(1728933069);
+/// assert_eq!((dt.year(), dt.month(), dt.day()), (2024, 10, 14));
+/// assert_eq!(dt.hour(), 19);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `chrono::DateTime<Utc>`, clamped to chrono's upper limit for instants more
+/// than about 262,000 years away.
+///
+/// <small>End Fun Doc</small>
+pub fn to_chrono(ts: u64) -> ::chrono::DateTime<Utc> {
+    chrono_utc(ts, 0)
+}
+
+/// ### to_chrono_local(ts: u64) -> chrono::DateTime<Local>
+///
+/// Converts a UNIX timestamp into chrono's local date-time.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::chrono::to_chrono_local;
+///
+/// let dt = to_chrono_local(1728933069);
+/// assert_eq!(dt.timestamp(), 1728933069);
+/// println!("Local: {}", dt);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `chrono::DateTime<Local>` in the offset chrono detects for the instant.
+///
+/// <small>End Fun Doc</small>
+pub fn to_chrono_local(ts: u64) -> ::chrono::DateTime<Local> {
+    chrono_utc(ts, 0).with_timezone(&Local)
+}
+
+/// ### from_chrono<Tz: TimeZone>(dt: &chrono::DateTime<Tz>) -> Result<u64, WTimeError>
+///
+/// Converts a chrono date-time in any time zone into a UNIX timestamp in seconds.
+///
+/// ### Example
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use wtime::chrono::from_chrono;
+///
+/// let dt = Utc.with_ymd_and_hms(2024, 10, 14, 19, 11, 9).unwrap();
+/// assert_eq!(from_chrono(&dt), Ok(1728933069));
+/// assert!(from_chrono(&Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 0).unwrap()).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the whole seconds since the UNIX epoch.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for instants before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_chrono<Tz: TimeZone>(dt: &::chrono::DateTime<Tz>) -> Result<u64, WTimeError> {
+    u64::try_from(dt.timestamp()).map_err(|_| WTimeError::OutOfRange("timestamp"))
+}
@@ -0,0 +1,171 @@
+use super::utc::utc_now;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// ### Clock
+///
+/// A source of the current wall-clock time that can be replaced in tests.
+///
+/// Code that calls `utc_ts_sec()` directly cannot be tested deterministically. Taking a
+/// `Clock` instead, and calling the `*_with_clock` variants of the `utc` and `local`
+/// functions, lets production code pass [`SystemClock`] while tests pass a [`FixedClock`] or
+/// an [`OffsetClock`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::{Clock, FixedClock, SystemClock};
+/// use wtime::utc::utc_ts_sec_with_clock;
+///
+/// fn is_expired(clock: &impl Clock, expires_at: u64) -> bool {
+///     utc_ts_sec_with_clock(clock) >= expires_at
+/// }
+///
+/// assert!(!is_expired(&FixedClock::from_ts(1728933069), 1728933070));
+/// assert!(is_expired(&FixedClock::from_ts(1728933070), 1728933070));
+/// assert!(is_expired(&SystemClock, 1728933070));
+/// ```
+///
+/// <small>End Trait Doc</small>
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> SystemTime {
+        (**self).now()
+    }
+}
+
+/// ### SystemClock
+///
+/// The [`Clock`] that reads the system clock through [`utc_now`].
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        utc_now()
+    }
+}
+
+/// ### FixedClock
+///
+/// A [`Clock`] frozen at a given instant, which only moves when told to.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::{utc_rfc3339_with_clock, utc_ts_sec_with_clock};
+///
+/// let mut clock = FixedClock::from_ts(1728933069);
+/// assert_eq!(utc_rfc3339_with_clock(&clock), "2024-10-14T19:11:09.000Z");
+///
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(utc_ts_sec_with_clock(&clock), 1728933129);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedClock {
+    time: SystemTime,
+}
+
+impl FixedClock {
+    /// Creates a clock frozen at `time`.
+    pub fn new(time: SystemTime) -> Self {
+        FixedClock { time }
+    }
+
+    /// Creates a clock frozen at a UNIX timestamp in seconds.
+    pub fn from_ts(ts: u64) -> Self {
+        FixedClock::new(UNIX_EPOCH + Duration::from_secs(ts))
+    }
+
+    /// Moves the clock forwards by `by`.
+    pub fn advance(&mut self, by: Duration) {
+        self.time += by;
+    }
+
+    /// Moves the clock to `time`.
+    pub fn set(&mut self, time: SystemTime) {
+        self.time = time;
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.time
+    }
+}
+
+/// ### OffsetClock<C: Clock = SystemClock>
+///
+/// A [`Clock`] running a fixed amount ahead of or behind another clock.
+///
+/// Useful to test how code behaves tomorrow, or around an expiry, while time keeps flowing.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::{FixedClock, OffsetClock, SystemClock};
+/// use wtime::utc::{utc_ts_sec, utc_ts_sec_with_clock};
+///
+/// let tomorrow = OffsetClock::ahead(SystemClock, Duration::from_secs(86_400));
+/// assert!(utc_ts_sec_with_clock(&tomorrow) >= utc_ts_sec() + 86_400);
+///
+/// let earlier = OffsetClock::behind(FixedClock::from_ts(1728933069), Duration::from_secs(9));
+/// assert_eq!(utc_ts_sec_with_clock(&earlier), 1728933060);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OffsetClock<C: Clock = SystemClock> {
+    inner: C,
+    offset: Duration,
+    behind: bool,
+}
+
+impl<C: Clock> OffsetClock<C> {
+    /// Creates a clock running `offset` ahead of `inner`.
+    pub fn ahead(inner: C, offset: Duration) -> Self {
+        OffsetClock {
+            inner,
+            offset,
+            behind: false,
+        }
+    }
+
+    /// Creates a clock running `offset` behind `inner`.
+    pub fn behind(inner: C, offset: Duration) -> Self {
+        OffsetClock {
+            inner,
+            offset,
+            behind: true,
+        }
+    }
+}
+
+impl<C: Clock> Clock for OffsetClock<C> {
+    fn now(&self) -> SystemTime {
+        let now = self.inner.now();
+        if self.behind {
+            now.checked_sub(self.offset).unwrap_or(UNIX_EPOCH)
+        } else {
+            now + self.offset
+        }
+    }
+}
+
+/// Returns the time of `clock` since the UNIX epoch, or zero for a clock set before 1970.
+pub(crate) fn since_epoch<C: Clock>(clock: &C) -> Duration {
+    clock
+        .now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+}
@@ -0,0 +1,381 @@
+use super::calc::{CalendarNames, Month, WeekConfig, Weekday};
+#[cfg(feature = "tz")]
+use super::tz::{offset_at, tz_offset_minutes};
+use std::cell::Cell;
+use std::sync::RwLock;
+
+/// ### TimestampFormat
+///
+/// The layout produced by [`format_ts`](crate::format::format_ts).
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimestampFormat {
+    /// The crate's own `year-month-day-hour-minute-second-millis-nanos` layout, as produced
+    /// by `format_utc_ts`.
+    Wtime,
+    /// RFC 3339 with milliseconds, e.g. `2024-10-14T19:11:09.123Z`.
+    Rfc3339,
+    /// An SQL `TIMESTAMP` literal with microseconds, e.g. `2024-10-14 19:11:09.123456`.
+    Sql,
+    /// An RFC 5322 date, e.g. `Mon, 14 Oct 2024 19:11:09 +0000`.
+    Email,
+}
+
+/// ### Zone
+///
+/// The time zone in which formatted timestamps are shown.
+///
+/// `Zone::Local` follows daylight saving time: a timestamp is shown in the offset the local
+/// zone observed at that instant, not the offset in force today.
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Zone {
+    /// Coordinated Universal Time.
+    Utc,
+    /// The machine's local zone, looked up each time it is used.
+    ///
+    /// Without the `tz` feature there is no zone detection and this is the same as `Utc`.
+    Local,
+    /// A fixed UTC offset in minutes, e.g. `330` for UTC+05:30.
+    Fixed(i64),
+}
+
+impl Zone {
+    /// Returns the UTC offset of the zone in minutes at the current instant.
+    pub fn offset_minutes(&self) -> i64 {
+        match self {
+            Zone::Utc => 0,
+            #[cfg(feature = "tz")]
+            Zone::Local => tz_offset_minutes(),
+            #[cfg(not(feature = "tz"))]
+            Zone::Local => 0,
+            Zone::Fixed(offset_minutes) => *offset_minutes,
+        }
+    }
+
+    /// Returns the UTC offset of the zone in minutes at the UNIX timestamp `ts`, following
+    /// the daylight-saving rules of the local zone.
+    #[cfg_attr(not(feature = "tz"), allow(unused_variables))]
+    pub fn offset_minutes_at(&self, ts: u64) -> i64 {
+        match self {
+            Zone::Utc => 0,
+            #[cfg(feature = "tz")]
+            Zone::Local => offset_at(ts),
+            #[cfg(not(feature = "tz"))]
+            Zone::Local => 0,
+            Zone::Fixed(offset_minutes) => *offset_minutes,
+        }
+    }
+}
+
+/// ### WeekStart
+///
+/// The first day of the week used by week-based calculations such as the `this_week` preset
+/// of [`resolve_range`](crate::range::resolve_range).
+///
+/// The default is the configured [`Config::week_start`], so functions that take a week start,
+/// such as [`start_of_week`](crate::utc::start_of_week), follow the application's setting
+/// when passed `WeekStart::default()`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, WeekStart};
+/// use wtime::utc::start_of_week;
+///
+/// let ts = 1728933069; // Monday 2024-10-14 19:11:09 UTC
+/// assert_eq!(start_of_week(ts, WeekStart::default()), 1728864000); // Monday
+///
+/// let sunday_weeks = Config {
+///     week_start: WeekStart::Sunday,
+///     ..Config::default()
+/// };
+/// config::scoped(sunday_weeks, || {
+///     assert_eq!(start_of_week(ts, WeekStart::default()), 1728777600); // Sunday
+/// });
+/// ```
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeekStart {
+    /// Weeks run Monday to Sunday, as in ISO 8601 and most of Europe.
+    Monday,
+    /// Weeks run Sunday to Saturday, as in the United States.
+    Sunday,
+    /// Weeks run Saturday to Friday, as in much of the Middle East.
+    Saturday,
+}
+
+impl Default for WeekStart {
+    /// Returns the week start of the current configuration.
+    fn default() -> Self {
+        get().week_start
+    }
+}
+
+impl From<WeekStart> for Weekday {
+    fn from(week_start: WeekStart) -> Self {
+        match week_start {
+            WeekStart::Monday => Weekday::Monday,
+            WeekStart::Sunday => Weekday::Sunday,
+            WeekStart::Saturday => Weekday::Saturday,
+        }
+    }
+}
+
+/// Maps each week start to the week numbering customary where it is used: ISO 8601 weeks for
+/// Monday, and weeks whose week 1 contains January 1 for Sunday and Saturday.
+impl From<WeekStart> for WeekConfig {
+    fn from(week_start: WeekStart) -> Self {
+        match week_start {
+            WeekStart::Monday => WeekConfig::ISO,
+            WeekStart::Sunday => WeekConfig::US,
+            WeekStart::Saturday => WeekConfig::MIDDLE_EAST,
+        }
+    }
+}
+
+/// ### Locale
+///
+/// The language of human-readable month and day names.
+///
+/// Each locale implements [`CalendarNames`], so it can be passed to
+/// [`get_month_name_in`](crate::calc::get_month_name_in) and
+/// [`get_day_name_in`](crate::calc::get_day_name_in); the configured locale also names
+/// months and days in [`format_with`](crate::format::format_with).
+///
+/// Protocol formats such as email and HTTP dates are always written in English, whatever the
+/// configured locale.
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Locale {
+    /// English names, e.g. `Monday` and `October`.
+    English,
+    /// Arabic names as written in Egypt and the Gulf, e.g. `الاثنين` and `أكتوبر`. Arabic
+    /// does not abbreviate them, so the abbreviations are the full names.
+    Arabic,
+}
+
+impl CalendarNames for Locale {
+    fn month_name(&self, month: Month) -> &str {
+        const ARABIC: [&str; 12] = [
+            "يناير",
+            "فبراير",
+            "مارس",
+            "أبريل",
+            "مايو",
+            "يونيو",
+            "يوليو",
+            "أغسطس",
+            "سبتمبر",
+            "أكتوبر",
+            "نوفمبر",
+            "ديسمبر",
+        ];
+        match self {
+            Locale::English => month.name(),
+            Locale::Arabic => ARABIC[month as usize],
+        }
+    }
+
+    fn month_abbr(&self, month: Month) -> &str {
+        match self {
+            Locale::English => month.short_name(),
+            Locale::Arabic => self.month_name(month),
+        }
+    }
+
+    fn day_name(&self, weekday: Weekday) -> &str {
+        const ARABIC: [&str; 7] = [
+            "الاثنين",
+            "الثلاثاء",
+            "الأربعاء",
+            "الخميس",
+            "الجمعة",
+            "السبت",
+            "الأحد",
+        ];
+        match self {
+            Locale::English => weekday.name(),
+            Locale::Arabic => ARABIC[weekday as usize],
+        }
+    }
+
+    fn day_abbr(&self, weekday: Weekday) -> &str {
+        match self {
+            Locale::English => weekday.short_name(),
+            Locale::Arabic => self.day_name(weekday),
+        }
+    }
+}
+
+/// ### Config
+///
+/// Application-wide conventions consulted by the formatting and week functions.
+///
+/// An application sets its conventions once with [`set`] instead of passing them to every
+/// call. [`scoped`] overrides them for the current thread only, which is useful in tests and
+/// for per-request settings.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, Locale, TimestampFormat, WeekStart, Zone};
+///
+/// let config = Config {
+///     default_format: TimestampFormat::Rfc3339,
+///     default_zone: Zone::Fixed(330),
+///     week_start: WeekStart::Sunday,
+///     locale: Locale::English,
+/// };
+/// config::scoped(config, || {
+///     assert_eq!(config::get().default_zone, Zone::Fixed(330));
+/// });
+/// assert_eq!(Config::default().week_start, WeekStart::Monday);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config {
+    /// The layout used by [`format_ts`](crate::format::format_ts).
+    pub default_format: TimestampFormat,
+    /// The zone used by [`format_ts`](crate::format::format_ts).
+    pub default_zone: Zone,
+    /// The first day of the week.
+    pub week_start: WeekStart,
+    /// The language of month and day names.
+    pub locale: Locale,
+}
+
+impl Config {
+    /// The crate's built-in conventions: the `Wtime` layout in UTC, Monday weeks and English
+    /// names.
+    pub const DEFAULT: Config = Config {
+        default_format: TimestampFormat::Wtime,
+        default_zone: Zone::Utc,
+        week_start: WeekStart::Monday,
+        locale: Locale::English,
+    };
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::DEFAULT
+    }
+}
+
+static GLOBAL: RwLock<Config> = RwLock::new(Config::DEFAULT);
+
+thread_local! {
+    static SCOPED: Cell<Option<Config>> = const { Cell::new(None) };
+}
+
+/// Restores the previous thread-local override when a [`scoped`] call ends, even by a panic.
+struct ScopeGuard(Option<Config>);
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPED.with(|scoped| scoped.set(self.0));
+    }
+}
+
+/// ### set(config: Config)
+///
+/// Replaces the process-wide configuration.
+///
+/// The new configuration is seen by every thread, except inside a [`scoped`] override.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, Zone};
+///
+/// config::set(Config {
+///     default_zone: Zone::Local,
+///     ..Config::default()
+/// });
+/// assert_eq!(config::get().default_zone, Zone::Local);
+/// # config::set(Config::default());
+/// ```
+///
+/// <small>End Fun Doc</small>
+pub fn set(config: Config) {
+    // The lock only guards a `Copy` value, so a poisoned lock still holds a valid config
+    *GLOBAL
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+}
+
+/// ### get() -> Config
+///
+/// Retrieves the configuration in effect on the current thread.
+///
+/// This is the innermost [`scoped`] override if there is one, and otherwise the
+/// configuration last passed to [`set`], or [`Config::DEFAULT`] if `set` was never called.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config;
+///
+/// let config = config::get();
+/// println!("Default format: {:?}", config.default_format);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a copy of the current [`Config`].
+///
+/// <small>End Fun Doc</small>
+pub fn get() -> Config {
+    SCOPED.with(Cell::get).unwrap_or_else(|| {
+        *GLOBAL
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    })
+}
+
+/// ### scoped<R>(config: Config, f: impl FnOnce() -> R) -> R
+///
+/// Runs a closure with a configuration override for the current thread.
+///
+/// Other threads keep seeing the process-wide configuration. Overrides nest, and the
+/// previous configuration is restored when the closure returns or panics.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, WeekStart};
+///
+/// let sunday_weeks = Config {
+///     week_start: WeekStart::Sunday,
+///     ..Config::default()
+/// };
+/// let saturday_weeks = Config {
+///     week_start: WeekStart::Saturday,
+///     ..Config::default()
+/// };
+///
+/// config::scoped(sunday_weeks, || {
+///     assert_eq!(config::get().week_start, WeekStart::Sunday);
+///     config::scoped(saturday_weeks, || {
+///         assert_eq!(config::get().week_start, WeekStart::Saturday);
+///     });
+///     assert_eq!(config::get().week_start, WeekStart::Sunday);
+/// });
+/// assert_eq!(config::get().week_start, WeekStart::Monday);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the value returned by `f`.
+///
+/// <small>End Fun Doc</small>
+pub fn scoped<R>(config: Config, f: impl FnOnce() -> R) -> R {
+    let _guard = ScopeGuard(SCOPED.with(|scoped| scoped.replace(Some(config))));
+    f()
+}
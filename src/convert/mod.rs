@@ -0,0 +1,143 @@
+use super::{error::WTimeError, utc::utc_tai_offset};
+
+/// The UNIX timestamp of the GPS epoch, 1980-01-06 00:00:00 UTC.
+pub const GPS_EPOCH: u64 = 315_964_800;
+
+/// The number of seconds GPS time is behind TAI, fixed since the GPS epoch.
+const GPS_TAI_OFFSET: i64 = 19;
+
+/// ### unix_to_tai(ts: u64) -> u64
+///
+/// Converts a UNIX timestamp into TAI, International Atomic Time, counted in seconds from
+/// the same 1970-01-01 origin.
+///
+/// UNIX time skips leap seconds while TAI counts them, so TAI runs ahead by the number of
+/// seconds given by [`utc_tai_offset`]: 37 since 2017. The conversion uses the built-in
+/// leap-second table and shares its limits, described in `utc::LEAP_SECONDS`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::convert::unix_to_tai;
+///
+/// assert_eq!(unix_to_tai(1728933069), 1728933106);
+/// assert_eq!(unix_to_tai(946684800), 946684832); // 2000-01-01, 32 leap seconds
+/// ```
+///
+/// ### Returns
+///
+/// Returns the TAI seconds since 1970-01-01, saturating at `u64::MAX`.
+///
+/// <small>End Fun Doc</small>
+pub fn unix_to_tai(ts: u64) -> u64 {
+    ts.saturating_add_signed(utc_tai_offset(ts))
+}
+
+/// ### tai_to_unix(tai: u64) -> u64
+///
+/// Converts TAI seconds since 1970-01-01 back into a UNIX timestamp.
+///
+/// A TAI second that falls on a leap second has no UNIX timestamp of its own and is mapped
+/// to the last second of the day, `23:59:59`, which UNIX time repeats.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::convert::{tai_to_unix, unix_to_tai};
+///
+/// assert_eq!(tai_to_unix(1728933106), 1728933069);
+///
+/// // The leap second at the end of 2016 and the seconds around it
+/// let midnight = 1483228800; // 2017-01-01 00:00:00 UTC
+/// assert_eq!(unix_to_tai(midnight - 1), midnight + 35);
+/// assert_eq!(unix_to_tai(midnight), midnight + 37);
+/// assert_eq!(tai_to_unix(midnight + 36), midnight - 1); // 23:59:60
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds.
+///
+/// <small>End Fun Doc</small>
+pub fn tai_to_unix(tai: u64) -> u64 {
+    from_offset_scale(tai, 0)
+}
+
+/// ### unix_to_gps(ts: u64) -> Result<u64, WTimeError>
+///
+/// Converts a UNIX timestamp into GPS time, the seconds since the GPS epoch of 1980-01-06
+/// 00:00:00 UTC used by GNSS receivers and telecom equipment.
+///
+/// GPS time counts leap seconds, like TAI, and stays 19 seconds behind it, so it has run
+/// ahead of UTC by one second for every leap second since 1980: 18 since 2017. The
+/// conversion uses the built-in leap-second table.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::convert::{unix_to_gps, GPS_EPOCH};
+///
+/// assert_eq!(unix_to_gps(GPS_EPOCH), Ok(0));
+/// assert_eq!(unix_to_gps(1728933069), Ok(1412968287));
+/// assert!(unix_to_gps(0).is_err());
+///
+/// // GPS week number and seconds into the week
+/// let gps = unix_to_gps(1728933069).unwrap();
+/// assert_eq!((gps / 604_800, gps % 604_800), (2336, 155_487));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the GPS seconds since the GPS epoch.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for a timestamp before the GPS epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn unix_to_gps(ts: u64) -> Result<u64, WTimeError> {
+    if ts < GPS_EPOCH {
+        return Err(WTimeError::OutOfRange("timestamp"));
+    }
+    Ok(unix_to_tai(ts) - GPS_EPOCH - GPS_TAI_OFFSET as u64)
+}
+
+/// ### gps_to_unix(gps: u64) -> u64
+///
+/// Converts GPS seconds since the GPS epoch into a UNIX timestamp.
+///
+/// As with [`tai_to_unix`], a GPS second that falls on a leap second is mapped to the
+/// `23:59:59` that UNIX time repeats.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::convert::{gps_to_unix, unix_to_gps, GPS_EPOCH};
+///
+/// assert_eq!(gps_to_unix(0), GPS_EPOCH);
+/// assert_eq!(gps_to_unix(1412968287), 1728933069);
+///
+/// let ts = 1483228800; // 2017-01-01 00:00:00 UTC
+/// assert_eq!(gps_to_unix(unix_to_gps(ts).unwrap()), ts);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds.
+///
+/// <small>End Fun Doc</small>
+pub fn gps_to_unix(gps: u64) -> u64 {
+    from_offset_scale(gps, GPS_EPOCH + GPS_TAI_OFFSET as u64)
+}
+
+/// Converts a reading of a scale that counts leap seconds, `shift` seconds behind TAI since
+/// 1970, into the last UNIX timestamp that does not come after it.
+fn from_offset_scale(reading: u64, shift: u64) -> u64 {
+    let tai = reading.saturating_add(shift);
+    // The offset only grows, so this guess is never too late and at most a few seconds early
+    let mut ts = tai.saturating_add_signed(-utc_tai_offset(tai));
+    while ts < u64::MAX && unix_to_tai(ts + 1) <= tai {
+        ts += 1;
+    }
+    ts
+}
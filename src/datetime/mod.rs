@@ -0,0 +1,132 @@
+use super::{
+    calc::{civil_from_days, clamp_offset, write_rfc3339, MAX_TS},
+    error::WTimeError,
+};
+use core::fmt;
+
+/// ### DateTime
+///
+/// An instant together with the UTC offset it was written in.
+///
+/// Returned by the parsers of the `parse` module. `ts` and `nanos` identify the instant; the
+/// offset only affects the wall-clock view returned by [`DateTime::date`] and
+/// [`DateTime::time`] and the text produced by `Display`, which is RFC 3339. Two values for
+/// the same instant in different offsets are not equal; compare `(ts, nanos)` for that.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 123_000_000, 120);
+/// assert_eq!(dt.date(), (2024, 10, 14));
+/// assert_eq!(dt.time(), (21, 11, 9));
+/// assert_eq!(dt.to_string(), "2024-10-14T21:11:09.123+02:00");
+///
+/// let utc = DateTime::from_ts(1728933069);
+/// assert_eq!(utc.to_string(), "2024-10-14T19:11:09Z");
+///
+/// // Seconds as a float, as exchanged by Python, Prometheus and JavaScript
+/// let from_python = DateTime::from_ts_f64(1728933069.123).unwrap();
+/// assert_eq!((from_python.ts, from_python.nanos), (1728933069, 123_000_000));
+/// assert_eq!(from_python.ts_f64(), 1728933069.123);
+/// assert!(DateTime::from_ts_f64(-1.5).is_err());
+/// assert!(DateTime::from_ts_f64(f64::NAN).is_err());
+///
+/// // Out-of-range values saturate instead of overflowing
+/// let far = DateTime::new(u64::MAX, 1_000_000_000, 0);
+/// assert_eq!(far.ts, u64::MAX);
+/// assert_eq!(far.to_string(), "100000000000-12-31T23:59:59Z");
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    /// Seconds since the UNIX epoch.
+    pub ts: u64,
+    /// Sub-second part in nanoseconds (0-999,999,999).
+    pub nanos: u64,
+    /// UTC offset of the wall-clock time in minutes, e.g. `120` for UTC+02:00.
+    pub offset_minutes: i64,
+}
+
+impl DateTime {
+    /// Creates a date-time from a UNIX timestamp, a sub-second part and a UTC offset.
+    ///
+    /// Nanoseconds above 999,999,999 carry over into `ts`, which saturates at `u64::MAX`.
+    pub fn new(ts: u64, nanos: u64, offset_minutes: i64) -> Self {
+        DateTime {
+            ts: ts.saturating_add(nanos / 1_000_000_000),
+            nanos: nanos % 1_000_000_000,
+            offset_minutes,
+        }
+    }
+
+    /// Creates a UTC date-time from a UNIX timestamp in whole seconds.
+    pub fn from_ts(ts: u64) -> Self {
+        DateTime::new(ts, 0, 0)
+    }
+
+    /// Creates a UTC date-time from a UNIX timestamp in seconds with a fractional part, as
+    /// exchanged by Python, Prometheus and JavaScript APIs.
+    ///
+    /// The fraction is rounded to the microsecond, about the precision an `f64` has for
+    /// current timestamps, so `1728933069.123` gives exactly 123 milliseconds.
+    ///
+    /// ### Errors
+    ///
+    /// Returns [`WTimeError::OutOfRange`] for a negative, infinite or NaN value, or one
+    /// beyond `u64::MAX` seconds.
+    pub fn from_ts_f64(secs: f64) -> Result<Self, WTimeError> {
+        if !(0.0..18_446_744_073_709_551_616.0).contains(&secs) {
+            return Err(WTimeError::OutOfRange("timestamp"));
+        }
+        let ts = secs as u64;
+        let micros = ((secs - ts as f64) * 1e6 + 0.5) as u64;
+        Ok(DateTime::new(ts, micros * 1_000, 0))
+    }
+
+    /// Returns the instant as a UNIX timestamp in seconds with a fractional part.
+    pub fn ts_f64(&self) -> f64 {
+        self.ts as f64 + self.nanos as f64 / 1e9
+    }
+
+    /// Returns the same instant shown in another UTC offset.
+    pub fn with_offset(self, offset_minutes: i64) -> Self {
+        DateTime {
+            offset_minutes,
+            ..self
+        }
+    }
+
+    /// Returns the wall-clock `(year, month, day)` in the date-time's offset.
+    pub fn date(&self) -> (i64, u64, u64) {
+        civil_from_days(self.wall().div_euclid(86_400))
+    }
+
+    /// Returns the wall-clock `(hour, minute, second)` in the date-time's offset.
+    pub fn time(&self) -> (u64, u64, u64) {
+        let secs_of_day = self.wall().rem_euclid(86_400) as u64;
+        (secs_of_day / 3600, secs_of_day / 60 % 60, secs_of_day % 60)
+    }
+
+    /// Returns the wall-clock seconds since the UNIX epoch, shown as at [`MAX_TS`] for later
+    /// instants and with the offset saturated below 24 hours, as `Display` does.
+    fn wall(&self) -> i64 {
+        self.ts.min(MAX_TS) as i64 + clamp_offset(self.offset_minutes) * 60
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Writes the date-time as RFC 3339 with as few fractional digits as needed (none, 3, 6
+    /// or 9), using `Z` for UTC. Instants after [`MAX_TS`] are written as that instant.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = match self.nanos {
+            0 => 0,
+            nanos if nanos % 1_000_000 == 0 => 3,
+            nanos if nanos % 1_000 == 0 => 6,
+            _ => 9,
+        };
+        write_rfc3339(f, self.ts, self.offset_minutes, self.nanos, digits)
+    }
+}
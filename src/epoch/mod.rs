@@ -0,0 +1,173 @@
+use super::{
+    calc::{days_from_civil, days_in_month, duration_since, MAX_YEAR},
+    error::WTimeError,
+};
+
+/// ### Epoch
+///
+/// A custom epoch: the instant from which a project-specific timestamp counts.
+///
+/// Snowflake-style IDs and many embedded protocols count time from their own epoch instead of
+/// 1970-01-01 to keep the numbers small. An `Epoch` is stored as its offset from the UNIX
+/// epoch and read with [`ts_since`], [`ts_millis_since`] and [`ts_micros_since`].
+/// [`to_custom`](Epoch::to_custom) and [`from_custom`](Epoch::from_custom) convert stored
+/// values between the custom epoch and UNIX time.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::epoch::{ts_since, Epoch};
+///
+/// let epoch = Epoch::new(2020, 1, 1).unwrap();
+/// assert_eq!(epoch.unix_secs(), 1577836800);
+/// assert_eq!(Epoch::from_unix_secs(1577836800), epoch);
+///
+/// println!("Seconds since 2020: {}", ts_since(&epoch));
+/// assert!(epoch.elapsed_secs() > 150_000_000);
+/// assert!(Epoch::new(2020, 2, 30).is_err());
+/// assert!(Epoch::new(1_000_000_000_000_000, 1, 1).is_err());
+///
+/// // An epoch of 2000-01-01 00:00:00 UTC, as used by many embedded protocols
+/// let y2k = Epoch::new(2000, 1, 1).unwrap();
+/// assert_eq!(y2k.to_custom(1728933069), Ok(782_248_269));
+/// assert_eq!(y2k.from_custom(782_248_269), 1728933069);
+/// assert!(y2k.to_custom(0).is_err());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Epoch {
+    unix_secs: u64,
+}
+
+impl Epoch {
+    /// Creates an epoch at midnight UTC of the given calendar date.
+    ///
+    /// Returns [`WTimeError::OutOfRange`] for an invalid date, or a date before 1970 or after
+    /// [`MAX_YEAR`].
+    pub fn new(year: u64, month: u64, day: u64) -> Result<Self, WTimeError> {
+        if year > MAX_YEAR {
+            return Err(WTimeError::OutOfRange("year"));
+        }
+        if !(1..=12).contains(&month) {
+            return Err(WTimeError::OutOfRange("month"));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(WTimeError::OutOfRange("day"));
+        }
+        let days = days_from_civil(year as i64, month, day);
+        let days = u64::try_from(days).map_err(|_| WTimeError::OutOfRange("year"))?;
+        Ok(Epoch {
+            unix_secs: days * 86_400,
+        })
+    }
+
+    /// Creates an epoch at a UNIX timestamp in seconds.
+    pub fn from_unix_secs(unix_secs: u64) -> Self {
+        Epoch { unix_secs }
+    }
+
+    /// Returns the UNIX timestamp of the epoch in seconds.
+    pub fn unix_secs(&self) -> u64 {
+        self.unix_secs
+    }
+
+    /// Returns the seconds elapsed since the epoch, or `0` while it is still in the future.
+    pub fn elapsed_secs(&self) -> u64 {
+        ts_since(self)
+    }
+
+    /// Converts a UNIX timestamp in seconds into seconds since the epoch.
+    ///
+    /// Returns [`WTimeError::OutOfRange`] for a timestamp before the epoch.
+    pub fn to_custom(&self, ts: u64) -> Result<u64, WTimeError> {
+        ts.checked_sub(self.unix_secs)
+            .ok_or(WTimeError::OutOfRange("timestamp"))
+    }
+
+    /// Converts seconds since the epoch into a UNIX timestamp in seconds, saturating at
+    /// `u64::MAX`.
+    pub fn from_custom(&self, secs: u64) -> u64 {
+        self.unix_secs.saturating_add(secs)
+    }
+}
+
+/// ### ts_since(epoch: &Epoch) -> u64
+///
+/// Retrieves the current time as seconds since a custom epoch.
+///
+/// Returns `0` while the epoch is still in the future.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::epoch::{ts_since, Epoch};
+/// use wtime::utc::utc_ts_sec;
+///
+/// let epoch = Epoch::new(2020, 1, 1).unwrap();
+/// let since = ts_since(&epoch);
+/// assert!(since <= utc_ts_sec() - 1577836800);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the whole seconds elapsed since the epoch as a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn ts_since(epoch: &Epoch) -> u64 {
+    duration_since().as_secs().saturating_sub(epoch.unix_secs)
+}
+
+/// ### ts_millis_since(epoch: &Epoch) -> u128
+///
+/// Retrieves the current time as milliseconds since a custom epoch.
+///
+/// Returns `0` while the epoch is still in the future.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::epoch::{ts_millis_since, Epoch};
+///
+/// // Twitter's Snowflake epoch, 2010-11-04T01:42:54.657Z, rounded to the second
+/// let epoch = Epoch::from_unix_secs(1288834974);
+/// let snowflake_ms = ts_millis_since(&epoch) as u64;
+/// println!("Snowflake timestamp bits: {:041b}", snowflake_ms);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds elapsed since the epoch as a `u128`.
+///
+/// <small>End Fun Doc</small>
+pub fn ts_millis_since(epoch: &Epoch) -> u128 {
+    duration_since()
+        .as_millis()
+        .saturating_sub(epoch.unix_secs as u128 * 1_000)
+}
+
+/// ### ts_micros_since(epoch: &Epoch) -> u128
+///
+/// Retrieves the current time as microseconds since a custom epoch.
+///
+/// Returns `0` while the epoch is still in the future.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::epoch::{ts_micros_since, Epoch};
+///
+/// let epoch = Epoch::new(2000, 1, 1).unwrap();
+/// println!("Microseconds since 2000: {}", ts_micros_since(&epoch));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the microseconds elapsed since the epoch as a `u128`.
+///
+/// <small>End Fun Doc</small>
+pub fn ts_micros_since(epoch: &Epoch) -> u128 {
+    duration_since()
+        .as_micros()
+        .saturating_sub(epoch.unix_secs as u128 * 1_000_000)
+}
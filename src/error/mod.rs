@@ -0,0 +1,42 @@
+use core::fmt;
+
+/// ### WTimeError
+///
+/// The error type returned by the fallible functions of this crate.
+///
+/// Parsers and validating constructors return `Result<_, WTimeError>` instead of panicking,
+/// so library consumers can decide how to handle malformed input.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::error::WTimeError;
+///
+/// let err = WTimeError::OutOfRange("offset");
+/// assert_eq!(err.to_string(), "offset is out of range");
+///
+/// let err = WTimeError::InvalidFormat("`<seconds> <+HHMM>`");
+/// assert_eq!(err.to_string(), "invalid format, expected `<seconds> <+HHMM>`");
+/// ```
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WTimeError {
+    /// The input does not match the expected format. Holds a description of that format.
+    InvalidFormat(&'static str),
+    /// A date, time or offset component is outside its valid range. Holds the component name.
+    OutOfRange(&'static str),
+}
+
+impl fmt::Display for WTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WTimeError::InvalidFormat(expected) => {
+                write!(f, "invalid format, expected {}", expected)
+            }
+            WTimeError::OutOfRange(component) => write!(f, "{} is out of range", component),
+        }
+    }
+}
+
+impl core::error::Error for WTimeError {}
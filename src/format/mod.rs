@@ -0,0 +1,1691 @@
+use super::{
+    calc::{
+        civil_from_days, clamp_offset, day_name_from_days, days_from_civil, duration_since,
+        get_month_abbr, iso_week_date, write_fraction, write_offset, write_rfc3339, write_wtime_ts,
+        CalendarNames, Month, Weekday, MAX_TS,
+    },
+    config::{self, Locale, TimestampFormat},
+    error::WTimeError,
+    range::TimeRange,
+};
+#[cfg(feature = "tz")]
+use super::{tz::tz_offset_minutes, utc::utc_ts_sec};
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+/// Wall-clock components of a UNIX timestamp shifted by a UTC offset.
+struct Civil {
+    year: i64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+    /// Days since 1970-01-01 of the wall-clock date, used for the weekday.
+    days: i64,
+}
+
+impl Civil {
+    /// Timestamps after [`MAX_TS`] are shown as that instant and offsets saturate below 24
+    /// hours, so that the wall-clock arithmetic cannot overflow.
+    fn new(ts: u64, offset_minutes: i64) -> Self {
+        let wall = ts.min(MAX_TS) as i64 + clamp_offset(offset_minutes) * 60;
+        let days = wall.div_euclid(86_400);
+        let secs_of_day = wall.rem_euclid(86_400) as u64;
+        let (year, month, day) = civil_from_days(days);
+
+        Civil {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day / 60 % 60,
+            second: secs_of_day % 60,
+            days,
+        }
+    }
+
+    fn day_abbr(&self) -> &'static str {
+        &day_name_from_days(self.days)[..3]
+    }
+
+    fn month_abbr(&self) -> &'static str {
+        get_month_abbr(self.month)
+    }
+}
+
+/// Collects the output of one of the `*_into` functions into a `String`.
+fn to_string(write: impl FnOnce(&mut String) -> fmt::Result) -> String {
+    let mut out = String::new();
+    // Writing into a `String` never fails
+    let _ = write(&mut out);
+    out
+}
+
+/// Writes an SQL datetime literal of the wall-clock time in the given offset.
+fn write_sql_datetime<W: Write>(
+    out: &mut W,
+    ts: u64,
+    offset_minutes: i64,
+    nanos: u64,
+    precision: usize,
+) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    write!(
+        out,
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        civil.year, civil.month, civil.day, civil.hour, civil.minute, civil.second,
+    )?;
+    let precision = precision.min(6);
+    if precision > 0 {
+        out.write_char('.')?;
+        write_fraction(out, nanos, precision)?;
+    }
+    Ok(())
+}
+
+/// A strftime specifier of [`format_with`], parsed once so a batch can reuse it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Spec {
+    Year,
+    ShortYear,
+    Month,
+    Day,
+    SpacedDay,
+    DayOfYear,
+    Hour,
+    Hour12,
+    AmPm,
+    Minute,
+    Second,
+    Fraction(usize),
+    Offset(&'static str),
+    DayAbbr,
+    DayName,
+    MonthAbbr,
+    MonthName,
+    Epoch,
+}
+
+impl Spec {
+    /// Returns `true` if the output only changes with the wall-clock date.
+    fn is_daily(self) -> bool {
+        !matches!(
+            self,
+            Spec::Hour
+                | Spec::Hour12
+                | Spec::AmPm
+                | Spec::Minute
+                | Spec::Second
+                | Spec::Fraction(_)
+                | Spec::Epoch
+        )
+    }
+}
+
+/// A piece of a parsed [`format_with`] pattern: copied text or a specifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Piece<'a> {
+    Literal(&'a str),
+    Spec(Spec),
+}
+
+/// Splits a [`format_with`] pattern into literal text and specifiers.
+fn parse_pattern(pattern: &str) -> Result<Vec<Piece<'_>>, WTimeError> {
+    const EXPECTED: &str = "a pattern of strftime specifiers such as `%Y-%m-%d %H:%M:%S`";
+
+    let mut pieces = Vec::new();
+    let mut rest = pattern;
+    while let Some(at) = rest.find('%') {
+        if at > 0 {
+            pieces.push(Piece::Literal(&rest[..at]));
+        }
+        let mut chars = rest[at + 1..].chars();
+        let piece = match chars.next().ok_or(WTimeError::InvalidFormat(EXPECTED))? {
+            'Y' => Piece::Spec(Spec::Year),
+            'y' => Piece::Spec(Spec::ShortYear),
+            'm' => Piece::Spec(Spec::Month),
+            'd' => Piece::Spec(Spec::Day),
+            'e' => Piece::Spec(Spec::SpacedDay),
+            'j' => Piece::Spec(Spec::DayOfYear),
+            'H' => Piece::Spec(Spec::Hour),
+            'I' => Piece::Spec(Spec::Hour12),
+            'p' => Piece::Spec(Spec::AmPm),
+            'M' => Piece::Spec(Spec::Minute),
+            'S' => Piece::Spec(Spec::Second),
+            'f' => Piece::Spec(Spec::Fraction(9)),
+            digits @ ('3' | '6' | '9') => match chars.next() {
+                Some('f') => Piece::Spec(Spec::Fraction(digits as usize - '0' as usize)),
+                _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+            },
+            'z' => Piece::Spec(Spec::Offset("")),
+            ':' => match chars.next() {
+                Some('z') => Piece::Spec(Spec::Offset(":")),
+                _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+            },
+            'a' => Piece::Spec(Spec::DayAbbr),
+            'A' => Piece::Spec(Spec::DayName),
+            'b' => Piece::Spec(Spec::MonthAbbr),
+            'B' => Piece::Spec(Spec::MonthName),
+            's' => Piece::Spec(Spec::Epoch),
+            '%' => Piece::Literal("%"),
+            _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+        };
+        pieces.push(piece);
+        rest = chars.as_str();
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Literal(rest));
+    }
+    Ok(pieces)
+}
+
+/// Writes one specifier of [`format_with`] for a timestamp already split into `civil`.
+fn write_spec(
+    out: &mut String,
+    spec: Spec,
+    civil: &Civil,
+    ts: u64,
+    nanos: u64,
+    offset_minutes: i64,
+    locale: Locale,
+) -> fmt::Result {
+    match spec {
+        Spec::Year => write!(out, "{:04}", civil.year),
+        Spec::ShortYear => write!(out, "{:02}", civil.year.rem_euclid(100)),
+        Spec::Month => write!(out, "{:02}", civil.month),
+        Spec::Day => write!(out, "{:02}", civil.day),
+        Spec::SpacedDay => write!(out, "{:2}", civil.day),
+        Spec::DayOfYear => write!(
+            out,
+            "{:03}",
+            civil.days - days_from_civil(civil.year, 1, 1) + 1
+        ),
+        Spec::Hour => write!(out, "{:02}", civil.hour),
+        Spec::Hour12 => write!(out, "{:02}", (civil.hour + 11) % 12 + 1),
+        Spec::AmPm => out.write_str(if civil.hour < 12 { "AM" } else { "PM" }),
+        Spec::Minute => write!(out, "{:02}", civil.minute),
+        Spec::Second => write!(out, "{:02}", civil.second),
+        Spec::Fraction(digits) => write_fraction(out, nanos, digits),
+        Spec::Offset(separator) => write_offset(out, offset_minutes, separator),
+        Spec::DayAbbr => out.write_str(locale.day_abbr(Weekday::from_days(civil.days))),
+        Spec::DayName => out.write_str(locale.day_name(Weekday::from_days(civil.days))),
+        Spec::MonthAbbr => out.write_str(locale.month_abbr(Month::ALL[civil.month as usize - 1])),
+        Spec::MonthName => out.write_str(locale.month_name(Month::ALL[civil.month as usize - 1])),
+        Spec::Epoch => write!(out, "{}", ts),
+    }
+}
+
+/// ### git_raw(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp in Git's raw date format.
+///
+/// This is the format Git stores in commit objects and prints with `--date=raw`: the
+/// seconds since the UNIX epoch followed by the author's UTC offset as `+HHMM`/`-HHMM`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::git_raw;
+///
+/// assert_eq!(git_raw(1728933069, 120), "1728933069 +0200");
+/// assert_eq!(git_raw(1728933069, -330), "1728933069 -0530");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `1728933069 +0200`.
+///
+/// <small>End Fun Doc</small>
+pub fn git_raw(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| git_raw_into(out, ts, offset_minutes))
+}
+
+/// ### git_raw_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp in Git's raw date format into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`git_raw`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::git_raw_into;
+///
+/// let mut line = String::from("author Jane <jane@example.com> ");
+/// git_raw_into(&mut line, 1728933069, 120).unwrap();
+/// assert_eq!(line, "author Jane <jane@example.com> 1728933069 +0200");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn git_raw_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    write!(out, "{} ", ts)?;
+    write_offset(out, offset_minutes, "")
+}
+
+/// ### git_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp in Git's default human-readable date format.
+///
+/// This is the format printed by `git log` without a `--date` option, for example
+/// `Mon Oct 14 21:11:09 2024 +0200`. The date and time are shown as wall-clock time in the
+/// given UTC offset, and like Git the day of the month is not zero-padded. Timestamps after
+/// [`MAX_TS`] are shown as that instant.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::git_date;
+///
+/// assert_eq!(git_date(1728933069, 0), "Mon Oct 14 19:11:09 2024 +0000");
+/// assert_eq!(git_date(1728933069, 120), "Mon Oct 14 21:11:09 2024 +0200");
+/// assert_eq!(git_date(1727740800, -60), "Mon Sep 30 23:00:00 2024 -0100");
+/// assert_eq!(git_date(u64::MAX, 0), "Sun Dec 31 23:59:59 100000000000 +0000");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Mon Oct 14 21:11:09 2024 +0200`.
+///
+/// <small>End Fun Doc</small>
+pub fn git_date(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| git_date_into(out, ts, offset_minutes))
+}
+
+/// ### git_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp in Git's default date format into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`git_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::git_date_into;
+///
+/// let mut line = String::from("Date:   ");
+/// git_date_into(&mut line, 1728933069, 120).unwrap();
+/// assert_eq!(line, "Date:   Mon Oct 14 21:11:09 2024 +0200");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn git_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    write!(
+        out,
+        "{} {} {} {:02}:{:02}:{:02} {} ",
+        civil.day_abbr(),
+        civil.month_abbr(),
+        civil.day,
+        civil.hour,
+        civil.minute,
+        civil.second,
+        civil.year,
+    )?;
+    write_offset(out, offset_minutes, "")
+}
+
+/// ### email_date_header() -> String
+///
+/// Produces the value of an RFC 5322 `Date:` header for the current time.
+///
+/// This function formats the current time as wall-clock time in the machine's local UTC
+/// offset, e.g. `Mon, 14 Oct 2024 21:11:09 +0200`, which is what mail user agents are
+/// expected to emit. Only the header value is returned, without the `Date: ` prefix.
+/// This function requires the `tz` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::email_date_header;
+///
+/// let value = email_date_header();
+/// println!("Date: {}", value);
+/// assert_eq!(value.len(), 31);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Mon, 14 Oct 2024 21:11:09 +0200`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tz")]
+pub fn email_date_header() -> String {
+    email_date_header_at(utc_ts_sec(), tz_offset_minutes())
+}
+
+/// ### email_date_header_at(ts: u64, offset_minutes: i64) -> String
+///
+/// Produces the value of an RFC 5322 `Date:` header for a given timestamp and offset.
+///
+/// The result follows the `date-time` production of RFC 5322 section 3.3: weekday,
+/// zero-padded day, English month abbreviation, four-digit year, `HH:MM:SS` and the numeric
+/// zone. The obsolete alphabetic zones such as `GMT` are never produced.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::email_date_header_at;
+///
+/// assert_eq!(
+///     email_date_header_at(1728933069, 120),
+///     "Mon, 14 Oct 2024 21:11:09 +0200"
+/// );
+/// assert_eq!(
+///     email_date_header_at(1727740800, 0),
+///     "Tue, 01 Oct 2024 00:00:00 +0000"
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Mon, 14 Oct 2024 21:11:09 +0200`.
+///
+/// <small>End Fun Doc</small>
+pub fn email_date_header_at(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| email_date_header_into(out, ts, offset_minutes))
+}
+
+/// ### email_date_header_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes the value of an RFC 5322 `Date:` header into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`email_date_header_at`]. RSS `pubDate` values use
+/// the same layout.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::email_date_header_into;
+///
+/// let mut header = String::from("Date: ");
+/// email_date_header_into(&mut header, 1728933069, 120).unwrap();
+/// assert_eq!(header, "Date: Mon, 14 Oct 2024 21:11:09 +0200");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn email_date_header_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    write!(
+        out,
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} ",
+        civil.day_abbr(),
+        civil.day,
+        civil.month_abbr(),
+        civil.year,
+        civil.hour,
+        civil.minute,
+        civil.second,
+    )?;
+    write_offset(out, offset_minutes, "")
+}
+
+/// ### rss_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp for the `pubDate` and `lastBuildDate` elements of an RSS 2.0 feed.
+///
+/// RSS 2.0 uses the RFC 822 date-time. This function emits the RFC 5322 refinement of it
+/// with a four-digit year, which every feed reader accepts, e.g.
+/// `Mon, 14 Oct 2024 21:11:09 +0200`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::rss_date;
+///
+/// assert_eq!(rss_date(1728933069, 0), "Mon, 14 Oct 2024 19:11:09 +0000");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Mon, 14 Oct 2024 19:11:09 +0000`.
+///
+/// <small>End Fun Doc</small>
+pub fn rss_date(ts: u64, offset_minutes: i64) -> String {
+    email_date_header_at(ts, offset_minutes)
+}
+
+/// ### rfc2822(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp as an RFC 2822 date, e.g. `Mon, 14 Oct 2024 21:11:09 +0200`.
+///
+/// This is the layout of email `Date:` headers, produced by [`email_date_header_at`]; RFC
+/// 5322 has since replaced RFC 2822 without changing it. Use [`email_date_header_into`] to
+/// write it without allocating.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::rfc2822;
+///
+/// assert_eq!(rfc2822(1728933069, 120), "Mon, 14 Oct 2024 21:11:09 +0200");
+/// assert_eq!(rfc2822(1728933069, -300), "Mon, 14 Oct 2024 14:11:09 -0500");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Mon, 14 Oct 2024 21:11:09 +0200`.
+///
+/// <small>End Fun Doc</small>
+pub fn rfc2822(ts: u64, offset_minutes: i64) -> String {
+    email_date_header_at(ts, offset_minutes)
+}
+
+/// ### http_date(ts: u64) -> String
+///
+/// Formats a UNIX timestamp as an HTTP date, for headers such as `Date`, `Last-Modified` and
+/// `Expires`.
+///
+/// HTTP (RFC 9110, formerly RFC 7231) requires the IMF-fixdate layout, always in GMT, e.g.
+/// `Tue, 15 Oct 2024 08:12:31 GMT`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::http_date;
+///
+/// assert_eq!(http_date(1728979951), "Tue, 15 Oct 2024 08:12:31 GMT");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `Tue, 15 Oct 2024 08:12:31 GMT`.
+///
+/// <small>End Fun Doc</small>
+pub fn http_date(ts: u64) -> String {
+    to_string(|out| http_date_into(out, ts))
+}
+
+/// ### http_date_into<W: Write>(out: &mut W, ts: u64) -> fmt::Result
+///
+/// Writes an HTTP date into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`http_date`], for servers that write their
+/// response headers straight into an output buffer.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::http_date_into;
+///
+/// let mut header = String::from("Last-Modified: ");
+/// http_date_into(&mut header, 1728979951).unwrap();
+/// assert_eq!(header, "Last-Modified: Tue, 15 Oct 2024 08:12:31 GMT");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn http_date_into<W: Write>(out: &mut W, ts: u64) -> fmt::Result {
+    let civil = Civil::new(ts, 0);
+    write!(
+        out,
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        civil.day_abbr(),
+        civil.day,
+        civil.month_abbr(),
+        civil.year,
+        civil.hour,
+        civil.minute,
+        civil.second,
+    )
+}
+
+/// ### atom_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp for the `updated` and `published` elements of an Atom feed.
+///
+/// Atom (RFC 4287) requires RFC 3339 timestamps. The wall-clock time is shown in the given
+/// UTC offset, and an offset of zero is written as `Z`. Offsets of 24 hours or more either
+/// way saturate at 23:59.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::atom_date;
+///
+/// assert_eq!(atom_date(1728933069, 0), "2024-10-14T19:11:09Z");
+/// assert_eq!(atom_date(1728933069, 120), "2024-10-14T21:11:09+02:00");
+/// assert_eq!(atom_date(0, i64::MAX), "1970-01-01T23:59:00+23:59");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T19:11:09Z`.
+///
+/// <small>End Fun Doc</small>
+pub fn atom_date(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| atom_date_into(out, ts, offset_minutes))
+}
+
+/// ### atom_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp for an Atom `updated` or `published` element into a
+/// caller-supplied buffer.
+///
+/// This is the allocation-free form of [`atom_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::atom_date_into;
+///
+/// let mut xml = String::from("<updated>");
+/// atom_date_into(&mut xml, 1728933069, 0).unwrap();
+/// xml.push_str("</updated>");
+/// assert_eq!(xml, "<updated>2024-10-14T19:11:09Z</updated>");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn atom_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    write_rfc3339(out, ts, offset_minutes, 0, 0)
+}
+
+/// ### exif_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp for the EXIF `DateTime`, `DateTimeOriginal` and
+/// `DateTimeDigitized` tags.
+///
+/// EXIF stores the wall-clock time as `YYYY:MM:DD HH:MM:SS` without a zone. Pass the offset
+/// of the camera's clock, and write it separately with [`exif_offset`] into the matching
+/// `OffsetTime*` tag.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::exif_date;
+///
+/// assert_eq!(exif_date(1728933069, 0), "2024:10:14 19:11:09");
+/// assert_eq!(exif_date(1728933069, 120), "2024:10:14 21:11:09");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024:10:14 19:11:09`.
+///
+/// <small>End Fun Doc</small>
+pub fn exif_date(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| exif_date_into(out, ts, offset_minutes))
+}
+
+/// ### exif_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp for the EXIF date tags into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`exif_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::exif_date_into;
+///
+/// let mut tag = String::new();
+/// exif_date_into(&mut tag, 1728933069, 0).unwrap();
+/// assert_eq!(tag, "2024:10:14 19:11:09");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn exif_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    write!(
+        out,
+        "{:04}:{:02}:{:02} {:02}:{:02}:{:02}",
+        civil.year, civil.month, civil.day, civil.hour, civil.minute, civil.second,
+    )
+}
+
+/// ### exif_subsec(nanos: u64, digits: usize) -> String
+///
+/// Formats a sub-second component for the EXIF `SubSecTime*` tags.
+///
+/// The tags hold the decimal fraction of the second as plain digits, e.g. `250` for
+/// 0.25 seconds at millisecond precision. `nanos` is the sub-second part in nanoseconds
+/// and `digits` the number of digits to write, clamped to 1-9. Extra precision is truncated.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::exif_subsec;
+///
+/// assert_eq!(exif_subsec(250_000_000, 3), "250");
+/// assert_eq!(exif_subsec(7_000_000, 2), "00");
+/// assert_eq!(exif_subsec(123_456_789, 6), "123456");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of `digits` decimal digits.
+///
+/// <small>End Fun Doc</small>
+pub fn exif_subsec(nanos: u64, digits: usize) -> String {
+    to_string(|out| write_fraction(out, nanos, digits.clamp(1, 9)))
+}
+
+/// ### exif_offset(offset_minutes: i64) -> String
+///
+/// Formats a UTC offset for the EXIF `OffsetTime`, `OffsetTimeOriginal` and
+/// `OffsetTimeDigitized` tags.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::exif_offset;
+///
+/// assert_eq!(exif_offset(120), "+02:00");
+/// assert_eq!(exif_offset(-570), "-09:30");
+/// assert_eq!(exif_offset(0), "+00:00");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` in the form `+HH:MM` or `-HH:MM`.
+///
+/// <small>End Fun Doc</small>
+pub fn exif_offset(offset_minutes: i64) -> String {
+    to_string(|out| write_offset(out, offset_minutes, ":"))
+}
+
+/// ### format_sql_datetime(ts: u64) -> String
+///
+/// Formats a UNIX timestamp as an SQL `DATETIME`/`TIMESTAMP` text literal in UTC.
+///
+/// The `YYYY-MM-DD HH:MM:SS` layout is accepted by MySQL, MariaDB, PostgreSQL and SQLite
+/// alike, so the result can be quoted directly into a query or compared with log output.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_sql_datetime;
+///
+/// assert_eq!(format_sql_datetime(1728933069), "2024-10-14 19:11:09");
+/// let query = format!("SELECT * FROM events WHERE created_at < '{}'", format_sql_datetime(0));
+/// assert!(query.ends_with("'1970-01-01 00:00:00'"));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14 19:11:09`.
+///
+/// <small>End Fun Doc</small>
+pub fn format_sql_datetime(ts: u64) -> String {
+    format_sql_datetime_with_precision(ts, 0, 0)
+}
+
+/// ### format_sql_datetime_with_precision(ts: u64, nanos: u64, precision: usize) -> String
+///
+/// Formats a UNIX timestamp as an SQL `DATETIME`/`TIMESTAMP` text literal with fractional
+/// seconds.
+///
+/// `nanos` is the sub-second part in nanoseconds and `precision` the number of fractional
+/// digits, matching the `DATETIME(p)`/`TIMESTAMP(p)` column precision of MySQL and
+/// PostgreSQL. Precision is clamped to 0-6; with 0 no decimal point is written. Extra
+/// precision is truncated, not rounded, so the literal never moves into the next second.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_sql_datetime_with_precision;
+///
+/// assert_eq!(
+///     format_sql_datetime_with_precision(1728933069, 123_456_789, 3),
+///     "2024-10-14 19:11:09.123"
+/// );
+/// assert_eq!(
+///     format_sql_datetime_with_precision(1728933069, 123_456_789, 6),
+///     "2024-10-14 19:11:09.123456"
+/// );
+/// assert_eq!(
+///     format_sql_datetime_with_precision(1728933069, 123_456_789, 0),
+///     "2024-10-14 19:11:09"
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14 19:11:09.123`.
+///
+/// <small>End Fun Doc</small>
+pub fn format_sql_datetime_with_precision(ts: u64, nanos: u64, precision: usize) -> String {
+    to_string(|out| format_sql_datetime_into(out, ts, nanos, precision))
+}
+
+/// ### format_sql_datetime_into<W: Write>(out: &mut W, ts: u64, nanos: u64, precision: usize) -> fmt::Result
+///
+/// Writes a UNIX timestamp as an SQL `DATETIME`/`TIMESTAMP` text literal into a
+/// caller-supplied buffer.
+///
+/// This is the allocation-free form of [`format_sql_datetime_with_precision`], handy when
+/// building a large `INSERT` statement in one `String`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_sql_datetime_into;
+///
+/// let mut query = String::from("INSERT INTO events (at) VALUES ");
+/// for (i, ts) in [1728933069, 1728933070].into_iter().enumerate() {
+///     query.push_str(if i == 0 { "('" } else { ", ('" });
+///     format_sql_datetime_into(&mut query, ts, 0, 0).unwrap();
+///     query.push_str("')");
+/// }
+/// assert!(query.ends_with("('2024-10-14 19:11:09'), ('2024-10-14 19:11:10')"));
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn format_sql_datetime_into<W: Write>(
+    out: &mut W,
+    ts: u64,
+    nanos: u64,
+    precision: usize,
+) -> fmt::Result {
+    write_sql_datetime(out, ts, 0, nanos, precision)
+}
+
+/// ### sql_timestamp(ts: u64, nanos: u64) -> String
+///
+/// Formats a UNIX timestamp as an SQL `TIMESTAMP` text literal in UTC, with microseconds
+/// only when there is a sub-second part.
+///
+/// This is the layout PostgreSQL, MySQL and SQLite print for `TIMESTAMP(6)` columns,
+/// `YYYY-MM-DD HH:MM:SS[.ffffff]`, so the result can be interpolated into a query and read
+/// back with [`parse_sql_datetime`](crate::parse::parse_sql_datetime). `nanos` is the
+/// sub-second part in nanoseconds; below a microsecond it is truncated.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::sql_timestamp;
+/// use wtime::parse::parse_sql_datetime;
+///
+/// assert_eq!(sql_timestamp(1728933069, 0), "2024-10-14 19:11:09");
+/// assert_eq!(sql_timestamp(1728933069, 123_456_789), "2024-10-14 19:11:09.123456");
+/// assert_eq!(
+///     parse_sql_datetime(&sql_timestamp(1728933069, 500_000_000)),
+///     Ok((1728933069, 500_000_000))
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14 19:11:09.123456`.
+///
+/// <small>End Fun Doc</small>
+pub fn sql_timestamp(ts: u64, nanos: u64) -> String {
+    let precision = if nanos / 1000 == 0 { 0 } else { 6 };
+    format_sql_datetime_with_precision(ts, nanos, precision)
+}
+
+/// ### sql_date(ts: u64) -> String
+///
+/// Formats the UTC date of a UNIX timestamp as an SQL `DATE` text literal, `YYYY-MM-DD`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::sql_date;
+///
+/// assert_eq!(sql_date(1728933069), "2024-10-14");
+/// let query = format!("SELECT * FROM orders WHERE day = '{}'", sql_date(0));
+/// assert!(query.ends_with("'1970-01-01'"));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14`.
+///
+/// <small>End Fun Doc</small>
+pub fn sql_date(ts: u64) -> String {
+    let civil = Civil::new(ts, 0);
+    format!("{:04}-{:02}-{:02}", civil.year, civil.month, civil.day)
+}
+
+/// ### ordinal_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp as an ISO 8601 ordinal date, `YYYY-DDD`.
+///
+/// Ordinal dates number the days of the year from 001 to 365, or 366 in a leap year. They
+/// are common in logistics, meteorology and batch or lot codes.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::ordinal_date;
+///
+/// assert_eq!(ordinal_date(1728933069, 0), "2024-288");
+/// assert_eq!(ordinal_date(1704067200, 0), "2024-001");
+/// // Already October 15 in UTC+05:00
+/// assert_eq!(ordinal_date(1728933069, 300), "2024-289");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-288`.
+///
+/// <small>End Fun Doc</small>
+pub fn ordinal_date(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| ordinal_date_into(out, ts, offset_minutes))
+}
+
+/// ### ordinal_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp as an ISO 8601 ordinal date into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`ordinal_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::ordinal_date_into;
+///
+/// let mut lot = String::from("LOT-");
+/// ordinal_date_into(&mut lot, 1728933069, 0).unwrap();
+/// assert_eq!(lot, "LOT-2024-288");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn ordinal_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    write!(
+        out,
+        "{:04}-{:03}",
+        civil.year,
+        civil.days - days_from_civil(civil.year, 1, 1) + 1
+    )
+}
+
+/// ### week_date(ts: u64, offset_minutes: i64) -> String
+///
+/// Formats a UNIX timestamp as an ISO 8601 week date, `YYYY-Www-D`.
+///
+/// The year is the ISO week-based year, which differs from the calendar year for a few
+/// days around New Year, and `D` is the weekday from 1 (Monday) to 7 (Sunday). See
+/// [`iso_week_date`](crate::calc::iso_week_date).
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::week_date;
+///
+/// assert_eq!(week_date(1728933069, 0), "2024-W42-1");
+/// // Monday, December 30, 2024
+/// assert_eq!(week_date(1735516800, 0), "2025-W01-1");
+/// // Sunday, January 3, 2021
+/// assert_eq!(week_date(1609632000, 0), "2020-W53-7");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-W42-1`.
+///
+/// <small>End Fun Doc</small>
+pub fn week_date(ts: u64, offset_minutes: i64) -> String {
+    to_string(|out| week_date_into(out, ts, offset_minutes))
+}
+
+/// ### week_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result
+///
+/// Writes a UNIX timestamp as an ISO 8601 week date into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`week_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::week_date_into;
+///
+/// let mut sprint = String::from("sprint ");
+/// week_date_into(&mut sprint, 1728933069, 0).unwrap();
+/// assert_eq!(sprint, "sprint 2024-W42-1");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn week_date_into<W: Write>(out: &mut W, ts: u64, offset_minutes: i64) -> fmt::Result {
+    let civil = Civil::new(ts, offset_minutes);
+    let (week_year, week, weekday) = iso_week_date(civil.year as u64, civil.month, civil.day);
+    write!(
+        out,
+        "{:04}-W{:02}-{}",
+        week_year,
+        week,
+        weekday.number_from_monday()
+    )
+}
+
+/// ### iso_duration(secs: u64) -> String
+///
+/// Formats a number of seconds as an ISO 8601 duration.
+///
+/// Days are written as exact 24-hour days; years and months are never produced because their
+/// length depends on the calendar. A zero duration is written as `PT0S`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::iso_duration;
+///
+/// assert_eq!(iso_duration(86_400), "P1D");
+/// assert_eq!(iso_duration(5_400), "PT1H30M");
+/// assert_eq!(iso_duration(90_061), "P1DT1H1M1S");
+/// assert_eq!(iso_duration(0), "PT0S");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `P1DT2H30M`.
+///
+/// <small>End Fun Doc</small>
+pub fn iso_duration(secs: u64) -> String {
+    if secs == 0 {
+        return "PT0S".to_string();
+    }
+
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    let mut duration = String::from("P");
+    if days > 0 {
+        duration.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || seconds > 0 {
+        duration.push('T');
+    }
+    for (value, unit) in [(hours, 'H'), (minutes, 'M'), (seconds, 'S')] {
+        if value > 0 {
+            duration.push_str(&format!("{}{}", value, unit));
+        }
+    }
+    duration
+}
+
+/// ### iso_interval(range: &TimeRange) -> String
+///
+/// Formats a [`TimeRange`] in ISO 8601 `start/end` interval notation.
+///
+/// Both endpoints are written as RFC 3339 timestamps in UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::iso_interval;
+/// use wtime::range::TimeRange;
+///
+/// let day = TimeRange::new(1728864000, 1728950400).unwrap();
+/// assert_eq!(iso_interval(&day), "2024-10-14T00:00:00Z/2024-10-15T00:00:00Z");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T00:00:00Z/2024-10-15T00:00:00Z`.
+///
+/// <small>End Fun Doc</small>
+pub fn iso_interval(range: &TimeRange) -> String {
+    format!(
+        "{}/{}",
+        atom_date(range.start(), 0),
+        atom_date(range.end(), 0)
+    )
+}
+
+/// ### iso_interval_with_duration(range: &TimeRange) -> String
+///
+/// Formats a [`TimeRange`] in ISO 8601 `start/duration` interval notation.
+///
+/// The start is written as an RFC 3339 timestamp in UTC and the length with
+/// [`iso_duration`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::iso_interval_with_duration;
+/// use wtime::range::TimeRange;
+///
+/// let day = TimeRange::new(1728864000, 1728950400).unwrap();
+/// assert_eq!(iso_interval_with_duration(&day), "2024-10-14T00:00:00Z/P1D");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T00:00:00Z/P1D`.
+///
+/// <small>End Fun Doc</small>
+pub fn iso_interval_with_duration(range: &TimeRange) -> String {
+    format!(
+        "{}/{}",
+        atom_date(range.start(), 0),
+        iso_duration(range.end() - range.start())
+    )
+}
+
+/// ### prometheus_duration(duration: Duration) -> String
+///
+/// Formats a `Duration` in the style of Prometheus and Grafana configuration files.
+///
+/// The output matches what Prometheus itself prints: units from `y` (365 days) down to
+/// `ms`, each used at most once, with years and weeks only used when the remainder is a
+/// whole number of them. Precision below one millisecond is truncated and a zero duration
+/// is `0s`.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::format::prometheus_duration;
+///
+/// assert_eq!(prometheus_duration(Duration::from_secs(5_400)), "1h30m");
+/// assert_eq!(prometheus_duration(Duration::from_secs(129_600)), "1d12h");
+/// assert_eq!(prometheus_duration(Duration::from_secs(1_209_600)), "2w");
+/// assert_eq!(prometheus_duration(Duration::from_secs(400 * 86_400)), "400d");
+/// assert_eq!(prometheus_duration(Duration::from_secs(730 * 86_400)), "2y");
+/// assert_eq!(prometheus_duration(Duration::from_millis(1_500)), "1s500ms");
+/// assert_eq!(prometheus_duration(Duration::ZERO), "0s");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `1d12h`.
+///
+/// <small>End Fun Doc</small>
+pub fn prometheus_duration(duration: Duration) -> String {
+    const UNITS: [(&str, u128, bool); 7] = [
+        ("y", 365 * 86_400_000, true),
+        ("w", 7 * 86_400_000, true),
+        ("d", 86_400_000, false),
+        ("h", 3_600_000, false),
+        ("m", 60_000, false),
+        ("s", 1_000, false),
+        ("ms", 1, false),
+    ];
+
+    let mut millis = duration.as_millis();
+    if millis == 0 {
+        return "0s".to_string();
+    }
+
+    let mut formatted = String::new();
+    for (unit, unit_millis, exact) in UNITS {
+        if exact && millis % unit_millis != 0 {
+            continue;
+        }
+        let value = millis / unit_millis;
+        if value > 0 {
+            formatted.push_str(&format!("{}{}", value, unit));
+            millis -= value * unit_millis;
+        }
+    }
+    formatted
+}
+
+/// ### Granularity
+///
+/// The smallest unit written by [`relative_with_granularity`].
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Granularity {
+    /// Seconds, e.g. `12 seconds ago`.
+    Second,
+    /// Minutes, e.g. `5 minutes ago`.
+    Minute,
+    /// Hours, e.g. `3 hours ago`.
+    Hour,
+    /// Days, e.g. `in 2 days`.
+    Day,
+    /// Weeks of 7 days.
+    Week,
+    /// Months of 30 days.
+    Month,
+    /// Years of 365 days.
+    Year,
+}
+
+impl Granularity {
+    const ALL: [Granularity; 7] = [
+        Granularity::Year,
+        Granularity::Month,
+        Granularity::Week,
+        Granularity::Day,
+        Granularity::Hour,
+        Granularity::Minute,
+        Granularity::Second,
+    ];
+
+    fn secs(self) -> u64 {
+        match self {
+            Granularity::Second => 1,
+            Granularity::Minute => 60,
+            Granularity::Hour => 3600,
+            Granularity::Day => 86_400,
+            Granularity::Week => 7 * 86_400,
+            Granularity::Month => 30 * 86_400,
+            Granularity::Year => 365 * 86_400,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Granularity::Second => "second",
+            Granularity::Minute => "minute",
+            Granularity::Hour => "hour",
+            Granularity::Day => "day",
+            Granularity::Week => "week",
+            Granularity::Month => "month",
+            Granularity::Year => "year",
+        }
+    }
+}
+
+/// ### relative(ts: u64, now: u64) -> String
+///
+/// Describes a timestamp relative to `now` in English, e.g. `5 hours ago` or `in 2 days`.
+///
+/// Differences under a minute are written as `just now`. This is
+/// [`relative_with_granularity`] with [`Granularity::Minute`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::relative;
+///
+/// let now = 1728933069;
+/// assert_eq!(relative(now - 20, now), "just now");
+/// assert_eq!(relative(now - 60, now), "1 minute ago");
+/// assert_eq!(relative(now - 5 * 3600 - 1800, now), "5 hours ago");
+/// assert_eq!(relative(now + 2 * 86_400, now), "in 2 days");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `3 minutes ago`.
+///
+/// <small>End Fun Doc</small>
+pub fn relative(ts: u64, now: u64) -> String {
+    relative_with_granularity(ts, now, Granularity::Minute)
+}
+
+/// ### relative_with_granularity(ts: u64, now: u64, granularity: Granularity) -> String
+///
+/// Describes a timestamp relative to `now` in English, using no unit smaller than
+/// `granularity`.
+///
+/// The largest unit that fits is used and the count is rounded down, so 95 minutes is
+/// `1 hour ago`. Months are 30 days and years 365 days. Differences smaller than one
+/// `granularity` unit are written as `just now`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::{relative_with_granularity, Granularity};
+///
+/// let now = 1728933069;
+/// assert_eq!(relative_with_granularity(now - 20, now, Granularity::Second), "20 seconds ago");
+/// assert_eq!(relative_with_granularity(now + 3600, now, Granularity::Day), "just now");
+/// assert_eq!(relative_with_granularity(now - 400 * 86_400, now, Granularity::Day), "1 year ago");
+/// assert_eq!(relative_with_granularity(now, now, Granularity::Second), "just now");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `in 2 weeks`.
+///
+/// <small>End Fun Doc</small>
+pub fn relative_with_granularity(ts: u64, now: u64, granularity: Granularity) -> String {
+    let diff = ts.abs_diff(now);
+    let unit = Granularity::ALL
+        .into_iter()
+        .filter(|unit| *unit >= granularity)
+        .find(|unit| diff >= unit.secs());
+
+    let Some(unit) = unit else {
+        return "just now".to_string();
+    };
+    let count = diff / unit.secs();
+    let plural = if count == 1 { "" } else { "s" };
+    if ts < now {
+        format!("{} {}{} ago", count, unit.name(), plural)
+    } else {
+        format!("in {} {}{}", count, unit.name(), plural)
+    }
+}
+
+/// ### format_ts(ts: u64, nanos: u64) -> String
+///
+/// Formats a timestamp using the application's configured layout and zone.
+///
+/// The layout is the `default_format` and the zone the `default_zone` of the current
+/// [`Config`](crate::config::Config), so an application can pick its conventions once with
+/// [`config::set`](crate::config::set). `nanos` is the sub-second part in nanoseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, TimestampFormat, Zone};
+/// use wtime::format::format_ts;
+///
+/// // The built-in default is the crate's own layout in UTC
+/// assert_eq!(
+///     format_ts(1728933069, 123_456_789),
+///     "2024-10-14-19-11-09-123-456789"
+/// );
+///
+/// let rfc3339_india = Config {
+///     default_format: TimestampFormat::Rfc3339,
+///     default_zone: Zone::Fixed(330),
+///     ..Config::default()
+/// };
+/// config::scoped(rfc3339_india, || {
+///     assert_eq!(
+///         format_ts(1728933069, 123_456_789),
+///         "2024-10-15T00:41:09.123+05:30"
+///     );
+/// });
+/// ```
+///
+/// ### Returns
+///
+/// Returns the formatted timestamp as a `String`.
+///
+/// <small>End Fun Doc</small>
+pub fn format_ts(ts: u64, nanos: u64) -> String {
+    to_string(|out| format_ts_into(out, ts, nanos))
+}
+
+/// ### format_ts_into<W: Write>(out: &mut W, ts: u64, nanos: u64) -> fmt::Result
+///
+/// Writes a timestamp using the application's configured layout and zone into a
+/// caller-supplied buffer.
+///
+/// This is the allocation-free form of [`format_ts`]. Reusing one buffer across calls keeps
+/// a hot logging path free of per-timestamp heap allocations.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_ts_into;
+///
+/// let mut line = String::with_capacity(64);
+/// for nanos in [0, 500_000_000] {
+///     line.clear();
+///     format_ts_into(&mut line, 1728933069, nanos).unwrap();
+///     line.push_str(" request served");
+///     println!("{}", line);
+/// }
+/// assert_eq!(line, "2024-10-14-19-11-09-500-000000 request served");
+///
+/// // Whole seconds in `nanos` carry over instead of being dropped
+/// line.clear();
+/// format_ts_into(&mut line, 1728933069, 1_500_000_000).unwrap();
+/// assert_eq!(line, "2024-10-14-19-11-10-500-000000");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn format_ts_into<W: Write>(out: &mut W, ts: u64, nanos: u64) -> fmt::Result {
+    let config = config::get();
+    let offset_minutes = config.default_zone.offset_minutes_at(ts);
+
+    match config.default_format {
+        TimestampFormat::Wtime => {
+            let since = Duration::from_secs(ts).saturating_add(Duration::from_nanos(nanos));
+            write_wtime_ts(out, since, clamp_offset(offset_minutes) * 60)
+        }
+        TimestampFormat::Rfc3339 => write_rfc3339(out, ts, offset_minutes, nanos, 3),
+        TimestampFormat::Sql => write_sql_datetime(out, ts, offset_minutes, nanos, 6),
+        TimestampFormat::Email => email_date_header_into(out, ts, offset_minutes),
+    }
+}
+
+/// ### format_with(pattern: &str, ts: u64, nanos: u64, offset_minutes: i64) -> Result<String, WTimeError>
+///
+/// Formats a timestamp with a strftime-style pattern.
+///
+/// The wall-clock time is shown in the given UTC offset and `nanos` is the sub-second part
+/// in nanoseconds. Characters other than specifiers are copied as they are. The supported
+/// specifiers are:
+///
+/// | Specifier | Meaning | Example |
+/// | :--- | :--- | :--- |
+/// | `%Y` | year, at least 4 digits | `2024` |
+/// | `%y` | year within the century, 2 digits | `24` |
+/// | `%m` | month, 2 digits | `10` |
+/// | `%d` | day of the month, 2 digits | `04` |
+/// | `%e` | day of the month, space-padded | ` 4` |
+/// | `%j` | day of the year, 3 digits | `288` |
+/// | `%H` | hour (00-23) | `19` |
+/// | `%I` | hour (01-12) | `07` |
+/// | `%p` | `AM` or `PM` | `PM` |
+/// | `%M` | minute | `11` |
+/// | `%S` | second | `09` |
+/// | `%f` | nanoseconds, 9 digits | `123456789` |
+/// | `%3f`, `%6f`, `%9f` | fraction truncated to 3, 6 or 9 digits | `123` |
+/// | `%z` | UTC offset as `+HHMM` | `+0530` |
+/// | `%:z` | UTC offset as `+HH:MM` | `+05:30` |
+/// | `%a`, `%A` | abbreviated and full weekday name | `Mon`, `Monday` |
+/// | `%b`, `%B` | abbreviated and full month name | `Oct`, `October` |
+/// | `%s` | seconds since the UNIX epoch | `1728933069` |
+/// | `%%` | a literal `%` | `%` |
+///
+/// Weekday and month names are written in the `locale` of the current
+/// [`Config`](crate::config::Config), English by default.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::{self, Config, Locale};
+/// use wtime::format::format_with;
+///
+/// let ts = 1728933069; // 2024-10-14 19:11:09 UTC
+///
+/// assert_eq!(
+///     format_with("%Y/%m/%d %H:%M:%S", ts, 0, 0).unwrap(),
+///     "2024/10/14 19:11:09"
+/// );
+/// assert_eq!(
+///     format_with("%d.%m.%y %I:%M %p (%:z)", ts, 0, 330).unwrap(),
+///     "15.10.24 12:41 AM (+05:30)"
+/// );
+/// assert_eq!(
+///     format_with("%A, %B %e at %H:%M:%S.%3f", ts, 123_456_789, 0).unwrap(),
+///     "Monday, October 14 at 19:11:09.123"
+/// );
+/// assert!(format_with("%Q", ts, 0, 0).is_err());
+///
+/// let arabic = Config {
+///     locale: Locale::Arabic,
+///     ..Config::default()
+/// };
+/// config::scoped(arabic, || {
+///     assert_eq!(format_with("%A %d %B %Y", ts, 0, 0).unwrap(), "الاثنين 14 أكتوبر 2024");
+/// });
+/// ```
+///
+/// ### Returns
+///
+/// Returns the formatted `String`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the pattern contains an unknown specifier or
+/// ends with a lone `%`.
+///
+/// <small>End Fun Doc</small>
+pub fn format_with(
+    pattern: &str,
+    ts: u64,
+    nanos: u64,
+    offset_minutes: i64,
+) -> Result<String, WTimeError> {
+    let pieces = parse_pattern(pattern)?;
+    let civil = Civil::new(ts, offset_minutes);
+    let locale = config::get().locale;
+    let mut out = String::with_capacity(pattern.len() + 16);
+
+    // Writing into a `String` never fails
+    for piece in pieces {
+        let _ = match piece {
+            Piece::Literal(text) => out.write_str(text),
+            Piece::Spec(spec) => {
+                write_spec(&mut out, spec, &civil, ts, nanos, offset_minutes, locale)
+            }
+        };
+    }
+
+    Ok(out)
+}
+
+/// ### format_utc_with(pattern: &str) -> Result<String, WTimeError>
+///
+/// Formats the current UTC time with a strftime-style pattern.
+///
+/// See [`format_with`] for the supported specifiers. `%z` is written as `+0000`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_utc_with;
+///
+/// let stamp = format_utc_with("%Y-%m-%dT%H:%M:%S.%6fZ").unwrap();
+/// println!("UTC: {}", stamp);
+/// assert_eq!(stamp.len(), 27);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the formatted `String`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the pattern is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn format_utc_with(pattern: &str) -> Result<String, WTimeError> {
+    let now = duration_since();
+    format_with(pattern, now.as_secs(), now.subsec_nanos() as u64, 0)
+}
+
+/// ### format_local_with(pattern: &str) -> Result<String, WTimeError>
+///
+/// Formats the current local time with a strftime-style pattern.
+///
+/// The time is shown in the machine's local UTC offset, which `%z` and `%:z` print. See
+/// [`format_with`] for the supported specifiers. This function requires the `tz` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_local_with;
+///
+/// let stamp = format_local_with("%a %d %b %Y %H:%M:%S %z").unwrap();
+/// println!("Local: {}", stamp);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the formatted `String`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the pattern is invalid.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tz")]
+pub fn format_local_with(pattern: &str) -> Result<String, WTimeError> {
+    let now = duration_since();
+    format_with(
+        pattern,
+        now.as_secs(),
+        now.subsec_nanos() as u64,
+        tz_offset_minutes(),
+    )
+}
+
+/// ### FormatMany<'a, I>
+///
+/// An iterator formatting UNIX timestamps with one strftime-style pattern, created by
+/// [`format_iter`].
+///
+/// The pattern is parsed once. The parts of the output that only depend on the date, such
+/// as `%Y-%m-%d` or `%A`, are rendered once per day and reused, so sorted data with many
+/// timestamps per day mostly pays for the time of day.
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone)]
+pub struct FormatMany<'a, I> {
+    timestamps: I,
+    capacity: usize,
+    locale: Locale,
+    /// The runs of literals and date specifiers between the time specifiers.
+    runs: Vec<Vec<Piece<'a>>>,
+    /// The time specifiers, one between each pair of runs.
+    times: Vec<Spec>,
+    /// The days since the epoch `rendered` was rendered for.
+    day: Option<i64>,
+    rendered: Vec<String>,
+}
+
+impl<I: Iterator<Item = u64>> Iterator for FormatMany<'_, I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let ts = self.timestamps.next()?;
+        let civil = Civil::new(ts, 0);
+
+        // Writing into a `String` never fails, so the `fmt::Result`s below are ignored
+        if self.day != Some(civil.days) {
+            self.day = Some(civil.days);
+            for (run, rendered) in self.runs.iter().zip(&mut self.rendered) {
+                rendered.clear();
+                for piece in run {
+                    let _ = match *piece {
+                        Piece::Literal(text) => rendered.write_str(text),
+                        Piece::Spec(spec) => {
+                            write_spec(rendered, spec, &civil, ts, 0, 0, self.locale)
+                        }
+                    };
+                }
+            }
+        }
+
+        let mut out = String::with_capacity(self.capacity);
+        out.push_str(&self.rendered[0]);
+        for (spec, rendered) in self.times.iter().zip(&self.rendered[1..]) {
+            let _ = write_spec(&mut out, *spec, &civil, ts, 0, 0, self.locale);
+            out.push_str(rendered);
+        }
+        Some(out)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.timestamps.size_hint()
+    }
+}
+
+/// ### format_iter<I: IntoIterator<Item = u64>>(timestamps: I, pattern: &str) -> Result<FormatMany<'_, I::IntoIter>, WTimeError>
+///
+/// Lazily formats UNIX timestamps in UTC with a strftime-style pattern.
+///
+/// Each item equals `format_with(pattern, ts, 0, 0)`, see [`format_with`] for the
+/// specifiers, but the pattern is parsed and the `locale` of the current
+/// [`Config`](crate::config::Config) read once, and the date parts are cached per day. Use
+/// it to stream a large export without collecting every line first.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_iter;
+///
+/// let rows = [1728933069, 1728933070, 1729000000];
+/// let mut lines = format_iter(rows, "%a %d %b %Y %H:%M:%S").unwrap();
+///
+/// assert_eq!(lines.next().unwrap(), "Mon 14 Oct 2024 19:11:09");
+/// assert_eq!(lines.next().unwrap(), "Mon 14 Oct 2024 19:11:10");
+/// assert_eq!(lines.next().unwrap(), "Tue 15 Oct 2024 13:46:40");
+/// assert_eq!(lines.next(), None);
+///
+/// assert!(format_iter(rows, "%Q").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`FormatMany`] iterator of `String`s.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the pattern is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn format_iter<I: IntoIterator<Item = u64>>(
+    timestamps: I,
+    pattern: &str,
+) -> Result<FormatMany<'_, I::IntoIter>, WTimeError> {
+    let mut runs = vec![Vec::new()];
+    let mut times = Vec::new();
+    for piece in parse_pattern(pattern)? {
+        match piece {
+            Piece::Spec(spec) if !spec.is_daily() => {
+                times.push(spec);
+                runs.push(Vec::new());
+            }
+            // `runs` always holds the run being built last
+            _ => runs.last_mut().unwrap().push(piece),
+        }
+    }
+
+    Ok(FormatMany {
+        timestamps: timestamps.into_iter(),
+        capacity: pattern.len() + 16,
+        locale: config::get().locale,
+        rendered: vec![String::new(); runs.len()],
+        runs,
+        times,
+        day: None,
+    })
+}
+
+/// ### format_many(timestamps: &[u64], pattern: &str) -> Result<Vec<String>, WTimeError>
+///
+/// Formats a batch of UNIX timestamps in UTC with a strftime-style pattern.
+///
+/// The result equals calling `format_with(pattern, ts, 0, 0)` for every timestamp, see
+/// [`format_with`] for the specifiers, but is faster for large datasets: the pattern is
+/// parsed once and the date parts are rendered once per day. It collects [`format_iter`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::format_many;
+///
+/// let created = [1728933069, 1728936669, 1729000000];
+/// assert_eq!(
+///     format_many(&created, "%Y-%m-%dT%H:%M:%SZ").unwrap(),
+///     ["2024-10-14T19:11:09Z", "2024-10-14T20:11:09Z", "2024-10-15T13:46:40Z"]
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns one `String` per timestamp, in the same order.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the pattern is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn format_many(timestamps: &[u64], pattern: &str) -> Result<Vec<String>, WTimeError> {
+    Ok(format_iter(timestamps.iter().copied(), pattern)?.collect())
+}
@@ -0,0 +1,157 @@
+use super::utc::utc_now;
+use std::fs::{self, ReadDir};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// ### OlderFiles
+///
+/// Iterator over the files of a directory whose last modification time is older than a
+/// given age.
+///
+/// Created by [`files_older_than`] and [`files_older_than_recursive`]. Each item is an
+/// `io::Result<PathBuf>`, so a single unreadable entry does not stop the walk; callers
+/// decide whether to skip or abort on errors.
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug)]
+pub struct OlderFiles {
+    pending: Vec<ReadDir>,
+    cutoff: SystemTime,
+    recursive: bool,
+}
+
+impl OlderFiles {
+    fn new(dir: &Path, age: Duration, recursive: bool) -> io::Result<Self> {
+        // An age reaching back before the UNIX epoch matches nothing
+        let cutoff = utc_now().checked_sub(age).unwrap_or(UNIX_EPOCH);
+
+        Ok(OlderFiles {
+            pending: vec![fs::read_dir(dir)?],
+            cutoff,
+            recursive,
+        })
+    }
+}
+
+impl Iterator for OlderFiles {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.pending.last_mut()?.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(err)) => return Some(Err(err)),
+                None => {
+                    // Current directory exhausted, continue with its parent
+                    self.pending.pop();
+                    continue;
+                }
+            };
+
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if file_type.is_dir() {
+                if self.recursive {
+                    match fs::read_dir(entry.path()) {
+                        Ok(read_dir) => self.pending.push(read_dir),
+                        Err(err) => return Some(Err(err)),
+                    }
+                }
+                continue;
+            }
+
+            match entry.metadata().and_then(|metadata| metadata.modified()) {
+                Ok(modified) if modified < self.cutoff => return Some(Ok(entry.path())),
+                Ok(_) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// ### files_older_than(dir: impl AsRef<Path>, age: Duration) -> io::Result<OlderFiles>
+///
+/// Lists the files in a directory whose modification time is older than `age`.
+///
+/// This function compares each file's last modification time (mtime) against the current
+/// UTC time minus `age` and yields the paths of the files that are older. Only the top level
+/// of `dir` is inspected; sub-directories are skipped. Use [`files_older_than_recursive`]
+/// to walk the whole tree. This is a building block for retention and cleanup jobs.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::fs::files_older_than;
+///
+/// let dir = std::env::temp_dir();
+/// let week = Duration::from_secs(7 * 86_400);
+///
+/// for path in files_older_than(&dir, week).unwrap().flatten() {
+///     println!("Older than a week: {}", path.display());
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns an [`OlderFiles`] iterator yielding `io::Result<PathBuf>` items.
+///
+/// ### Errors
+///
+/// Returns an error if `dir` cannot be read. Errors on individual entries are yielded by the
+/// iterator instead.
+///
+/// <small>End Fun Doc</small>
+pub fn files_older_than(dir: impl AsRef<Path>, age: Duration) -> io::Result<OlderFiles> {
+    OlderFiles::new(dir.as_ref(), age, false)
+}
+
+/// ### files_older_than_recursive(dir: impl AsRef<Path>, age: Duration) -> io::Result<OlderFiles>
+///
+/// Lists the files in a directory tree whose modification time is older than `age`.
+///
+/// This function works like [`files_older_than`] but also descends into every
+/// sub-directory. Directories themselves are never yielded, only the files they contain.
+/// Symbolic links are not followed.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::fs::files_older_than_recursive;
+///
+/// let dir = std::env::temp_dir().join("wtime-doc-recursive");
+/// std::fs::create_dir_all(dir.join("nested")).unwrap();
+/// std::fs::write(dir.join("nested").join("log.txt"), "entry").unwrap();
+///
+/// // Every file is older than zero seconds once it has been written
+/// std::thread::sleep(Duration::from_millis(20));
+/// let old: Vec<_> = files_older_than_recursive(&dir, Duration::ZERO)
+///     .unwrap()
+///     .flatten()
+///     .collect();
+/// assert!(old.contains(&dir.join("nested").join("log.txt")));
+///
+/// // Nothing has been around for a hundred years
+/// let ancient = files_older_than_recursive(&dir, Duration::from_secs(100 * 365 * 86_400));
+/// assert_eq!(ancient.unwrap().count(), 0);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+///
+/// ### Returns
+///
+/// Returns an [`OlderFiles`] iterator yielding `io::Result<PathBuf>` items.
+///
+/// ### Errors
+///
+/// Returns an error if `dir` cannot be read. Errors on individual entries and
+/// sub-directories are yielded by the iterator instead.
+///
+/// <small>End Fun Doc</small>
+pub fn files_older_than_recursive(dir: impl AsRef<Path>, age: Duration) -> io::Result<OlderFiles> {
+    OlderFiles::new(dir.as_ref(), age, true)
+}
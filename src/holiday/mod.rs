@@ -0,0 +1,238 @@
+use super::calc::{add_days, nth_weekday_of_month, Weekday, Weekend};
+
+/// How far the business-day searches look before giving up, about ten years.
+const MAX_SEARCH_DAYS: u32 = 3660;
+
+/// ### HolidayCalendar
+///
+/// A region's non-working days: its weekend and its public holidays.
+///
+/// The crate ships no holiday data. Applications describe their own holidays, usually with
+/// [`Holidays`], or implement `is_holiday` over their own source such as a database table.
+/// The provided methods then skip both weekends and holidays. A calendar without business
+/// days, such as one whose weekend is all seven days, makes the searches return `None`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::holiday::HolidayCalendar;
+///
+/// /// Company shutdown between Christmas and New Year
+/// struct Shutdown;
+///
+/// impl HolidayCalendar for Shutdown {
+///     fn is_holiday(&self, (_, month, day): (u64, u64, u64)) -> bool {
+///         (month == 12 && day >= 24) || (month == 1 && day == 1)
+///     }
+/// }
+///
+/// assert!(!Shutdown.is_business_day((2024, 12, 27)));
+/// assert_eq!(Shutdown.next_business_day((2024, 12, 20)), Some((2024, 12, 23)));
+/// assert_eq!(Shutdown.next_business_day((2024, 12, 23)), Some((2025, 1, 2)));
+/// ```
+///
+/// <small>End Trait Doc</small>
+pub trait HolidayCalendar {
+    /// Returns `true` if `(year, month, day)` is a holiday.
+    fn is_holiday(&self, date: (u64, u64, u64)) -> bool;
+
+    /// Returns the weekend days, Saturday and Sunday unless overridden.
+    fn weekend(&self) -> Weekend {
+        Weekend::default()
+    }
+
+    /// Returns `true` if the date is neither a weekend day nor a holiday.
+    fn is_business_day(&self, date: (u64, u64, u64)) -> bool {
+        self.weekend().is_business_day(date) && !self.is_holiday(date)
+    }
+
+    /// Returns the first business day after `date`, or `None` if there is none within
+    /// about ten years.
+    fn next_business_day(&self, date: (u64, u64, u64)) -> Option<(u64, u64, u64)> {
+        search_business_day(self, date, 1)
+    }
+
+    /// Returns the last business day before `date`, or `None` if there is none within about
+    /// ten years or before 0000-01-01.
+    fn previous_business_day(&self, date: (u64, u64, u64)) -> Option<(u64, u64, u64)> {
+        search_business_day(self, date, -1)
+    }
+}
+
+/// Steps from `date` one day at a time in the direction of `step` to a business day.
+fn search_business_day<H: HolidayCalendar + ?Sized>(
+    calendar: &H,
+    date: (u64, u64, u64),
+    step: i64,
+) -> Option<(u64, u64, u64)> {
+    if calendar.weekend() == Weekend::new(&Weekday::ALL) {
+        return None;
+    }
+    let mut date = date;
+    for _ in 0..MAX_SEARCH_DAYS {
+        let next = add_days(date, step);
+        if next == date {
+            return None;
+        }
+        date = next;
+        if calendar.is_business_day(date) {
+            return Some(date);
+        }
+    }
+    None
+}
+
+/// ### Holiday
+///
+/// One rule of a [`Holidays`] calendar.
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Holiday {
+    /// The same month and day every year, e.g. December 25.
+    Fixed {
+        /// The month, 1-12.
+        month: u64,
+        /// The day of the month.
+        day: u64,
+    },
+    /// The `nth` given weekday of a month, e.g. the fourth Thursday of November. A negative
+    /// `nth` counts from the end of the month, so `-1` is the last one.
+    Floating {
+        /// The month, 1-12.
+        month: u64,
+        /// The weekday.
+        weekday: Weekday,
+        /// Which occurrence of the weekday: 1 to 5, or -1 to -5 from the end.
+        nth: i64,
+    },
+    /// A single date, e.g. a one-off national day of mourning.
+    Date {
+        /// The year.
+        year: u64,
+        /// The month, 1-12.
+        month: u64,
+        /// The day of the month.
+        day: u64,
+    },
+}
+
+impl Holiday {
+    /// Returns `true` if the rule falls on `(year, month, day)`.
+    pub fn matches(&self, date: (u64, u64, u64)) -> bool {
+        let (year, month, day) = date;
+        match *self {
+            Holiday::Fixed {
+                month: holiday_month,
+                day: holiday_day,
+            } => (month, day) == (holiday_month, holiday_day),
+            Holiday::Floating {
+                month: holiday_month,
+                weekday,
+                nth,
+            } => {
+                month == holiday_month
+                    && nth_weekday_of_month(year, month, nth, weekday) == Ok(date)
+            }
+            Holiday::Date {
+                year: holiday_year,
+                month: holiday_month,
+                day: holiday_day,
+            } => date == (holiday_year, holiday_month, holiday_day),
+        }
+    }
+}
+
+/// ### Holidays
+///
+/// A [`HolidayCalendar`] built from fixed, floating and one-off holiday rules.
+///
+/// Start from [`Holidays::new`] and chain the rules. A holiday that falls on a weekend is
+/// not moved to a weekday; add the observed date with [`Holidays::date`] where a region
+/// does that.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{Weekday, Weekend};
+/// use wtime::holiday::{HolidayCalendar, Holidays};
+///
+/// let us_federal = Holidays::new()
+///     .fixed(1, 1) // New Year's Day
+///     .floating(1, Weekday::Monday, 3) // Martin Luther King Jr. Day
+///     .floating(5, Weekday::Monday, -1) // Memorial Day
+///     .fixed(7, 4) // Independence Day
+///     .floating(9, Weekday::Monday, 1) // Labor Day
+///     .floating(11, Weekday::Thursday, 4) // Thanksgiving
+///     .fixed(12, 25); // Christmas Day
+///
+/// assert!(us_federal.is_holiday((2024, 11, 28)));
+/// assert!(us_federal.is_holiday((2024, 5, 27)));
+/// assert!(!us_federal.is_business_day((2024, 7, 4)));
+///
+/// assert_eq!(us_federal.next_business_day((2024, 11, 27)), Some((2024, 11, 29)));
+/// assert_eq!(us_federal.previous_business_day((2024, 5, 28)), Some((2024, 5, 24)));
+///
+/// let closed = Holidays::new().with_weekend(Weekend::new(&Weekday::ALL));
+/// assert_eq!(closed.next_business_day((2024, 11, 27)), None);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Holidays {
+    weekend: Weekend,
+    rules: Vec<Holiday>,
+}
+
+impl Holidays {
+    /// Creates a calendar with a Saturday-Sunday weekend and no holidays.
+    pub fn new() -> Self {
+        Holidays::default()
+    }
+
+    /// Replaces the weekend days.
+    pub fn with_weekend(self, weekend: Weekend) -> Self {
+        Holidays { weekend, ..self }
+    }
+
+    /// Adds a rule.
+    pub fn with(mut self, holiday: Holiday) -> Self {
+        self.rules.push(holiday);
+        self
+    }
+
+    /// Adds a holiday on the same `month` and `day` every year.
+    pub fn fixed(self, month: u64, day: u64) -> Self {
+        self.with(Holiday::Fixed { month, day })
+    }
+
+    /// Adds a holiday on the `nth` `weekday` of `month`, counting from the end of the month
+    /// when `nth` is negative.
+    pub fn floating(self, month: u64, weekday: Weekday, nth: i64) -> Self {
+        self.with(Holiday::Floating {
+            month,
+            weekday,
+            nth,
+        })
+    }
+
+    /// Adds a holiday on a single date.
+    pub fn date(self, year: u64, month: u64, day: u64) -> Self {
+        self.with(Holiday::Date { year, month, day })
+    }
+
+    /// Returns the rules of the calendar.
+    pub fn rules(&self) -> &[Holiday] {
+        &self.rules
+    }
+}
+
+impl HolidayCalendar for Holidays {
+    fn is_holiday(&self, date: (u64, u64, u64)) -> bool {
+        self.rules.iter().any(|rule| rule.matches(date))
+    }
+
+    fn weekend(&self) -> Weekend {
+        self.weekend
+    }
+}
@@ -0,0 +1,355 @@
+use super::clock::{since_epoch, Clock, SystemClock};
+use super::error::WTimeError;
+#[cfg(feature = "ulid")]
+use super::utc::utc_ts_millis;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of low bits of an ID holding the sequence number.
+const SEQUENCE_BITS: u32 = 20;
+
+/// ### IdGenerator
+///
+/// A thread-safe source of unique, strictly increasing 64-bit IDs.
+///
+/// Formatted timestamps make poor IDs: two calls within the same microsecond return the
+/// same string. Each ID of an `IdGenerator` instead holds the UTC time in milliseconds in
+/// its upper 44 bits and a sequence number in its lower 20 bits. The sequence counts the
+/// IDs issued within one millisecond; an ID is always greater than the previous one, even if
+/// more than a million are issued in the same millisecond or the system clock goes back, in
+/// which case the generator runs ahead of the clock until it catches up.
+///
+/// IDs are unique per generator. Use [`next_id`] for one shared by the whole process.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::id::{id_millis, id_sequence, IdGenerator};
+///
+/// let ids = IdGenerator::new();
+/// let clock = FixedClock::from_ts(1728933069);
+///
+/// let first = ids.next_id_with_clock(&clock);
+/// let second = ids.next_id_with_clock(&clock);
+/// assert!(second > first);
+/// assert_eq!(id_millis(second), 1728933069000);
+/// assert_eq!((id_sequence(first), id_sequence(second)), (0, 1));
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Default)]
+pub struct IdGenerator {
+    last: AtomicU64,
+}
+
+impl IdGenerator {
+    /// Creates a generator, usable in a `static`.
+    pub const fn new() -> Self {
+        IdGenerator {
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the next ID, reading the system clock.
+    pub fn next_id(&self) -> u64 {
+        self.next_id_with_clock(&SystemClock)
+    }
+
+    /// Returns the next ID, reading the time from `clock`.
+    pub fn next_id_with_clock<C: Clock>(&self, clock: &C) -> u64 {
+        let floor = (since_epoch(clock).as_millis() as u64) << SEQUENCE_BITS;
+        let mut last = self.last.load(Ordering::Relaxed);
+        loop {
+            let id = floor.max(last + 1);
+            match self
+                .last
+                .compare_exchange_weak(last, id, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return id,
+                Err(current) => last = current,
+            }
+        }
+    }
+}
+
+static GENERATOR: IdGenerator = IdGenerator::new();
+
+/// ### next_id() -> u64
+///
+/// Generates a unique ID from a generator shared by the whole process.
+///
+/// The IDs sort in creation order across all threads, see [`IdGenerator`] for the layout.
+///
+/// ### Example
+///
+/// ```
+/// use std::thread;
+/// use wtime::id::next_id;
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| thread::spawn(|| (0..1000).map(|_| next_id()).collect::<Vec<_>>()))
+///     .collect();
+///
+/// let mut ids: Vec<u64> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+/// ids.sort_unstable();
+/// ids.dedup();
+/// assert_eq!(ids.len(), 4000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `u64` greater than every ID previously returned by `next_id`.
+///
+/// <small>End Fun Doc</small>
+pub fn next_id() -> u64 {
+    GENERATOR.next_id()
+}
+
+/// ### id_millis(id: u64) -> u64
+///
+/// Extracts the UTC time in milliseconds since the UNIX epoch from an ID.
+///
+/// For an ID issued faster than one million per millisecond, or while the system clock
+/// went back, this is slightly later than the actual time of issue.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::{id_millis, next_id};
+/// use wtime::utc::utc_ts_millis;
+///
+/// let before = utc_ts_millis();
+/// let id = next_id();
+/// assert!(id_millis(id) >= before as u64);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds since the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn id_millis(id: u64) -> u64 {
+    id >> SEQUENCE_BITS
+}
+
+/// ### id_sequence(id: u64) -> u64
+///
+/// Extracts the sequence number of an ID within its millisecond.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::id_sequence;
+///
+/// assert_eq!(id_sequence((1728933069000 << 20) + 7), 7);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a number below 2<sup>20</sup>.
+///
+/// <small>End Fun Doc</small>
+pub fn id_sequence(id: u64) -> u64 {
+    id & ((1 << SEQUENCE_BITS) - 1)
+}
+
+/// The Crockford base32 alphabet, without `I`, `L`, `O` and `U`.
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// ### ulid_from_parts(millis: u64, random: u128) -> String
+///
+/// Encodes a ULID from its timestamp and random parts.
+///
+/// A ULID is 128 bits written as 26 Crockford base32 characters: 48 bits of UTC milliseconds
+/// since the UNIX epoch followed by 80 random bits. ULIDs sort in time order as plain
+/// strings. Only the low 48 bits of `millis` and the low 80 bits of `random` are used. With
+/// the `ulid` feature, [`ulid`] fills in the current time and random bits from the operating
+/// system; this function suits callers with their own random source.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::ulid_from_parts;
+///
+/// assert_eq!(ulid_from_parts(1469922850259, 0), "01ARZ3NDEK0000000000000000");
+/// assert_eq!(ulid_from_parts(1728933069123, 0), "01JA6720A30000000000000000");
+/// assert_eq!(ulid_from_parts(0, u128::MAX), "0000000000ZZZZZZZZZZZZZZZZ");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of 26 uppercase characters.
+///
+/// <small>End Fun Doc</small>
+pub fn ulid_from_parts(millis: u64, random: u128) -> String {
+    let value = (u128::from(millis) & ((1 << 48) - 1)) << 80 | random & ((1 << 80) - 1);
+    (0..26)
+        .map(|i| CROCKFORD[(value >> (5 * (25 - i)) & 31) as usize] as char)
+        .collect()
+}
+
+/// ### ulid() -> String
+///
+/// Generates a ULID from the current UTC time and the operating system's random number
+/// generator. This function requires the `ulid` feature.
+///
+/// ULIDs are sortable like the formatted timestamps of `format_utc_ts`, at 26 characters
+/// instead of 30, and unique without coordination thanks to their 80 random bits. ULIDs
+/// generated in the same millisecond are not ordered among themselves; use
+/// [`IdGenerator`] where strict ordering matters. See [`ulid_from_parts`] for the layout.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::ulid;
+///
+/// let first = ulid();
+/// std::thread::sleep(std::time::Duration::from_millis(2));
+/// let second = ulid();
+///
+/// assert_eq!(first.len(), 26);
+/// assert_ne!(first, second);
+/// assert!(second > first);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of 26 uppercase characters.
+///
+/// ### Panics
+///
+/// Panics if the operating system's random number generator is unavailable.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "ulid")]
+pub fn ulid() -> String {
+    let mut random = [0u8; 16];
+    getrandom::fill(&mut random).expect("the operating system RNG is unavailable");
+    ulid_from_parts(utc_ts_millis() as u64, u128::from_be_bytes(random))
+}
+
+/// ### Base
+///
+/// The alphabet of [`encode_ts`] and [`decode_ts`].
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Base {
+    /// Digits and lowercase letters. Decoding ignores case, so it survives case-insensitive
+    /// file systems and URLs typed by hand.
+    Base36,
+    /// Digits, uppercase and lowercase letters, in that order.
+    Base62,
+}
+
+impl Base {
+    /// The digits of base 62, in ASCII order so encodings of equal length sort by value.
+    const BASE62: &'static [u8; 62] =
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    /// The digits of base 36.
+    const BASE36: &'static [u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    fn digits(self) -> &'static [u8] {
+        match self {
+            Base::Base36 => Base::BASE36,
+            Base::Base62 => Base::BASE62,
+        }
+    }
+
+    fn value_of(self, c: u8) -> Option<u64> {
+        let c = match self {
+            Base::Base36 => c.to_ascii_lowercase(),
+            Base::Base62 => c,
+        };
+        self.digits()
+            .iter()
+            .position(|&digit| digit == c)
+            .map(|value| value as u64)
+    }
+}
+
+/// ### encode_ts(ts: u64, base: Base) -> String
+///
+/// Encodes a timestamp compactly in base 36 or base 62, for URLs and file names.
+///
+/// A current timestamp in seconds takes 6 characters in either base, and one in
+/// milliseconds 8 or 7, against 30 for `format_utc_ts`. Encodings of the same length sort
+/// in time order as plain strings. [`decode_ts`] recovers the timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::{encode_ts, Base};
+///
+/// assert_eq!(encode_ts(1728933069, Base::Base36), "sld1al");
+/// assert_eq!(encode_ts(1728933069, Base::Base62), "1t0QTF");
+/// assert_eq!(encode_ts(1728933069123, Base::Base62), "URCsxfv");
+/// assert_eq!(encode_ts(0, Base::Base62), "0");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of digits and letters, `"0"` for zero.
+///
+/// <small>End Fun Doc</small>
+pub fn encode_ts(ts: u64, base: Base) -> String {
+    let digits = base.digits();
+    let radix = digits.len() as u64;
+    let mut encoded = Vec::new();
+    let mut rest = ts;
+    loop {
+        encoded.push(digits[(rest % radix) as usize]);
+        rest /= radix;
+        if rest == 0 {
+            break;
+        }
+    }
+    encoded.iter().rev().map(|&digit| digit as char).collect()
+}
+
+/// ### decode_ts(s: &str, base: Base) -> Result<u64, WTimeError>
+///
+/// Decodes a timestamp written by [`encode_ts`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::id::{decode_ts, encode_ts, Base};
+///
+/// assert_eq!(decode_ts("1t0QTF", Base::Base62), Ok(1728933069));
+/// assert_eq!(decode_ts("SLD1AL", Base::Base36), Ok(1728933069));
+///
+/// let ts = 1728933069123;
+/// assert_eq!(decode_ts(&encode_ts(ts, Base::Base36), Base::Base36), Ok(ts));
+///
+/// assert!(decode_ts("", Base::Base62).is_err());
+/// assert!(decode_ts("1t0-QTF", Base::Base62).is_err());
+/// assert!(decode_ts("zzzzzzzzzzzzzzz", Base::Base36).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the timestamp in the unit it was encoded in.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if `s` is empty or contains a character outside the
+/// alphabet of `base`, and [`WTimeError::OutOfRange`] if the value does not fit in a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn decode_ts(s: &str, base: Base) -> Result<u64, WTimeError> {
+    const EXPECTED: &str = "a timestamp in base 36 or base 62 such as `1t0QTF`";
+
+    if s.is_empty() {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let radix = base.digits().len() as u64;
+    s.bytes().try_fold(0u64, |ts, c| {
+        let value = base
+            .value_of(c)
+            .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+        ts.checked_mul(radix)
+            .and_then(|ts| ts.checked_add(value))
+            .ok_or(WTimeError::OutOfRange("timestamp"))
+    })
+}
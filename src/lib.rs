@@ -2,8 +2,74 @@
     html_logo_url = "https://github.com/dr-montasir/wtime/raw/HEAD/logo.svg?sanitize=true",
     html_root_url = "https://docs.rs/wtime/latest/wtime"
 )]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "net")]
+pub mod bson;
 pub mod calc;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod convert;
+pub mod datetime;
+#[cfg(feature = "calendars")]
+pub mod epoch;
+pub mod error;
+#[cfg(feature = "format")]
+pub mod format;
+#[cfg(feature = "std")]
+pub mod fs;
+#[cfg(feature = "calendars")]
+pub mod holiday;
+#[cfg(feature = "id")]
+pub mod id;
+#[cfg(feature = "tz")]
 pub mod local;
+#[cfg(feature = "net")]
+pub mod logical;
+#[cfg(feature = "std")]
+pub mod monotonic;
+#[cfg(feature = "net")]
+pub mod msgpack;
+#[cfg(feature = "net")]
+pub mod otel;
+#[cfg(feature = "format")]
+pub mod parse;
+#[cfg(feature = "prost")]
+pub mod proto;
+#[cfg(feature = "calendars")]
+pub mod range;
+#[cfg(feature = "std")]
+pub mod rate;
+#[cfg(feature = "std")]
+pub mod retry;
+pub mod schedule;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "std")]
+pub mod sleep;
+pub mod source;
+#[cfg(feature = "std")]
+pub mod stopwatch;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod throttle;
+#[cfg(feature = "std")]
+pub mod tick;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "async-tokio")]
+pub mod tokio;
+#[cfg(feature = "std")]
+pub mod ttl;
+#[cfg(feature = "tz")]
 pub mod tz;
+#[cfg(feature = "std")]
 pub mod utc;
@@ -1,10 +1,36 @@
 use super::{
-    calc::{calc_date, duration_since, get_millis, get_minute, get_nanos, get_second},
-    tz::tz_number,
-    utc::{utc_ts_millis, utc_ts_nanos, utc_ts_sec},
+    calc::{
+        calc_date, duration_since, unit_bounds, write_rfc3339, write_wtime_ts, ArrayWriter,
+        Snapshot, Unit, Weekday, MAX_TS,
+    },
+    clock::{since_epoch, Clock},
+    config::WeekStart,
+    tz::{offset_at, tz_offset_minutes},
+    utc::{
+        utc_ts_millis, utc_ts_nanos, utc_ts_sec, write_wtime_ts_opts, FormatOptions, FORMAT_TS_LEN,
+    },
 };
+use std::fmt::{self, Write};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Returns the local timezone offset in seconds, negative west of Greenwich.
+fn offset_secs() -> i64 {
+    tz_offset_minutes() * 60
+}
+
+/// Shifts a UTC timestamp counted in `1 / units_per_sec` seconds by an offset in seconds,
+/// using signed arithmetic so negative offsets work, and clamps the result at the Unix epoch.
+fn shift(utc: i128, units_per_sec: i128, offset_secs: i64) -> i128 {
+    (utc + offset_secs as i128 * units_per_sec).max(0)
+}
+
+/// Reads `clock` once, returning its time since the Unix epoch and the local offset in
+/// seconds in force at that instant.
+fn read_clock<C: Clock>(clock: &C) -> (Duration, i64) {
+    let since = since_epoch(clock);
+    (since, offset_at(since.as_secs()) * 60)
+}
+
 /// ### local_now()
 ///
 /// Retrieves the current local time based on a variable timezone offset.
@@ -33,24 +59,25 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 ///
 /// ### Note
 ///
-/// The timezone offset is obtained dynamically via the `tz_number()` function,
-/// allowing for more flexibility than a hard-coded offset. Ensure that `tz_number()`
-/// accurately reflects the intended timezone offset in hours. Calculating the offset
-/// in seconds allows for precise adjustment of the UTC time to the local time.
+/// The timezone offset is obtained dynamically via the `tz_offset_minutes()` function,
+/// allowing for more flexibility than a hard-coded offset. The offset is kept in minutes,
+/// so zones such as UTC+05:30 and UTC+05:45 are adjusted precisely.
 ///
 /// <small>End Fun Doc</small>
 pub fn local_now() -> SystemTime {
-    // Timezone offset
-    let timezone_offset_hours: i64 = tz_number();
-
-    // Calculate the offset in seconds
-    let offset_in_seconds = timezone_offset_hours * 3600;
+    // Timezone offset in seconds, negative west of Greenwich
+    let offset_in_seconds = offset_secs();
 
     // Get the duration since the Unix epoch for the UTC time
     let duration_since_epoch = duration_since();
 
-    // Calculate the new duration for the local time
-    let local_duration = duration_since_epoch + Duration::from_secs(offset_in_seconds as u64);
+    // Shift by the offset in either direction, clamping at the Unix epoch
+    let offset = Duration::from_secs(offset_in_seconds.unsigned_abs());
+    let local_duration = if offset_in_seconds >= 0 {
+        duration_since_epoch + offset
+    } else {
+        duration_since_epoch.saturating_sub(offset)
+    };
 
     // Convert back to SystemTime
     UNIX_EPOCH + local_duration
@@ -61,14 +88,23 @@ pub fn local_now() -> SystemTime {
 /// Retrieves the current local time as a UNIX timestamp in seconds.
 ///
 /// This function calculates the local time in seconds since the UNIX epoch by
-/// adding the local timezone offset (in hours) to the current UTC timestamp.
+/// adding the local timezone offset (in minutes) to the current UTC timestamp.
 /// This is useful for obtaining a UNIX timestamp that reflects the local time
-/// settings.
+/// settings. West of Greenwich the offset is negative and the local timestamp is
+/// smaller than the UTC one.
 ///
 /// ### Example
 ///
 /// ```
 /// use wtime::local::local_ts_sec;
+/// use wtime::utc::utc_ts_sec;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     let behind = utc_ts_sec() - local_ts_sec();
+///     assert!((5 * 3600..=5 * 3600 + 1).contains(&behind));
+/// }
 ///
 /// let local_timestamp = local_ts_sec();
 /// println!("Current Local Timestamp in Seconds: {}", local_timestamp);
@@ -81,7 +117,39 @@ pub fn local_now() -> SystemTime {
 ///
 /// <small>End Fun Doc</small>
 pub fn local_ts_sec() -> u64 {
-    utc_ts_sec() + (tz_number() * 60 * 60) as u64
+    shift(utc_ts_sec() as i128, 1, offset_secs()) as u64
+}
+
+/// ### local_ts_sec_with_clock<C: Clock>(clock: &C) -> u64
+///
+/// Reads a [`Clock`] as a local UNIX timestamp in seconds.
+///
+/// This is [`local_ts_sec`] with an injectable clock. The local offset is the one in force
+/// at the clock's time, so a clock frozen in winter gets the winter offset.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::local::local_ts_sec_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+///
+/// // India, UTC+05:30 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "IST-5:30");
+///     assert_eq!(local_ts_sec_with_clock(&clock), 1728933069 + 19_800);
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the local seconds since the UNIX epoch, clamped at 0.
+///
+/// <small>End Fun Doc</small>
+pub fn local_ts_sec_with_clock<C: Clock>(clock: &C) -> u64 {
+    let (since, offset_secs) = read_clock(clock);
+    shift(since.as_secs() as i128, 1, offset_secs) as u64
 }
 
 /// ### local_ts_millis()
@@ -89,7 +157,7 @@ pub fn local_ts_sec() -> u64 {
 /// Retrieves the current local time as a UNIX timestamp in milliseconds.
 ///
 /// This function calculates the local time in milliseconds since the UNIX epoch
-/// by adding the local timezone offset (in hours) to the current UTC timestamp.
+/// by adding the local timezone offset (in minutes) to the current UTC timestamp.
 /// This is useful for obtaining a timestamp that is precise to the millisecond for
 /// applications that require high-resolution timing.
 ///
@@ -109,7 +177,36 @@ pub fn local_ts_sec() -> u64 {
 ///
 /// <small>End Fun Doc</small>
 pub fn local_ts_millis() -> u128 {
-    utc_ts_millis() + (tz_number() * 60 * 60 * 1_000) as u128
+    shift(utc_ts_millis() as i128, 1_000, offset_secs()) as u128
+}
+
+/// ### local_ts_millis_with_clock<C: Clock>(clock: &C) -> u128
+///
+/// Reads a [`Clock`] as a local UNIX timestamp in milliseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::local::local_ts_millis_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     assert_eq!(local_ts_millis_with_clock(&clock), (1728933069 - 18_000) * 1000);
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the local milliseconds since the UNIX epoch, clamped at 0.
+///
+/// <small>End Fun Doc</small>
+pub fn local_ts_millis_with_clock<C: Clock>(clock: &C) -> u128 {
+    let (since, offset_secs) = read_clock(clock);
+    shift(since.as_millis() as i128, 1_000, offset_secs) as u128
 }
 
 /// ### local_ts_nanos()
@@ -117,7 +214,7 @@ pub fn local_ts_millis() -> u128 {
 /// Retrieves the current local time as a UNIX timestamp in nanoseconds.
 ///
 /// This function calculates the local time in nanoseconds since the UNIX epoch
-/// by adding the local timezone offset (in hours) to the current UTC timestamp.
+/// by adding the local timezone offset (in minutes) to the current UTC timestamp.
 /// This is useful for applications that require extremely high-resolution timestamps.
 ///
 /// ### Example
@@ -136,7 +233,36 @@ pub fn local_ts_millis() -> u128 {
 ///
 /// <small>End Fun Doc</small>
 pub fn local_ts_nanos() -> u128 {
-    utc_ts_nanos() + (tz_number() * 60 * 60 * 1_000_000) as u128
+    shift(utc_ts_nanos() as i128, 1_000_000_000, offset_secs()) as u128
+}
+
+/// ### local_ts_nanos_with_clock<C: Clock>(clock: &C) -> u128
+///
+/// Reads a [`Clock`] as a local UNIX timestamp in nanoseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::local::local_ts_nanos_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+///
+/// // UTC (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "UTC0");
+///     assert_eq!(local_ts_nanos_with_clock(&clock), 1728933069_000_000_000);
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the local nanoseconds since the UNIX epoch, clamped at 0.
+///
+/// <small>End Fun Doc</small>
+pub fn local_ts_nanos_with_clock<C: Clock>(clock: &C) -> u128 {
+    let (since, offset_secs) = read_clock(clock);
+    shift(since.as_nanos() as i128, 1_000_000_000, offset_secs) as u128
 }
 
 /// ### get_local_year() -> u64
@@ -240,6 +366,32 @@ pub fn get_local_hour() -> u64 {
     hour
 }
 
+/// ### is_today_local(ts: u64) -> bool
+///
+/// Checks whether a UNIX timestamp in seconds falls on the current local day.
+///
+/// Each instant is placed on the local calendar with the offset in force at that instant,
+/// so daylight saving time is followed.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::is_today_local;
+/// use wtime::utc::utc_ts_sec;
+///
+/// assert!(is_today_local(utc_ts_sec()));
+/// assert!(!is_today_local(utc_ts_sec() + 2 * 86_400));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if `ts` shares today's local date.
+///
+/// <small>End Fun Doc</small>
+pub fn is_today_local(ts: u64) -> bool {
+    to_wall(ts).div_euclid(86_400) == to_wall(utc_ts_sec()).div_euclid(86_400)
+}
+
 /// ### format_local_ts()
 ///
 /// Retrieves the current local timestamp formatted as a string.
@@ -254,7 +406,8 @@ pub fn get_local_hour() -> u64 {
 /// - Minute: 2 digits, zero-padded (00-59)
 /// - Second: 2 digits, zero-padded (00-59)
 /// - Milliseconds: 3 digits, zero-padded (000-999)
-/// - Nanoseconds: 6 digits, zero-padded (000000-999999)
+/// - Nanoseconds: 6 digits, zero-padded (000000-999999), the nanoseconds within the
+///   millisecond, so that the last two fields together are the 9-digit fraction of the second
 ///
 /// This function does not perform error handling or validation beyond the necessary
 /// calculations for the timestamp components. It is assumed that the underlying
@@ -327,18 +480,506 @@ pub fn get_local_hour() -> u64 {
 ///
 /// <small>End Fun Doc</small>
 pub fn format_local_ts() -> String {
-    let year = get_local_year();
-    let month = get_local_month();
-    let day = get_local_day();
-    let hour = get_local_hour();
-    let minute = get_minute();
-    let second = get_second();
-    let millis = get_millis();
-    let nanos = get_nanos();
-
-    // Create the formatted string with updated formatting
-    format!(
-        "{:04}-{:02}-{:02}-{:02}-{:02}-{:02}-{:03}-{:06}",
-        year, month, day, hour, minute, second, millis, nanos,
+    LocalSnapshot::now().to_string()
+}
+
+/// ### format_local_ts_with_clock<C: Clock>(clock: &C) -> String
+///
+/// Formats the time of a [`Clock`] in the layout of [`format_local_ts`].
+///
+/// ### Example
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use wtime::clock::FixedClock;
+/// use wtime::local::format_local_ts_with_clock;
+///
+/// let clock = FixedClock::new(UNIX_EPOCH + Duration::new(1728933069, 123_456_789));
+///
+/// // India, UTC+05:30 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "IST-5:30");
+///     assert_eq!(format_local_ts_with_clock(&clock), "2024-10-15-00-41-09-123-456789");
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of [`FORMAT_TS_LEN`] characters for years up to 9999.
+///
+/// <small>End Fun Doc</small>
+pub fn format_local_ts_with_clock<C: Clock>(clock: &C) -> String {
+    let (since, offset_secs) = read_clock(clock);
+    let mut timestamp = String::with_capacity(FORMAT_TS_LEN);
+    // Writing into a `String` never fails
+    let _ = write_wtime_ts(&mut timestamp, since, offset_secs);
+    timestamp
+}
+
+/// ### LocalSnapshot
+///
+/// The current local time with all of its components read from the clock at once.
+///
+/// This is the local-time counterpart of [`UtcSnapshot`](crate::utc::UtcSnapshot): the
+/// clock is read once, and the local offset in force at that instant is looked up once, so
+/// the date, time, weekday and week always describe the same moment. The accessors of
+/// [`Snapshot`] are available through `Deref`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::Weekday;
+/// use wtime::clock::FixedClock;
+/// use wtime::local::LocalSnapshot;
+///
+/// let now = LocalSnapshot::now();
+/// println!("Local time: {:02}:{:02}:{:02}", now.hour(), now.minute(), now.second());
+///
+/// // India, UTC+05:30 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "IST-5:30");
+///     let snapshot = LocalSnapshot::with_clock(&FixedClock::from_ts(1728933069));
+///     assert_eq!(snapshot.date(), (2024, 10, 15));
+///     assert_eq!(snapshot.time(), (0, 41, 9));
+///     assert_eq!(snapshot.weekday(), Weekday::Tuesday);
+///     assert_eq!(snapshot.offset_minutes(), 330);
+/// }
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LocalSnapshot(Snapshot);
+
+impl LocalSnapshot {
+    /// Reads the system clock once.
+    pub fn now() -> Self {
+        LocalSnapshot::at(duration_since())
+    }
+
+    /// Reads `clock` once.
+    pub fn with_clock<C: Clock>(clock: &C) -> Self {
+        LocalSnapshot::at(since_epoch(clock))
+    }
+
+    /// Captures `since` with the local offset in force at that instant.
+    fn at(since: Duration) -> Self {
+        LocalSnapshot(Snapshot::from_duration(since, offset_at(since.as_secs())))
+    }
+}
+
+impl core::ops::Deref for LocalSnapshot {
+    type Target = Snapshot;
+
+    fn deref(&self) -> &Snapshot {
+        &self.0
+    }
+}
+
+impl fmt::Display for LocalSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// ### format_local_ts_opts(options: FormatOptions) -> String
+///
+/// Retrieves the current local timestamp with a chosen precision and separators.
+///
+/// This is the local-time counterpart of [`format_utc_ts_opts`]; see [`FormatOptions`] for
+/// the layout. With `offset_suffix`, the local UTC offset is appended.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::local::format_local_ts_opts;
+/// use wtime::utc::{FormatOptions, Precision};
+///
+/// let timestamp = format_local_ts_opts(FormatOptions {
+///     precision: Precision::Micros,
+///     separator: '-',
+///     date_time_separator: ' ',
+///     offset_suffix: false,
+/// });
+/// assert_eq!(timestamp.len(), 27);
+/// assert_eq!(&timestamp[10..11], " ");
+///
+/// // India, UTC+05:30 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "IST-5:30");
+///     let options = FormatOptions {
+///         precision: Precision::Seconds,
+///         offset_suffix: true,
+///         ..FormatOptions::DEFAULT
+///     };
+///     assert!(format_local_ts_opts(options).ends_with("+05:30"));
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of 19, 23, 27 or [`FORMAT_TS_LEN`] characters for years up to 9999
+/// and single-byte separators, plus the length of the offset suffix.
+///
+/// <small>End Fun Doc</small>
+pub fn format_local_ts_opts(options: FormatOptions) -> String {
+    let mut timestamp = String::with_capacity(FORMAT_TS_LEN);
+    // Writing into a `String` never fails
+    let _ = write_wtime_ts_opts(&mut timestamp, duration_since(), offset_secs(), options);
+    timestamp
+}
+
+/// ### format_local_ts_into<W: Write>(out: &mut W) -> fmt::Result
+///
+/// Writes the current local timestamp into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`format_local_ts`] for hot logging paths. It produces
+/// the same `year-month-day-hour-minute-second-millis-nanos` layout, but reads the clock
+/// once, so all components always belong to the same instant.
+///
+/// ### Example
+///
+/// ```rust
+/// use std::fmt::Write;
+/// use wtime::local::format_local_ts_into;
+///
+/// let mut line = String::with_capacity(64);
+/// format_local_ts_into(&mut line).unwrap();
+/// write!(line, " user {} logged in", 42).unwrap();
+/// println!("{}", line);
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn format_local_ts_into<W: Write>(out: &mut W) -> fmt::Result {
+    write_wtime_ts(out, duration_since(), offset_secs())
+}
+
+/// ### format_local_ts_bytes() -> [u8; FORMAT_TS_LEN]
+///
+/// Retrieves the current local timestamp as a fixed-size byte array.
+///
+/// The layout is the same as [`format_local_ts`], and since its length is fixed the result
+/// lives on the stack without any heap allocation. The bytes are always ASCII.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::local::format_local_ts_bytes;
+///
+/// let bytes = format_local_ts_bytes();
+/// let timestamp = std::str::from_utf8(&bytes).unwrap();
+/// println!("Formatted Local Timestamp: {}", timestamp);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `[u8; FORMAT_TS_LEN]` holding the ASCII timestamp.
+///
+/// <small>End Fun Doc</small>
+pub fn format_local_ts_bytes() -> [u8; FORMAT_TS_LEN] {
+    let mut buf = [b'0'; FORMAT_TS_LEN];
+    // Only a year past 9999 could overflow the buffer
+    let _ = write_wtime_ts(
+        &mut ArrayWriter::new(&mut buf),
+        duration_since(),
+        offset_secs(),
+    );
+    buf
+}
+
+/// ### local_rfc3339() -> String
+///
+/// Retrieves the current local time as an RFC 3339 / ISO 8601 timestamp with milliseconds.
+///
+/// The wall-clock time is shown in the machine's local UTC offset, which is appended as
+/// `+HH:MM` or `-HH:MM`, e.g. `2024-10-14T21:11:09.123+02:00`. Half-hour and 45-minute
+/// offsets are kept exactly. On a machine running in UTC the suffix is `Z`.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::local::local_rfc3339;
+///
+/// let timestamp = local_rfc3339();
+/// println!("RFC 3339 Local Timestamp: {}", timestamp);
+/// assert_eq!(&timestamp[10..11], "T");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T21:11:09.123+02:00`.
+///
+/// <small>End Fun Doc</small>
+pub fn local_rfc3339() -> String {
+    let now = duration_since();
+    let mut timestamp = String::with_capacity(29);
+    // Writing into a `String` never fails
+    let _ = write_rfc3339(
+        &mut timestamp,
+        now.as_secs(),
+        tz_offset_minutes(),
+        now.subsec_nanos() as u64,
+        3,
+    );
+    timestamp
+}
+
+/// ### local_rfc3339_with_clock<C: Clock>(clock: &C) -> String
+///
+/// Formats the time of a [`Clock`] as a local RFC 3339 timestamp with milliseconds, like
+/// [`local_rfc3339`].
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::clock::FixedClock;
+/// use wtime::local::local_rfc3339_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     assert_eq!(local_rfc3339_with_clock(&clock), "2024-10-14T14:11:09.000-05:00");
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T21:11:09.123+02:00`.
+///
+/// <small>End Fun Doc</small>
+pub fn local_rfc3339_with_clock<C: Clock>(clock: &C) -> String {
+    let (since, offset_secs) = read_clock(clock);
+    let mut timestamp = String::with_capacity(29);
+    // Writing into a `String` never fails
+    let _ = write_rfc3339(
+        &mut timestamp,
+        since.as_secs(),
+        offset_secs / 60,
+        since.subsec_nanos() as u64,
+        3,
+    );
+    timestamp
+}
+
+/// ### local_rfc3339_into<W: Write>(out: &mut W) -> fmt::Result
+///
+/// Writes the current local time as an RFC 3339 timestamp with milliseconds into a
+/// caller-supplied buffer.
+///
+/// This is the allocation-free form of [`local_rfc3339`].
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::local::local_rfc3339_into;
+///
+/// let mut line = String::with_capacity(64);
+/// local_rfc3339_into(&mut line).unwrap();
+/// line.push_str(" INFO service started");
+///
+/// assert_eq!(&line[10..11], "T");
+/// assert!(line.ends_with(" INFO service started"));
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn local_rfc3339_into<W: Write>(out: &mut W) -> fmt::Result {
+    let now = duration_since();
+    write_rfc3339(
+        out,
+        now.as_secs(),
+        tz_offset_minutes(),
+        now.subsec_nanos() as u64,
+        3,
     )
 }
+
+/// Converts local wall-clock seconds back to a UNIX timestamp.
+///
+/// When the wall-clock time is skipped by a daylight-saving transition, the first instant
+/// after the gap is returned; when it occurs twice, the earlier one.
+fn from_wall(wall: i64) -> u64 {
+    let offset_at_secs = |ts: i64| offset_at(ts.max(0) as u64) * 60;
+    let first = wall - offset_at_secs(wall);
+    let second = wall - offset_at_secs(first);
+    let (earlier, later) = (first.min(second), first.max(second));
+    let utc = if earlier + offset_at_secs(earlier) >= wall {
+        earlier
+    } else {
+        later
+    };
+    utc.max(0) as u64
+}
+
+/// Returns the local wall-clock time of a UNIX timestamp in seconds since 1970-01-01 00:00.
+///
+/// Timestamps after [`MAX_TS`] are treated as `MAX_TS`, so that adding the offset and the
+/// calendar math on the result cannot overflow.
+fn to_wall(ts: u64) -> i64 {
+    let ts = ts.min(MAX_TS);
+    ts as i64 + offset_at(ts) * 60
+}
+
+/// Returns the first instant of the local `unit` containing `ts`, with weeks starting on
+/// `first_day`.
+fn local_unit_start(ts: u64, unit: Unit, first_day: Weekday) -> u64 {
+    from_wall(unit_bounds(to_wall(ts), unit, first_day).0)
+}
+
+/// Returns the first instant after `after` at which the local clock reads `secs_of_day`
+/// seconds past midnight, today or tomorrow.
+pub(crate) fn next_local_time(after: u64, secs_of_day: i64) -> u64 {
+    let (midnight, _) = unit_bounds(to_wall(after), Unit::Day, Weekday::Monday);
+    let today = from_wall(midnight + secs_of_day);
+    if today > after {
+        today
+    } else {
+        from_wall(midnight + 86_400 + secs_of_day)
+    }
+}
+
+/// ### start_of_local_day(ts: u64) -> u64
+///
+/// Calculates the local midnight that starts the day containing a UNIX timestamp.
+///
+/// Daylight saving time is followed: the offset of the start of the day may differ from
+/// that of `ts`. Where the clocks skip midnight, the day starts at the first instant after
+/// the gap.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::start_of_local_day;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     // 2024-10-15 02:00 UTC is still October 14 in New York
+///     assert_eq!(start_of_local_day(1728957600), 1728882000); // 2024-10-14 05:00 UTC
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of local midnight.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_local_day(ts: u64) -> u64 {
+    local_unit_start(ts, Unit::Day, Weekday::Monday)
+}
+
+/// ### end_of_local_day(ts: u64) -> u64
+///
+/// Calculates the last second of the local day containing a UNIX timestamp.
+///
+/// On the days daylight saving time starts or ends, the local day is 23 or 25 hours long.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::end_of_local_day;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     assert_eq!(end_of_local_day(1728933069), 1728968399); // 2024-10-15 04:59:59 UTC
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of 23:59:59 local time.
+///
+/// <small>End Fun Doc</small>
+pub fn end_of_local_day(ts: u64) -> u64 {
+    let (_, next_day) = unit_bounds(to_wall(ts), Unit::Day, Weekday::Monday);
+    from_wall(next_day).saturating_sub(1)
+}
+
+/// ### start_of_local_week(ts: u64, week_start: WeekStart) -> u64
+///
+/// Calculates the local midnight that starts the week containing a UNIX timestamp, for
+/// weeks starting on `week_start`. Pass `WeekStart::default()` to follow the configured
+/// [`Config::week_start`](crate::config::Config::week_start).
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::WeekStart;
+/// use wtime::local::start_of_local_week;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     let ts = 1728933069; // Monday 2024-10-14 14:11:09 in New York
+///     assert_eq!(start_of_local_week(ts, WeekStart::Monday), 1728882000);
+///     assert_eq!(start_of_local_week(ts, WeekStart::Sunday), 1728795600);
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of local midnight on the first day of the week.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_local_week(ts: u64, week_start: WeekStart) -> u64 {
+    local_unit_start(ts, Unit::Week, week_start.into())
+}
+
+/// ### start_of_local_month(ts: u64) -> u64
+///
+/// Calculates the local midnight that starts the month containing a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::start_of_local_month;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     assert_eq!(start_of_local_month(1728933069), 1727758800); // 2024-10-01 05:00 UTC
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of local midnight on the first day of the month.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_local_month(ts: u64) -> u64 {
+    local_unit_start(ts, Unit::Month, Weekday::Monday)
+}
+
+/// ### start_of_local_year(ts: u64) -> u64
+///
+/// Calculates the local midnight that starts the year containing a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::start_of_local_year;
+///
+/// // New York in winter, UTC-05:00 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5");
+///     assert_eq!(start_of_local_year(1728933069), 1704085200); // 2024-01-01 05:00 UTC
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of local midnight on January 1.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_local_year(ts: u64) -> u64 {
+    local_unit_start(ts, Unit::Year, Weekday::Monday)
+}
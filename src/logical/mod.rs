@@ -0,0 +1,286 @@
+use super::error::WTimeError;
+
+/// Microseconds in a day, the exclusive upper bound of `time-micros`.
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+/// ### to_timestamp_millis(ts: u64, nanos: u64) -> i64
+///
+/// Encodes a UNIX timestamp as the `timestamp-millis` logical type of Avro and the
+/// `TIMESTAMP(MILLIS)` logical type of Parquet.
+///
+/// Both store milliseconds since the UNIX epoch in an `int64`. `nanos` is the sub-second
+/// part and is truncated to milliseconds. Values beyond the `int64` range saturate.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::to_timestamp_millis;
+///
+/// assert_eq!(to_timestamp_millis(1728933069, 123_456_789), 1728933069123);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds since the UNIX epoch as an `i64`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_timestamp_millis(ts: u64, nanos: u64) -> i64 {
+    let millis = ts as i128 * 1_000 + (nanos / 1_000_000) as i128;
+    i64::try_from(millis).unwrap_or(i64::MAX)
+}
+
+/// ### from_timestamp_millis(millis: i64) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes a `timestamp-millis` value into a UNIX timestamp and its sub-second part.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::from_timestamp_millis;
+///
+/// assert_eq!(from_timestamp_millis(1728933069123), Ok((1728933069, 123_000_000)));
+/// assert!(from_timestamp_millis(-1).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for values before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_timestamp_millis(millis: i64) -> Result<(u64, u64), WTimeError> {
+    let millis = u64::try_from(millis).map_err(|_| WTimeError::OutOfRange("timestamp"))?;
+    Ok((millis / 1_000, millis % 1_000 * 1_000_000))
+}
+
+/// ### to_timestamp_micros(ts: u64, nanos: u64) -> i64
+///
+/// Encodes a UNIX timestamp as the `timestamp-micros` logical type of Avro and the
+/// `TIMESTAMP(MICROS)` logical type of Parquet.
+///
+/// Both store microseconds since the UNIX epoch in an `int64`. `nanos` is the sub-second
+/// part and is truncated to microseconds. Values beyond the `int64` range saturate.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::to_timestamp_micros;
+///
+/// assert_eq!(to_timestamp_micros(1728933069, 123_456_789), 1728933069123456);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the microseconds since the UNIX epoch as an `i64`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_timestamp_micros(ts: u64, nanos: u64) -> i64 {
+    let micros = ts as i128 * 1_000_000 + (nanos / 1_000) as i128;
+    i64::try_from(micros).unwrap_or(i64::MAX)
+}
+
+/// ### from_timestamp_micros(micros: i64) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes a `timestamp-micros` value into a UNIX timestamp and its sub-second part.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::from_timestamp_micros;
+///
+/// assert_eq!(from_timestamp_micros(1728933069123456), Ok((1728933069, 123_456_000)));
+/// assert!(from_timestamp_micros(-1).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for values before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_timestamp_micros(micros: i64) -> Result<(u64, u64), WTimeError> {
+    let micros = u64::try_from(micros).map_err(|_| WTimeError::OutOfRange("timestamp"))?;
+    Ok((micros / 1_000_000, micros % 1_000_000 * 1_000))
+}
+
+/// ### to_date(ts: u64) -> i32
+///
+/// Encodes the UTC calendar date of a UNIX timestamp as the `date` logical type of Avro and
+/// Parquet.
+///
+/// Both store the number of days since 1970-01-01 in an `int32`. Dates beyond the `int32`
+/// range saturate.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::to_date;
+///
+/// assert_eq!(to_date(1728933069), 20010); // 2024-10-14
+/// assert_eq!(to_date(0), 0);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the days since the UNIX epoch as an `i32`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_date(ts: u64) -> i32 {
+    i32::try_from(ts / 86_400).unwrap_or(i32::MAX)
+}
+
+/// ### from_date(days: i32) -> Result<u64, WTimeError>
+///
+/// Decodes a `date` value into the UNIX timestamp of midnight UTC on that day.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::calc_date;
+/// use wtime::logical::from_date;
+///
+/// let midnight = from_date(20010).unwrap();
+/// assert_eq!(midnight, 1728864000);
+/// assert_eq!(calc_date(midnight), (2024, 10, 14));
+/// assert!(from_date(-1).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of the start of the day.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for dates before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_date(days: i32) -> Result<u64, WTimeError> {
+    let days = u64::try_from(days).map_err(|_| WTimeError::OutOfRange("date"))?;
+    Ok(days * 86_400)
+}
+
+/// ### to_time_millis(ts: u64, nanos: u64) -> i32
+///
+/// Encodes the UTC time of day of a UNIX timestamp as the `time-millis` logical type of
+/// Avro and the `TIME(MILLIS)` logical type of Parquet.
+///
+/// Both store milliseconds after midnight in an `int32`. `nanos` is the sub-second part and
+/// is truncated to milliseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::to_time_millis;
+///
+/// assert_eq!(to_time_millis(1728933069, 123_456_789), 69_069_123); // 19:11:09.123
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds after midnight as an `i32`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_time_millis(ts: u64, nanos: u64) -> i32 {
+    ((ts % 86_400) * 1_000 + nanos % 1_000_000_000 / 1_000_000) as i32
+}
+
+/// ### from_time_millis(millis: i32) -> Result<(u64, u64, u64, u64), WTimeError>
+///
+/// Decodes a `time-millis` value into `(hour, minute, second, millisecond)`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::from_time_millis;
+///
+/// assert_eq!(from_time_millis(69_069_123), Ok((19, 11, 9, 123)));
+/// assert!(from_time_millis(86_400_000).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the hour, minute, second and millisecond of the time of day.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for values outside a single day.
+///
+/// <small>End Fun Doc</small>
+pub fn from_time_millis(millis: i32) -> Result<(u64, u64, u64, u64), WTimeError> {
+    if !(0..86_400_000).contains(&millis) {
+        return Err(WTimeError::OutOfRange("time"));
+    }
+    let millis = millis as u64;
+    Ok((
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1_000 % 60,
+        millis % 1_000,
+    ))
+}
+
+/// ### to_time_micros(ts: u64, nanos: u64) -> i64
+///
+/// Encodes the UTC time of day of a UNIX timestamp as the `time-micros` logical type of
+/// Avro and the `TIME(MICROS)` logical type of Parquet.
+///
+/// Both store microseconds after midnight in an `int64`. `nanos` is the sub-second part and
+/// is truncated to microseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::to_time_micros;
+///
+/// assert_eq!(to_time_micros(1728933069, 123_456_789), 69_069_123_456);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the microseconds after midnight as an `i64`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_time_micros(ts: u64, nanos: u64) -> i64 {
+    ((ts % 86_400) * 1_000_000 + nanos % 1_000_000_000 / 1_000) as i64
+}
+
+/// ### from_time_micros(micros: i64) -> Result<(u64, u64, u64, u64), WTimeError>
+///
+/// Decodes a `time-micros` value into `(hour, minute, second, microsecond)`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::logical::from_time_micros;
+///
+/// assert_eq!(from_time_micros(69_069_123_456), Ok((19, 11, 9, 123_456)));
+/// assert!(from_time_micros(-1).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the hour, minute, second and microsecond of the time of day.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for values outside a single day.
+///
+/// <small>End Fun Doc</small>
+pub fn from_time_micros(micros: i64) -> Result<(u64, u64, u64, u64), WTimeError> {
+    if !(0..MICROS_PER_DAY).contains(&micros) {
+        return Err(WTimeError::OutOfRange("time"));
+    }
+    let micros = micros as u64;
+    Ok((
+        micros / 3_600_000_000,
+        micros / 60_000_000 % 60,
+        micros / 1_000_000 % 60,
+        micros % 1_000_000,
+    ))
+}
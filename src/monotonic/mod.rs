@@ -0,0 +1,188 @@
+use super::calc::duration_since;
+use std::time::{Duration, Instant};
+
+/// ### mono_now() -> Instant
+///
+/// Retrieves the current reading of the monotonic clock.
+///
+/// Unlike the wall clock behind [`utc_now`](crate::utc::utc_now), the monotonic clock never
+/// jumps when the system time is adjusted, for example by NTP. Its readings have no meaning
+/// on their own: use them only to measure intervals.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::monotonic::mono_now;
+///
+/// let start = mono_now();
+/// let end = mono_now();
+/// assert!(end >= start);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current `Instant`.
+///
+/// <small>End Fun Doc</small>
+pub fn mono_now() -> Instant {
+    Instant::now()
+}
+
+/// ### elapsed_since(start: Instant) -> Duration
+///
+/// Measures the time elapsed since a monotonic reading.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::{elapsed_since, mono_now};
+///
+/// let start = mono_now();
+/// std::thread::sleep(Duration::from_millis(5));
+/// assert!(elapsed_since(start) >= Duration::from_millis(5));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the elapsed time as a `Duration`.
+///
+/// <small>End Fun Doc</small>
+pub fn elapsed_since(start: Instant) -> Duration {
+    start.elapsed()
+}
+
+/// ### elapsed_millis(start: Instant) -> u64
+///
+/// Measures the time elapsed since a monotonic reading in whole milliseconds.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::{elapsed_millis, mono_now};
+///
+/// let start = mono_now();
+/// std::thread::sleep(Duration::from_millis(5));
+/// assert!(elapsed_millis(start) >= 5);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the elapsed milliseconds as a `u64`, saturating at `u64::MAX`.
+///
+/// <small>End Fun Doc</small>
+pub fn elapsed_millis(start: Instant) -> u64 {
+    u64::try_from(elapsed_since(start).as_millis()).unwrap_or(u64::MAX)
+}
+
+/// ### elapsed_nanos(start: Instant) -> u64
+///
+/// Measures the time elapsed since a monotonic reading in nanoseconds.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::{elapsed_nanos, mono_now};
+///
+/// let start = mono_now();
+/// std::thread::sleep(Duration::from_millis(5));
+/// assert!(elapsed_nanos(start) >= 5_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the elapsed nanoseconds as a `u64`, saturating at `u64::MAX` after about 584
+/// years.
+///
+/// <small>End Fun Doc</small>
+pub fn elapsed_nanos(start: Instant) -> u64 {
+    u64::try_from(elapsed_since(start).as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// ### Deadline
+///
+/// A point in the future after which an operation should give up, measured on the
+/// monotonic clock.
+///
+/// IO loops and retry loops check a `Deadline` between attempts. Because it is based on
+/// [`mono_now`], adjusting the system time, for example by NTP, neither cuts it short nor
+/// extends it. A deadline given as a UNIX timestamp with [`Deadline::at`] is converted to
+/// the monotonic clock once, when it is created. A deadline too far away for the platform's
+/// clock never expires.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::Deadline;
+/// use wtime::utc::utc_ts_sec;
+///
+/// let deadline = Deadline::after(Duration::from_millis(50));
+/// let mut attempts = 0;
+/// while let Some(left) = deadline.checked_remaining() {
+///     attempts += 1;
+///     // Try the operation here, waiting at most `left` for it
+///     std::thread::sleep(left.min(Duration::from_millis(20)));
+/// }
+/// assert!(deadline.expired());
+/// assert_eq!(deadline.remaining(), Duration::ZERO);
+/// assert!(attempts >= 3);
+///
+/// let forever = Deadline::after(Duration::MAX);
+/// assert!(!forever.expired());
+///
+/// let in_an_hour = Deadline::at(utc_ts_sec() + 3600);
+/// assert!(in_an_hour.remaining() > Duration::from_secs(3590));
+/// assert!(Deadline::at(1728933069).expired());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Deadline {
+    /// The monotonic instant of the deadline, or `None` if it is beyond the clock's range.
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// Creates a deadline `timeout` from now.
+    pub fn after(timeout: Duration) -> Self {
+        Deadline {
+            at: mono_now().checked_add(timeout),
+        }
+    }
+
+    /// Creates a deadline at a UNIX timestamp in seconds. A timestamp that has already
+    /// passed gives a deadline that has expired.
+    pub fn at(ts: u64) -> Self {
+        let timeout = Duration::from_secs(ts).saturating_sub(duration_since());
+        Deadline::after(timeout)
+    }
+
+    /// Returns `true` once the deadline has been reached.
+    pub fn expired(&self) -> bool {
+        self.checked_remaining().is_none()
+    }
+
+    /// Returns the time left before the deadline, or zero once it has been reached.
+    pub fn remaining(&self) -> Duration {
+        self.checked_remaining().unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns the time left before the deadline, or `None` once it has been reached.
+    pub fn checked_remaining(&self) -> Option<Duration> {
+        match self.at {
+            Some(at) => at
+                .checked_duration_since(mono_now())
+                .filter(|left| !left.is_zero()),
+            None => Some(Duration::MAX),
+        }
+    }
+
+    /// Returns the monotonic instant of the deadline, or `None` if it never expires.
+    #[cfg(feature = "async-tokio")]
+    pub(crate) fn instant(&self) -> Option<Instant> {
+        self.at
+    }
+}
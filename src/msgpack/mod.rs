@@ -0,0 +1,173 @@
+use super::error::WTimeError;
+
+/// Extension type reserved by the MessagePack specification for timestamps.
+pub const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// ### timestamp_ext_data(ts: u64, nanos: u64) -> Vec<u8>
+///
+/// Encodes a UNIX timestamp as the payload of a MessagePack timestamp extension.
+///
+/// The smallest of the three layouts defined by the specification is chosen:
+/// - timestamp 32 (4 bytes) when there are no nanoseconds and the seconds fit in 32 bits
+/// - timestamp 64 (8 bytes) when the seconds fit in 34 bits
+/// - timestamp 96 (12 bytes) otherwise
+///
+/// Use this with `rmp::encode::write_ext_meta(wr, data.len() as u32, TIMESTAMP_EXT_TYPE)`
+/// or any other writer that emits the extension header itself. `nanos` must be below one
+/// second; larger values are reduced modulo one second.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::msgpack::timestamp_ext_data;
+///
+/// assert_eq!(timestamp_ext_data(1728933069, 0), 1728933069u32.to_be_bytes());
+/// assert_eq!(timestamp_ext_data(1728933069, 500_000_000).len(), 8);
+/// assert_eq!(timestamp_ext_data(1 << 34, 0).len(), 12);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the 4, 8 or 12 payload bytes.
+///
+/// <small>End Fun Doc</small>
+pub fn timestamp_ext_data(ts: u64, nanos: u64) -> Vec<u8> {
+    let nanos = nanos % 1_000_000_000;
+    if nanos == 0 && ts <= u32::MAX as u64 {
+        (ts as u32).to_be_bytes().to_vec()
+    } else if ts < 1 << 34 {
+        (nanos << 34 | ts).to_be_bytes().to_vec()
+    } else {
+        let mut data = (nanos as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(&(ts.min(i64::MAX as u64) as i64).to_be_bytes());
+        data
+    }
+}
+
+/// ### decode_timestamp_ext_data(data: &[u8]) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes the payload of a MessagePack timestamp extension.
+///
+/// Accepts all three layouts, distinguished by their length, as produced by
+/// [`timestamp_ext_data`] or any other MessagePack implementation.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::msgpack::{decode_timestamp_ext_data, timestamp_ext_data};
+///
+/// let data = timestamp_ext_data(1728933069, 500_000_000);
+/// assert_eq!(decode_timestamp_ext_data(&data), Ok((1728933069, 500_000_000)));
+/// assert!(decode_timestamp_ext_data(&[0, 1, 2]).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] for a payload of the wrong length, or
+/// [`WTimeError::OutOfRange`] for nanoseconds above one second or a negative timestamp 96.
+///
+/// <small>End Fun Doc</small>
+pub fn decode_timestamp_ext_data(data: &[u8]) -> Result<(u64, u64), WTimeError> {
+    let (ts, nanos) = match data.len() {
+        4 => (u32::from_be_bytes(data.try_into().unwrap()) as u64, 0),
+        8 => {
+            let value = u64::from_be_bytes(data.try_into().unwrap());
+            (value & ((1 << 34) - 1), value >> 34)
+        }
+        12 => {
+            let nanos = u32::from_be_bytes(data[..4].try_into().unwrap()) as u64;
+            let ts = i64::from_be_bytes(data[4..].try_into().unwrap());
+            let ts = u64::try_from(ts).map_err(|_| WTimeError::OutOfRange("seconds"))?;
+            (ts, nanos)
+        }
+        _ => {
+            return Err(WTimeError::InvalidFormat(
+                "a 4, 8 or 12 byte timestamp extension",
+            ))
+        }
+    };
+    if nanos >= 1_000_000_000 {
+        return Err(WTimeError::OutOfRange("nanos"));
+    }
+    Ok((ts, nanos))
+}
+
+/// ### encode_timestamp(ts: u64, nanos: u64) -> Vec<u8>
+///
+/// Encodes a UNIX timestamp as a complete MessagePack timestamp extension value, including
+/// the `fixext 4`, `fixext 8` or `ext 8` header and the `-1` type.
+///
+/// The bytes can be spliced directly into a MessagePack stream, e.g. with
+/// `std::io::Write::write_all` on the same writer used by `rmp`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::msgpack::encode_timestamp;
+///
+/// assert_eq!(encode_timestamp(1728933069, 0), [0xd6, 0xff, 0x67, 0x0d, 0x6c, 0xcd]);
+/// assert_eq!(encode_timestamp(1728933069, 1)[..2], [0xd7, 0xff]);
+/// assert_eq!(encode_timestamp(1 << 34, 0)[..3], [0xc7, 12, 0xff]);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the 6, 10 or 15 encoded bytes.
+///
+/// <small>End Fun Doc</small>
+pub fn encode_timestamp(ts: u64, nanos: u64) -> Vec<u8> {
+    let data = timestamp_ext_data(ts, nanos);
+    let mut encoded = match data.len() {
+        4 => vec![0xd6],
+        8 => vec![0xd7],
+        _ => vec![0xc7, data.len() as u8],
+    };
+    encoded.push(TIMESTAMP_EXT_TYPE as u8);
+    encoded.extend_from_slice(&data);
+    encoded
+}
+
+/// ### decode_timestamp(bytes: &[u8]) -> Result<(u64, u64), WTimeError>
+///
+/// Decodes a complete MessagePack timestamp extension value, header included.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::msgpack::{decode_timestamp, encode_timestamp};
+///
+/// for (ts, nanos) in [(0, 0), (1728933069, 0), (1728933069, 999_999_999), (1 << 40, 7)] {
+///     assert_eq!(decode_timestamp(&encode_timestamp(ts, nanos)), Ok((ts, nanos)));
+/// }
+/// assert!(decode_timestamp(&[0xd6, 0x01, 0, 0, 0, 0]).is_err()); // not the timestamp type
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the bytes are not exactly one timestamp
+/// extension, or [`WTimeError::OutOfRange`] for invalid nanoseconds or a negative
+/// timestamp 96.
+///
+/// <small>End Fun Doc</small>
+pub fn decode_timestamp(bytes: &[u8]) -> Result<(u64, u64), WTimeError> {
+    const EXPECTED: &str = "a MessagePack timestamp extension";
+
+    let (ext_type, data) = match bytes {
+        [0xd6, ext_type, data @ ..] if data.len() == 4 => (*ext_type, data),
+        [0xd7, ext_type, data @ ..] if data.len() == 8 => (*ext_type, data),
+        [0xc7, 12, ext_type, data @ ..] if data.len() == 12 => (*ext_type, data),
+        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+    };
+    if ext_type as i8 != TIMESTAMP_EXT_TYPE {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    decode_timestamp_ext_data(data)
+}
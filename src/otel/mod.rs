@@ -0,0 +1,139 @@
+use super::utc::utc_now;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// ### to_unix_nanos(time: SystemTime) -> u64
+///
+/// Converts a `SystemTime` into the `fixed64` nanoseconds-since-epoch encoding used by the
+/// OTLP `time_unix_nano`, `start_time_unix_nano` and `end_time_unix_nano` fields.
+///
+/// OTLP reserves `0` for "unset", so times at or before the UNIX epoch map to `0`. Times
+/// after the year 2554, beyond the range of a `u64` of nanoseconds, saturate at `u64::MAX`.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use wtime::otel::to_unix_nanos;
+///
+/// let time = UNIX_EPOCH + Duration::new(1728933069, 123_456_789);
+/// assert_eq!(to_unix_nanos(time), 1728933069_123_456_789);
+/// assert_eq!(to_unix_nanos(UNIX_EPOCH), 0);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the nanoseconds since the UNIX epoch as a `u64`.
+///
+/// <small>End Fun Doc</small>
+pub fn to_unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |since| {
+        u64::try_from(since.as_nanos()).unwrap_or(u64::MAX)
+    })
+}
+
+/// ### from_unix_nanos(nanos: u64) -> SystemTime
+///
+/// Converts an OTLP nanoseconds-since-epoch value into a `SystemTime`.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use wtime::otel::from_unix_nanos;
+///
+/// assert_eq!(
+///     from_unix_nanos(1728933069_123_456_789),
+///     UNIX_EPOCH + Duration::new(1728933069, 123_456_789)
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns the corresponding `SystemTime`.
+///
+/// <small>End Fun Doc</small>
+pub fn from_unix_nanos(nanos: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_nanos(nanos)
+}
+
+/// ### now_unix_nanos() -> u64
+///
+/// Retrieves the current UTC time in the OTLP nanoseconds-since-epoch encoding.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::otel::now_unix_nanos;
+///
+/// let observed = now_unix_nanos();
+/// println!("time_unix_nano: {}", observed);
+/// assert!(observed > 1728933069_000_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current time as nanoseconds since the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn now_unix_nanos() -> u64 {
+    to_unix_nanos(utc_now())
+}
+
+/// ### SpanTimer
+///
+/// Measures a span and produces its OTLP start and end timestamps.
+///
+/// The wall clock and a monotonic `Instant` are read together when the span starts. The end
+/// timestamp is derived from the start wall time plus the monotonic elapsed time, so an NTP
+/// step in the middle of a span can never produce an end before the start or a wrong
+/// duration, which is what the OpenTelemetry SDKs do as well.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::otel::SpanTimer;
+///
+/// let span = SpanTimer::start();
+/// // ... do the traced work ...
+/// let (start_time_unix_nano, end_time_unix_nano) = span.end();
+/// assert!(end_time_unix_nano >= start_time_unix_nano);
+/// assert_eq!(start_time_unix_nano, span.start_unix_nanos());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy)]
+pub struct SpanTimer {
+    start_unix_nanos: u64,
+    start: Instant,
+}
+
+impl SpanTimer {
+    /// Starts a span now.
+    pub fn start() -> Self {
+        SpanTimer {
+            start_unix_nanos: now_unix_nanos(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Returns the OTLP `start_time_unix_nano` of the span.
+    pub fn start_unix_nanos(&self) -> u64 {
+        self.start_unix_nanos
+    }
+
+    /// Returns the monotonic time elapsed since the span started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the `(start_time_unix_nano, end_time_unix_nano)` pair, ending the span now.
+    ///
+    /// The timer is not consumed, so it can be called again for a later end time.
+    pub fn end(&self) -> (u64, u64) {
+        let elapsed = u64::try_from(self.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        (
+            self.start_unix_nanos,
+            self.start_unix_nanos.saturating_add(elapsed),
+        )
+    }
+}
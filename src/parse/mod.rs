@@ -0,0 +1,1408 @@
+use super::{
+    calc::{
+        civil_from_days, days_from_civil, get_day_name, get_month_name, month_length, shift_months,
+        unit_bounds, Unit, Weekday, MAX_TS, MAX_YEAR,
+    },
+    datetime::DateTime,
+    error::WTimeError,
+    range::TimeRange,
+};
+use std::time::Duration;
+
+/// ### ExifDateTime
+///
+/// A photo timestamp assembled from the EXIF date, sub-second and offset tags.
+///
+/// Returned by [`parse_exif_date`].
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExifDateTime {
+    /// Seconds since the UNIX epoch. When `offset_minutes` is `None` the wall-clock time was
+    /// read as if it were UTC.
+    pub ts: u64,
+    /// Sub-second part in nanoseconds (0-999,999,999), `0` without a `SubSecTime*` tag.
+    pub nanos: u64,
+    /// UTC offset in minutes from the `OffsetTime*` tag, if present.
+    pub offset_minutes: Option<i64>,
+}
+
+/// Components of an ISO 8601 duration such as `P1Y2M3DT4H5M6.5S`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IsoDuration {
+    pub(crate) years: u64,
+    pub(crate) months: u64,
+    pub(crate) weeks: u64,
+    pub(crate) days: u64,
+    pub(crate) hours: u64,
+    pub(crate) minutes: u64,
+    pub(crate) seconds: u64,
+    pub(crate) nanos: u64,
+}
+
+impl IsoDuration {
+    /// Parses the `PnYnMnDTnHnMnS` and `PnW` forms. Only the seconds may have a fraction, and
+    /// each designator may appear at most once and in that order.
+    pub(crate) fn parse(s: &str) -> Result<Self, WTimeError> {
+        const EXPECTED: &str = "an ISO 8601 duration such as `P1DT2H30M`";
+
+        let rest = s
+            .strip_prefix(['P', 'p'])
+            .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+        let mut duration = IsoDuration::default();
+        let mut in_time = false;
+        let mut number = String::new();
+        let mut seen_component = false;
+        let mut last_rank = None;
+
+        for c in rest.chars() {
+            match c.to_ascii_uppercase() {
+                '0'..='9' | '.' | ',' => number.push(c),
+                'T' if !in_time && number.is_empty() => in_time = true,
+                unit => {
+                    let (whole, fraction) = match number.split_once(['.', ',']) {
+                        Some((whole, fraction)) => (whole, Some(fraction)),
+                        None => (number.as_str(), None),
+                    };
+                    let value = parse_digits(whole, EXPECTED)?;
+                    if fraction.is_some() && !(in_time && unit == 'S') {
+                        return Err(WTimeError::InvalidFormat(EXPECTED));
+                    }
+                    let (rank, field) = match (in_time, unit) {
+                        (false, 'Y') => (0, &mut duration.years),
+                        (false, 'M') => (1, &mut duration.months),
+                        (false, 'W') => (2, &mut duration.weeks),
+                        (false, 'D') => (3, &mut duration.days),
+                        (true, 'H') => (4, &mut duration.hours),
+                        (true, 'M') => (5, &mut duration.minutes),
+                        (true, 'S') => (6, &mut duration.seconds),
+                        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+                    };
+                    // Each designator at most once, from years down to seconds
+                    if last_rank >= Some(rank) {
+                        return Err(WTimeError::InvalidFormat(EXPECTED));
+                    }
+                    last_rank = Some(rank);
+                    *field = value;
+                    if let Some(fraction) = fraction {
+                        duration.nanos = parse_fraction(fraction, EXPECTED)?;
+                    }
+                    number.clear();
+                    seen_component = true;
+                }
+            }
+        }
+
+        // A trailing number without unit, a bare `P` or a `T` without time components
+        if !number.is_empty() || !seen_component || rest.to_ascii_uppercase().ends_with('T') {
+            return Err(WTimeError::InvalidFormat(EXPECTED));
+        }
+        Ok(duration)
+    }
+
+    /// Converts the duration to an exact `Duration`, which is only possible without the
+    /// calendar components (years and months) whose length varies.
+    pub(crate) fn exact(&self) -> Result<Duration, WTimeError> {
+        if self.years != 0 || self.months != 0 {
+            return Err(WTimeError::InvalidFormat(
+                "an ISO 8601 duration without years or months",
+            ));
+        }
+        [
+            (self.weeks, 7 * 86_400),
+            (self.days, 86_400),
+            (self.hours, 3600),
+            (self.minutes, 60),
+            (self.seconds, 1),
+        ]
+        .into_iter()
+        .try_fold(0u64, |secs, (value, unit)| {
+            value
+                .checked_mul(unit)
+                .and_then(|part| secs.checked_add(part))
+        })
+        .map(|secs| Duration::new(secs, self.nanos as u32))
+        .ok_or(WTimeError::OutOfRange("duration"))
+    }
+
+    /// Applies the duration to a timestamp, forwards or backwards. Calendar components
+    /// (years and months) are applied first with end-of-month clamping, then the exact ones.
+    pub(crate) fn shift(&self, ts: u64, forward: bool) -> Result<u64, WTimeError> {
+        let sign = if forward { 1 } else { -1 };
+        let ts = i64::try_from(ts).map_err(|_| WTimeError::OutOfRange("timestamp"))?;
+        let (year, month, day) = civil_from_days(ts.div_euclid(86_400));
+        // Limiting the months to MAX_YEAR years keeps the calendar math far from overflow
+        let months = i64::try_from(self.years)
+            .ok()
+            .and_then(|years| years.checked_mul(12))
+            .and_then(|months| months.checked_add(i64::try_from(self.months).ok()?))
+            .filter(|&months| months <= MAX_YEAR as i64 * 12)
+            .ok_or(WTimeError::OutOfRange("duration"))?;
+        let (year, month, day) = shift_months(year, month, day, months * sign);
+
+        let exact = [
+            (self.weeks, 7 * 86_400),
+            (self.days, 86_400),
+            (self.hours, 3600),
+            (self.minutes, 60),
+            (self.seconds, 1),
+        ]
+        .into_iter()
+        .try_fold(0i64, |secs, (value, unit)| {
+            i64::try_from(value)
+                .ok()?
+                .checked_mul(unit)
+                .and_then(|part| secs.checked_add(part))
+        })
+        .ok_or(WTimeError::OutOfRange("duration"))?;
+        days_from_civil(year, month, day)
+            .checked_mul(86_400)
+            .and_then(|secs| secs.checked_add(ts.rem_euclid(86_400)))
+            .and_then(|secs| secs.checked_add(exact * sign))
+            .and_then(|shifted| u64::try_from(shifted).ok())
+            .ok_or(WTimeError::OutOfRange("timestamp"))
+    }
+}
+
+/// Parses a run of ASCII digits, rejecting signs, spaces and empty input.
+fn parse_digits(s: &str, expected: &'static str) -> Result<u64, WTimeError> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(WTimeError::InvalidFormat(expected));
+    }
+    s.parse().map_err(|_| WTimeError::OutOfRange("number"))
+}
+
+/// Parses a `+HHMM`/`-HHMM` UTC offset into minutes.
+fn parse_offset_hhmm(s: &str, expected: &'static str) -> Result<i64, WTimeError> {
+    let sign = match s.as_bytes().first() {
+        Some(b'+') => 1,
+        Some(b'-') => -1,
+        _ => return Err(WTimeError::InvalidFormat(expected)),
+    };
+    // Checking for ASCII first keeps the byte slicing below on character boundaries
+    let digits = &s[1..];
+    if digits.len() != 4 || !digits.is_ascii() {
+        return Err(WTimeError::InvalidFormat(expected));
+    }
+    let hours = parse_digits(&digits[..2], expected)?;
+    let minutes = parse_digits(&digits[2..], expected)?;
+    if hours > 23 || minutes > 59 {
+        return Err(WTimeError::OutOfRange("offset"));
+    }
+    Ok(sign * (hours * 60 + minutes) as i64)
+}
+
+/// Parses a `+HH:MM`/`-HH:MM`/`Z` UTC offset, as used by RFC 3339, into minutes.
+fn parse_offset_rfc3339(s: &str, expected: &'static str) -> Result<i64, WTimeError> {
+    if s.eq_ignore_ascii_case("z") {
+        return Ok(0);
+    }
+    match s.split_once(':') {
+        Some((hours, minutes)) if hours.len() == 3 && minutes.len() == 2 => {
+            parse_offset_hhmm(&format!("{}{}", hours, minutes), expected)
+        }
+        _ => Err(WTimeError::InvalidFormat(expected)),
+    }
+}
+
+/// Parses an `HH:MM:SS` time of day.
+fn parse_hms(s: &str, expected: &'static str) -> Result<(u64, u64, u64), WTimeError> {
+    let mut parts = s.split(':');
+    let (Some(hour), Some(minute), Some(second), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(WTimeError::InvalidFormat(expected));
+    };
+    Ok((
+        parse_two_digits(hour, expected)?,
+        parse_two_digits(minute, expected)?,
+        parse_two_digits(second, expected)?,
+    ))
+}
+
+/// Parses exactly two ASCII digits.
+fn parse_two_digits(s: &str, expected: &'static str) -> Result<u64, WTimeError> {
+    if s.len() != 2 {
+        return Err(WTimeError::InvalidFormat(expected));
+    }
+    parse_digits(s, expected)
+}
+
+/// Parses a `YYYY-MM-DD` calendar date.
+fn parse_ymd(s: &str, expected: &'static str) -> Result<(i64, u64, u64), WTimeError> {
+    let mut parts = s.split('-');
+    let (Some(year), Some(month), Some(day), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(WTimeError::InvalidFormat(expected));
+    };
+    if year.len() != 4 {
+        return Err(WTimeError::InvalidFormat(expected));
+    }
+    Ok((
+        parse_digits(year, expected)? as i64,
+        parse_two_digits(month, expected)?,
+        parse_two_digits(day, expected)?,
+    ))
+}
+
+/// Parses 1-9 fractional digits of a second into nanoseconds.
+fn parse_fraction(s: &str, expected: &'static str) -> Result<u64, WTimeError> {
+    if s.len() > 9 {
+        return Err(WTimeError::InvalidFormat(expected));
+    }
+    Ok(parse_digits(s, expected)? * 10u64.pow(9 - s.len() as u32))
+}
+
+/// Strips the space and NUL padding cameras leave around EXIF ASCII tags.
+fn trim_exif_tag(s: &str) -> &str {
+    s.trim_matches(|c: char| c == '\0' || c.is_whitespace())
+}
+
+/// Looks up a month number (1-12) by its English three-letter abbreviation.
+fn month_from_abbr(s: &str) -> Option<u64> {
+    (1..=12).find(|&month| get_month_name(month)[..3].eq_ignore_ascii_case(s))
+}
+
+/// Checks whether `s` is an English three-letter weekday abbreviation.
+fn is_day_abbr(s: &str) -> bool {
+    (0..7).any(|day| get_day_name(day * 86_400)[..3].eq_ignore_ascii_case(s))
+}
+
+/// Checks whether `s` is a full English weekday name.
+fn is_day_name(s: &str) -> bool {
+    (0..7).any(|day| get_day_name(day * 86_400).eq_ignore_ascii_case(s))
+}
+
+/// Validates wall-clock components and converts them into a UNIX timestamp, undoing the
+/// given UTC offset.
+fn civil_to_ts(
+    (year, month, day): (i64, u64, u64),
+    (hour, minute, second): (u64, u64, u64),
+    offset_minutes: i64,
+) -> Result<u64, WTimeError> {
+    if !(0..=9999).contains(&year) {
+        return Err(WTimeError::OutOfRange("year"));
+    }
+    if !(1..=12).contains(&month) {
+        return Err(WTimeError::OutOfRange("month"));
+    }
+    if day == 0 || day > month_length(year, month) {
+        return Err(WTimeError::OutOfRange("day"));
+    }
+    if hour > 23 {
+        return Err(WTimeError::OutOfRange("hour"));
+    }
+    if minute > 59 {
+        return Err(WTimeError::OutOfRange("minute"));
+    }
+    if second > 59 {
+        return Err(WTimeError::OutOfRange("second"));
+    }
+
+    let ts = days_from_civil(year, month, day) * 86_400
+        + (hour * 3600 + minute * 60 + second) as i64
+        - offset_minutes * 60;
+    u64::try_from(ts).map_err(|_| WTimeError::OutOfRange("timestamp"))
+}
+
+/// ### parse_git_raw(s: &str) -> Result<(u64, i64), WTimeError>
+///
+/// Parses a date in Git's raw format.
+///
+/// This function reads the `<seconds> <+|-HHMM>` form stored in commit objects and printed by
+/// `git log --date=raw`, the inverse of [`crate::format::git_raw`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_git_raw;
+///
+/// assert_eq!(parse_git_raw("1728933069 +0200"), Ok((1728933069, 120)));
+/// assert_eq!(parse_git_raw("1728933069 -0530"), Ok((1728933069, -330)));
+/// assert!(parse_git_raw("1728933069").is_err());
+/// assert!(parse_git_raw("1 +1é1").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the UTC offset in minutes.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not in the raw format, or
+/// [`WTimeError::OutOfRange`] if the offset is not a valid `HHMM` value.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_git_raw(s: &str) -> Result<(u64, i64), WTimeError> {
+    const EXPECTED: &str = "`<seconds> <+|-HHMM>`";
+
+    let mut parts = s.split_whitespace();
+    let (Some(seconds), Some(offset), None) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    };
+    Ok((
+        parse_digits(seconds, EXPECTED)?,
+        parse_offset_hhmm(offset, EXPECTED)?,
+    ))
+}
+
+/// ### parse_git_date(s: &str) -> Result<(u64, i64), WTimeError>
+///
+/// Parses a date in Git's default human-readable format.
+///
+/// This function reads dates such as `Mon Oct 14 21:11:09 2024 +0200`, as printed by
+/// `git log`, and is the inverse of [`crate::format::git_date`]. The day of the month may
+/// or may not be zero-padded. The weekday name must be a valid abbreviation but is not
+/// checked against the date, matching Git's own leniency.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_git_date;
+///
+/// assert_eq!(
+///     parse_git_date("Mon Oct 14 21:11:09 2024 +0200"),
+///     Ok((1728933069, 120))
+/// );
+/// assert_eq!(
+///     parse_git_date("Tue Oct 1 00:00:00 2024 +0000"),
+///     Ok((1727740800, 0))
+/// );
+/// assert!(parse_git_date("Mon Oct 32 21:11:09 2024 +0200").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the UTC offset in minutes.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input does not follow the layout, or
+/// [`WTimeError::OutOfRange`] if a component is invalid (e.g. February 30).
+///
+/// <small>End Fun Doc</small>
+pub fn parse_git_date(s: &str) -> Result<(u64, i64), WTimeError> {
+    const EXPECTED: &str = "`Ddd Mmm D HH:MM:SS YYYY <+|-HHMM>`";
+
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let [weekday, month, day, time, year, offset] = parts[..] else {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    };
+    if !is_day_abbr(weekday) {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let month = month_from_abbr(month).ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let day = parse_digits(day, EXPECTED)?;
+    let year = parse_digits(year, EXPECTED)? as i64;
+    let offset = parse_offset_hhmm(offset, EXPECTED)?;
+
+    let ts = civil_to_ts((year, month, day), parse_hms(time, EXPECTED)?, offset)?;
+    Ok((ts, offset))
+}
+
+/// ### parse_rss_date(s: &str) -> Result<(u64, i64), WTimeError>
+///
+/// Parses an RSS 2.0 date (RFC 822 date-time).
+///
+/// Feeds found in the wild are often sloppy, so this parser is lenient where RFC 822 and
+/// its successors allow variation:
+/// - the leading weekday (`Mon, `) is optional and not checked against the date
+/// - the day may have one or two digits
+/// - the year may have two digits, read as 2000-2049 for `00`-`49` and 1950-1999 for
+///   `50`-`99`, as RFC 2822 prescribes
+/// - the seconds are optional
+/// - the zone may be numeric (`+0200`) or one of `UT`, `GMT`, `Z`, `EST`, `EDT`, `CST`,
+///   `CDT`, `MST`, `MDT`, `PST` and `PDT`
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_rss_date;
+///
+/// assert_eq!(
+///     parse_rss_date("Mon, 14 Oct 2024 21:11:09 +0200"),
+///     Ok((1728933069, 120))
+/// );
+/// assert_eq!(parse_rss_date("14 Oct 24 19:11:09 GMT"), Ok((1728933069, 0)));
+/// assert_eq!(parse_rss_date("Mon, 14 Oct 2024 15:11 EDT"), Ok((1728933060, -240)));
+/// assert!(parse_rss_date("Mon, 14 Oct 2024").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the UTC offset in minutes.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an RFC 822 date, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_rss_date(s: &str) -> Result<(u64, i64), WTimeError> {
+    const EXPECTED: &str = "`[Ddd, ]D Mmm YY[YY] HH:MM[:SS] zone`";
+
+    let s = s.trim();
+    let s = match s.split_once(',') {
+        Some((weekday, rest)) if is_day_abbr(weekday.trim()) => rest,
+        Some(_) => return Err(WTimeError::InvalidFormat(EXPECTED)),
+        None => s,
+    };
+
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let [day, month, year, time, zone] = parts[..] else {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    };
+    if day.len() > 2 {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let day = parse_digits(day, EXPECTED)?;
+    let month = month_from_abbr(month).ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let year = match (year.len(), parse_digits(year, EXPECTED)? as i64) {
+        (2, year) if year < 50 => 2000 + year,
+        (2, year) => 1900 + year,
+        (4, year) => year,
+        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+    };
+    let (hour, minute, second) = match time.len() {
+        5 => {
+            let (hour, minute) = time
+                .split_once(':')
+                .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+            (
+                parse_two_digits(hour, EXPECTED)?,
+                parse_two_digits(minute, EXPECTED)?,
+                0,
+            )
+        }
+        _ => parse_hms(time, EXPECTED)?,
+    };
+    let offset = match zone.to_ascii_uppercase().as_str() {
+        "UT" | "GMT" | "Z" => 0,
+        "EDT" => -4 * 60,
+        "EST" | "CDT" => -5 * 60,
+        "CST" | "MDT" => -6 * 60,
+        "MST" | "PDT" => -7 * 60,
+        "PST" => -8 * 60,
+        _ => parse_offset_hhmm(zone, EXPECTED)?,
+    };
+
+    let ts = civil_to_ts((year, month, day), (hour, minute, second), offset)?;
+    Ok((ts, offset))
+}
+
+/// ### parse_rfc2822(s: &str) -> Result<(u64, i64), WTimeError>
+///
+/// Parses an RFC 2822 date, such as the value of an email `Date:` header.
+///
+/// RFC 2822 and its successor RFC 5322 use the same date-time as RSS, so this accepts what
+/// [`parse_rss_date`] accepts, including the obsolete forms mail software must still read:
+/// a missing weekday or seconds, two-digit years and the `GMT`, `UT` and North American
+/// zone names.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::format::rfc2822;
+/// use wtime::parse::parse_rfc2822;
+///
+/// assert_eq!(
+///     parse_rfc2822("Mon, 14 Oct 2024 21:11:09 +0200"),
+///     Ok((1728933069, 120))
+/// );
+/// assert_eq!(parse_rfc2822(&rfc2822(1728933069, -300)), Ok((1728933069, -300)));
+/// assert!(parse_rfc2822("2024-10-14T19:11:09Z").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the UTC offset in minutes.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an RFC 2822 date, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_rfc2822(s: &str) -> Result<(u64, i64), WTimeError> {
+    parse_rss_date(s)
+}
+
+/// ### parse_http_date(s: &str) -> Result<u64, WTimeError>
+///
+/// Parses an HTTP date in any of the three formats HTTP recipients must accept.
+///
+/// - IMF-fixdate, the format every sender should use: `Sun, 06 Nov 1994 08:49:37 GMT`
+/// - the obsolete RFC 850 format: `Sunday, 06-Nov-94 08:49:37 GMT`
+/// - the obsolete ANSI C `asctime()` format: `Sun Nov  6 08:49:37 1994`
+///
+/// HTTP dates are always in GMT. The weekday is not checked against the date. The
+/// two-digit years of the RFC 850 format are read as 2000-2069 for `00`-`69` and 1970-1999
+/// for `70`-`99`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_http_date;
+///
+/// assert_eq!(parse_http_date("Tue, 15 Oct 2024 08:12:31 GMT"), Ok(1728979951));
+/// assert_eq!(parse_http_date("Tuesday, 15-Oct-24 08:12:31 GMT"), Ok(1728979951));
+/// assert_eq!(parse_http_date("Tue Oct 15 08:12:31 2024"), Ok(1728979951));
+/// assert_eq!(parse_http_date("Sun Nov  6 08:49:37 1994"), Ok(784111777));
+/// assert!(parse_http_date("Tue, 15 Oct 2024 08:12:31 +0000").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an HTTP date, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_http_date(s: &str) -> Result<u64, WTimeError> {
+    const EXPECTED: &str = "an HTTP date such as `Tue, 15 Oct 2024 08:12:31 GMT`";
+
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    let (day, month, year, time) = match parts[..] {
+        [weekday, day, month, year, time, "GMT"] => {
+            let weekday = weekday
+                .strip_suffix(',')
+                .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+            if !is_day_abbr(weekday) || year.len() != 4 {
+                return Err(WTimeError::InvalidFormat(EXPECTED));
+            }
+            (parse_two_digits(day, EXPECTED)?, month, year, time)
+        }
+        [weekday, date, time, "GMT"] => {
+            let weekday = weekday
+                .strip_suffix(',')
+                .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+            let mut date = date.split('-');
+            let (true, Some(day), Some(month), Some(year), None) = (
+                is_day_name(weekday),
+                date.next(),
+                date.next(),
+                date.next(),
+                date.next(),
+            ) else {
+                return Err(WTimeError::InvalidFormat(EXPECTED));
+            };
+            (parse_two_digits(day, EXPECTED)?, month, year, time)
+        }
+        [weekday, month, day, time, year] => {
+            if !is_day_abbr(weekday) || day.len() > 2 || year.len() != 4 {
+                return Err(WTimeError::InvalidFormat(EXPECTED));
+            }
+            (parse_digits(day, EXPECTED)?, month, year, time)
+        }
+        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+    };
+
+    let month = month_from_abbr(month).ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let year = match (year.len(), parse_digits(year, EXPECTED)? as i64) {
+        (2, year) if year < 70 => 2000 + year,
+        (2, year) => 1900 + year,
+        (4, year) => year,
+        _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+    };
+    civil_to_ts((year, month, day), parse_hms(time, EXPECTED)?, 0)
+}
+
+/// ### parse_atom_date(s: &str) -> Result<(u64, i64), WTimeError>
+///
+/// Parses an Atom feed date (RFC 3339).
+///
+/// This function reads timestamps such as `2024-10-14T19:11:09Z` or
+/// `2024-10-14T21:11:09.250+02:00`, the inverse of [`crate::format::atom_date`].
+/// Fractional seconds are accepted and truncated to whole seconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_atom_date;
+///
+/// assert_eq!(parse_atom_date("2024-10-14T19:11:09Z"), Ok((1728933069, 0)));
+/// assert_eq!(
+///     parse_atom_date("2024-10-14T21:11:09.250+02:00"),
+///     Ok((1728933069, 120))
+/// );
+/// assert!(parse_atom_date("2024-10-14 19:11:09").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the UTC offset in minutes.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an RFC 3339 timestamp, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_atom_date(s: &str) -> Result<(u64, i64), WTimeError> {
+    let (ts, _, offset) = parse_rfc3339_parts(s)?;
+    Ok((ts, offset))
+}
+
+/// ### parse_rfc3339(s: &str) -> Result<DateTime, WTimeError>
+///
+/// Parses an RFC 3339 / ISO 8601 timestamp such as `2024-10-14T19:11:09.123Z`.
+///
+/// The date and time must be separated by `T` (or `t`) and followed by `Z` or a
+/// `+HH:MM`/`-HH:MM` offset. Fractions of up to 9 digits are kept; longer ones are truncated
+/// to nanoseconds. This is the inverse of [`crate::utc::utc_rfc3339`] and of the `Display`
+/// implementation of [`DateTime`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_rfc3339;
+///
+/// let dt = parse_rfc3339("2024-10-14T19:11:09.123Z").unwrap();
+/// assert_eq!((dt.ts, dt.nanos, dt.offset_minutes), (1728933069, 123_000_000, 0));
+///
+/// let dt = parse_rfc3339("2024-10-15T00:41:09+05:30").unwrap();
+/// assert_eq!(dt.ts, 1728933069);
+/// assert_eq!(dt.date(), (2024, 10, 15));
+///
+/// assert!(parse_rfc3339("2024-10-14 19:11:09").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`DateTime`] holding the instant and the offset it was written in.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an RFC 3339 timestamp, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_rfc3339(s: &str) -> Result<DateTime, WTimeError> {
+    let (ts, nanos, offset) = parse_rfc3339_parts(s)?;
+    Ok(DateTime::new(ts, nanos, offset))
+}
+
+/// Parses an RFC 3339 timestamp into the UNIX timestamp, the sub-second part in
+/// nanoseconds (digits beyond nanosecond precision are truncated) and the offset in minutes.
+pub(crate) fn parse_rfc3339_parts(s: &str) -> Result<(u64, u64, i64), WTimeError> {
+    const EXPECTED: &str = "`YYYY-MM-DDTHH:MM:SS[.fff]<Z|+HH:MM>`";
+
+    let (date, rest) = s
+        .split_once(['T', 't'])
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let (year, month, day) = parse_ymd(date, EXPECTED)?;
+
+    // The time is always 8 characters, optionally followed by a fraction, then the offset
+    if rest.len() < 9 || !rest.is_char_boundary(8) {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+    let (time, rest) = rest.split_at(8);
+    let hms = parse_hms(time, EXPECTED)?;
+    let (nanos, zone) = match rest.strip_prefix('.') {
+        Some(fraction) => {
+            let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return Err(WTimeError::InvalidFormat(EXPECTED));
+            }
+            (
+                parse_fraction(&fraction[..digits.min(9)], EXPECTED)?,
+                &fraction[digits..],
+            )
+        }
+        None => (0, rest),
+    };
+    let offset = parse_offset_rfc3339(zone, EXPECTED)?;
+
+    let ts = civil_to_ts((year, month, day), hms, offset)?;
+    Ok((ts, nanos, offset))
+}
+
+/// ### parse_exif_date(date: &str, subsec: Option<&str>, offset: Option<&str>) -> Result<ExifDateTime, WTimeError>
+///
+/// Parses the EXIF `DateTimeOriginal` tag together with its companion tags.
+///
+/// `date` is the `YYYY:MM:DD HH:MM:SS` value of `DateTime`, `DateTimeOriginal` or
+/// `DateTimeDigitized`. `subsec` is the matching `SubSecTime*` value (decimal digits of the
+/// fraction of a second) and `offset` the matching `OffsetTime*` value (`+HH:MM`). Cameras
+/// pad tags with spaces and NUL bytes, so surrounding whitespace and NULs are ignored.
+///
+/// EXIF timestamps without an offset tag are wall-clock times in an unknown zone; in that
+/// case the returned timestamp treats them as UTC and `offset_minutes` is `None`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::{parse_exif_date, ExifDateTime};
+///
+/// assert_eq!(
+///     parse_exif_date("2024:10:14 21:11:09", Some("25"), Some("+02:00")),
+///     Ok(ExifDateTime { ts: 1728933069, nanos: 250_000_000, offset_minutes: Some(120) })
+/// );
+/// assert_eq!(
+///     parse_exif_date("2024:10:14 19:11:09", None, None),
+///     Ok(ExifDateTime { ts: 1728933069, nanos: 0, offset_minutes: None })
+/// );
+/// // Unknown dates are written as blanks by some cameras
+/// assert!(parse_exif_date("    :  :     :  :  ", None, None).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns an [`ExifDateTime`] with the timestamp, sub-second nanoseconds and offset.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if a tag does not follow the EXIF layout, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_exif_date(
+    date: &str,
+    subsec: Option<&str>,
+    offset: Option<&str>,
+) -> Result<ExifDateTime, WTimeError> {
+    const EXPECTED: &str = "`YYYY:MM:DD HH:MM:SS`";
+    const EXPECTED_SUBSEC: &str = "decimal digits";
+    const EXPECTED_OFFSET: &str = "`+HH:MM`";
+
+    let date = trim_exif_tag(date);
+    let (ymd, time) = date
+        .split_once(' ')
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let (year, month, day) = parse_ymd(&ymd.replace(':', "-"), EXPECTED)?;
+    let hms = parse_hms(time, EXPECTED)?;
+
+    let nanos = match subsec.map(trim_exif_tag) {
+        Some(digits) if !digits.is_empty() => parse_fraction(digits, EXPECTED_SUBSEC)?,
+        _ => 0,
+    };
+
+    let offset_minutes = match offset.map(trim_exif_tag) {
+        Some(zone) if zone.eq_ignore_ascii_case("z") => {
+            return Err(WTimeError::InvalidFormat(EXPECTED_OFFSET));
+        }
+        Some(zone) if !zone.is_empty() => Some(parse_offset_rfc3339(zone, EXPECTED_OFFSET)?),
+        _ => None,
+    };
+
+    Ok(ExifDateTime {
+        ts: civil_to_ts((year, month, day), hms, offset_minutes.unwrap_or(0))?,
+        nanos,
+        offset_minutes,
+    })
+}
+
+/// ### parse_sql_datetime(s: &str) -> Result<(u64, u64), WTimeError>
+///
+/// Parses an SQL `DATETIME`/`TIMESTAMP` text literal in UTC.
+///
+/// This function reads `YYYY-MM-DD HH:MM:SS` with an optional fraction of up to 9 digits,
+/// as printed by MySQL, PostgreSQL and SQLite, and is the inverse of
+/// [`crate::format::format_sql_datetime_with_precision`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_sql_datetime;
+///
+/// assert_eq!(parse_sql_datetime("2024-10-14 19:11:09"), Ok((1728933069, 0)));
+/// assert_eq!(
+///     parse_sql_datetime("2024-10-14 19:11:09.123456"),
+///     Ok((1728933069, 123_456_000))
+/// );
+/// assert!(parse_sql_datetime("2024-10-14T19:11:09").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an SQL datetime literal, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_sql_datetime(s: &str) -> Result<(u64, u64), WTimeError> {
+    const EXPECTED: &str = "`YYYY-MM-DD HH:MM:SS[.ffffff]`";
+
+    let (date, time) = s
+        .split_once(' ')
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let (time, nanos) = match time.split_once('.') {
+        Some((time, fraction)) => (time, parse_fraction(fraction, EXPECTED)?),
+        None => (time, 0),
+    };
+
+    let ts = civil_to_ts(parse_ymd(date, EXPECTED)?, parse_hms(time, EXPECTED)?, 0)?;
+    Ok((ts, nanos))
+}
+
+/// ### parse_sql_date(s: &str) -> Result<u64, WTimeError>
+///
+/// Parses an SQL `DATE` text literal, `YYYY-MM-DD`, as midnight UTC.
+///
+/// This is the inverse of [`crate::format::sql_date`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_sql_date;
+///
+/// assert_eq!(parse_sql_date("2024-10-14"), Ok(1728864000));
+/// assert!(parse_sql_date("2024-02-30").is_err());
+/// assert!(parse_sql_date("14/10/2024").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of midnight UTC on that date.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an SQL date literal, or
+/// [`WTimeError::OutOfRange`] if the date does not exist.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_sql_date(s: &str) -> Result<u64, WTimeError> {
+    civil_to_ts(parse_ymd(s, "`YYYY-MM-DD`")?, (0, 0, 0), 0)
+}
+
+/// ### parse_ymd_hms(s: &str) -> Result<DateTime, WTimeError>
+///
+/// Parses a `YYYY-MM-DD HH:MM:SS` date and time as UTC.
+///
+/// The date and time may be separated by a space or a `T`, and the seconds may carry a
+/// fraction of up to 9 digits. There is no zone, so the wall-clock time is read as UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_ymd_hms;
+///
+/// let dt = parse_ymd_hms("2024-10-14 19:11:09").unwrap();
+/// assert_eq!(dt.ts, 1728933069);
+///
+/// let dt = parse_ymd_hms("2024-10-14T19:11:09.5").unwrap();
+/// assert_eq!((dt.ts, dt.nanos), (1728933069, 500_000_000));
+///
+/// assert!(parse_ymd_hms("2024-02-30 00:00:00").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a UTC [`DateTime`].
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input does not match the layout, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_ymd_hms(s: &str) -> Result<DateTime, WTimeError> {
+    const EXPECTED: &str = "`YYYY-MM-DD HH:MM:SS[.fff]`";
+
+    let (date, time) = s
+        .split_once([' ', 'T'])
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let (time, nanos) = match time.split_once('.') {
+        Some((time, fraction)) => (time, parse_fraction(fraction, EXPECTED)?),
+        None => (time, 0),
+    };
+
+    let ts = civil_to_ts(parse_ymd(date, EXPECTED)?, parse_hms(time, EXPECTED)?, 0)?;
+    Ok(DateTime::new(ts, nanos, 0))
+}
+
+/// ### parse_utc_ts(s: &str) -> Result<DateTime, WTimeError>
+///
+/// Parses the `year-month-day-hour-minute-second-millis-nanos` layout produced by
+/// [`crate::utc::format_utc_ts`].
+///
+/// The last two fields are the milliseconds (3 digits) and the nanoseconds within the
+/// millisecond (6 digits), so `...-123-456789` is 0.123456789 seconds. Output of
+/// `format_local_ts` can be read too; its wall-clock time is then returned as if it were UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_utc_ts;
+///
+/// let dt = parse_utc_ts("2024-10-14-19-11-09-123-456789").unwrap();
+/// assert_eq!((dt.ts, dt.nanos), (1728933069, 123_456_789));
+///
+/// assert!(parse_utc_ts("2024-10-14 19:11:09").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a UTC [`DateTime`].
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input does not match the layout, or
+/// [`WTimeError::OutOfRange`] if a component is invalid.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_utc_ts(s: &str) -> Result<DateTime, WTimeError> {
+    const EXPECTED: &str = "`YYYY-MM-DD-HH-MM-SS-mmm-nnnnnn`";
+
+    let fields: Vec<&str> = s.split('-').collect();
+    let [year, month, day, hour, minute, second, millis, nanos] = fields[..] else {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    };
+    if year.len() != 4 || millis.len() != 3 || nanos.len() != 6 {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+
+    let ymd = (
+        parse_digits(year, EXPECTED)? as i64,
+        parse_two_digits(month, EXPECTED)?,
+        parse_two_digits(day, EXPECTED)?,
+    );
+    let hms = (
+        parse_two_digits(hour, EXPECTED)?,
+        parse_two_digits(minute, EXPECTED)?,
+        parse_two_digits(second, EXPECTED)?,
+    );
+    let nanos = parse_digits(millis, EXPECTED)? * 1_000_000 + parse_digits(nanos, EXPECTED)?;
+
+    Ok(DateTime::new(civil_to_ts(ymd, hms, 0)?, nanos, 0))
+}
+
+/// Parses an ISO 8601 date or date-time used as an interval endpoint: `YYYY-MM-DD`,
+/// optionally followed by `THH:MM[:SS[.fff]]` and a `Z`/`+HH:MM`/`+HHMM` zone (UTC if absent).
+fn parse_iso_instant(s: &str, expected: &'static str) -> Result<u64, WTimeError> {
+    let (date, time) = match s.split_once(['T', 't']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (s, None),
+    };
+    let ymd = parse_ymd(date, expected)?;
+    let Some(time) = time else {
+        return civil_to_ts(ymd, (0, 0, 0), 0);
+    };
+
+    let zone_at = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+    let (time, zone) = time.split_at(zone_at);
+    let offset = match zone.len() {
+        0 => 0,
+        5 => parse_offset_hhmm(zone, expected)?,
+        _ => parse_offset_rfc3339(zone, expected)?,
+    };
+    // Sub-second precision is dropped, endpoints are whole seconds
+    let time = time.split_once(['.', ',']).map_or(time, |(time, _)| time);
+    let hms = match time.len() {
+        5 => {
+            let (hour, minute) = time
+                .split_once(':')
+                .ok_or(WTimeError::InvalidFormat(expected))?;
+            (
+                parse_two_digits(hour, expected)?,
+                parse_two_digits(minute, expected)?,
+                0,
+            )
+        }
+        _ => parse_hms(time, expected)?,
+    };
+    civil_to_ts(ymd, hms, offset)
+}
+
+/// ### parse_iso_interval(s: &str) -> Result<TimeRange, WTimeError>
+///
+/// Parses an ISO 8601 time interval into a [`TimeRange`].
+///
+/// All three forms with a definite start and end are supported:
+/// - `start/end`, e.g. `2024-10-14T00:00Z/2024-10-15T00:00Z`
+/// - `start/duration`, e.g. `2024-10-14T00:00Z/P1D`
+/// - `duration/end`, e.g. `PT1H/2024-10-14T12:00:00Z`
+///
+/// Endpoints are `YYYY-MM-DD` dates, optionally followed by `THH:MM` or `THH:MM:SS` (a
+/// fraction is accepted and dropped) and a zone; endpoints without a zone are read as UTC.
+/// Durations use the `PnYnMnDTnHnMnS` or `PnW` form. Years and months are calendar
+/// units: `2024-01-31T00:00Z/P1M` ends on February 29.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::parse::parse_iso_interval;
+///
+/// let day = parse_iso_interval("2024-10-14T00:00Z/P1D").unwrap();
+/// assert_eq!((day.start(), day.end()), (1728864000, 1728950400));
+///
+/// assert_eq!(
+///     parse_iso_interval("2024-10-14T00:00Z/2024-10-15T00:00Z"),
+///     Ok(day)
+/// );
+/// assert_eq!(parse_iso_interval("P1D/2024-10-15"), Ok(day));
+/// assert!(parse_iso_interval("2024-10-15/2024-10-14").is_err());
+/// assert!(parse_iso_interval("P1D/P2D").is_err());
+///
+/// // Durations too long for any timestamp
+/// assert!(parse_iso_interval("2024-10-14/P9999999999999999999D").is_err());
+/// assert!(parse_iso_interval("2024-10-14/P9999999999999999999Y").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the [`TimeRange`] covered by the interval.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is not an interval, or
+/// [`WTimeError::OutOfRange`] if a component is invalid, the duration or the resulting
+/// timestamp does not fit, or the end precedes the start.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_iso_interval(s: &str) -> Result<TimeRange, WTimeError> {
+    const EXPECTED: &str = "`start/end`, `start/duration` or `duration/end`";
+
+    let (first, second) = s
+        .trim()
+        .split_once('/')
+        .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+    let is_duration = |part: &str| part.starts_with(['P', 'p']);
+
+    match (is_duration(first), is_duration(second)) {
+        (false, false) => TimeRange::new(
+            parse_iso_instant(first, EXPECTED)?,
+            parse_iso_instant(second, EXPECTED)?,
+        ),
+        (false, true) => {
+            let start = parse_iso_instant(first, EXPECTED)?;
+            TimeRange::new(start, IsoDuration::parse(second)?.shift(start, true)?)
+        }
+        (true, false) => {
+            let end = parse_iso_instant(second, EXPECTED)?;
+            TimeRange::new(IsoDuration::parse(first)?.shift(end, false)?, end)
+        }
+        (true, true) => Err(WTimeError::InvalidFormat(EXPECTED)),
+    }
+}
+
+/// ### parse_date_math(expr: &str, now: u64, offset_minutes: i64) -> Result<u64, WTimeError>
+///
+/// Evaluates an Elasticsearch-style date math expression.
+///
+/// An expression starts with an anchor, either `now` or a date followed by `||`
+/// (`2024-10-14||`, `2024-10-14T12:00:00Z||`), and continues with any number of operations
+/// applied from left to right:
+/// - `+<n><unit>` / `-<n><unit>` adds or subtracts `n` units
+/// - `/<unit>` rounds down to the start of the unit
+///
+/// Units are `y` (years), `M` (months), `w` (weeks), `d` (days), `h`/`H` (hours), `m`
+/// (minutes) and `s` (seconds). Years and months are calendar units with end-of-month
+/// clamping. Calendar arithmetic and rounding happen in the wall-clock time of
+/// `offset_minutes`, like the `time_zone` parameter of Elasticsearch, and weeks start on
+/// Monday. Anchor dates without a zone are read as UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::error::WTimeError;
+/// use wtime::parse::parse_date_math;
+///
+/// let now = 1728933069; // 2024-10-14 19:11:09 UTC
+///
+/// assert_eq!(parse_date_math("now", now, 0), Ok(now));
+/// assert_eq!(parse_date_math("now-1h/d", now, 0), Ok(1728864000));
+/// assert_eq!(parse_date_math("now+2w", now, 0), Ok(now + 14 * 86_400));
+/// assert_eq!(parse_date_math("2024-10-14||+1M/d", now, 0), Ok(1731542400));
+/// assert_eq!(parse_date_math("2024-01-31||+1M", now, 0), Ok(1709164800)); // 2024-02-29
+///
+/// // Rounding to the day in UTC+02:00 gives local midnight
+/// assert_eq!(parse_date_math("now/d", now, 120), Ok(1728856800));
+///
+/// assert!(parse_date_math("now+1x", now, 0).is_err());
+/// let far = format!("now{}", "+2147483647y".repeat(400));
+/// assert_eq!(parse_date_math(&far, now, 0), Err(WTimeError::OutOfRange("timestamp")));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the resulting UNIX timestamp in seconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the expression is malformed, or
+/// [`WTimeError::OutOfRange`] if the result lies before the UNIX epoch, if any step moves more
+/// than [`MAX_TS`] seconds away from it, or if the offset is 24 hours or more either way.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_date_math(expr: &str, now: u64, offset_minutes: i64) -> Result<u64, WTimeError> {
+    const EXPECTED: &str = "a date math expression such as `now-1h/d` or `2024-10-14||+1M`";
+
+    let expr = expr.trim();
+    let (anchor, mut ops) = match expr.strip_prefix("now") {
+        Some(ops) => (now, ops),
+        None => {
+            let (date, ops) = expr
+                .split_once("||")
+                .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+            (parse_iso_instant(date, EXPECTED)?, ops)
+        }
+    };
+
+    if offset_minutes.unsigned_abs() >= 24 * 60 {
+        return Err(WTimeError::OutOfRange("offset"));
+    }
+    // Keeping the wall-clock time within MAX_TS of the epoch after every step, and each
+    // amount below 2^31 units, leaves the calendar math of the next step far from overflow
+    let in_range = |wall: i64| {
+        if wall.unsigned_abs() <= MAX_TS {
+            Ok(wall)
+        } else {
+            Err(WTimeError::OutOfRange("timestamp"))
+        }
+    };
+    let offset = offset_minutes * 60;
+    let mut wall = in_range(i64::try_from(anchor).unwrap_or(i64::MAX))? + offset;
+    while let Some(op) = ops.chars().next() {
+        let rest = &ops[op.len_utf8()..];
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let unit = rest[digits..]
+            .chars()
+            .next()
+            .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+        ops = &rest[digits + unit.len_utf8()..];
+
+        let days = wall.div_euclid(86_400);
+        let secs_of_day = wall.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+
+        wall = in_range(match op {
+            '+' | '-' => {
+                if digits == 0 {
+                    return Err(WTimeError::InvalidFormat(EXPECTED));
+                }
+                let amount = parse_digits(&rest[..digits], EXPECTED)?;
+                if amount > i32::MAX as u64 {
+                    return Err(WTimeError::OutOfRange("amount"));
+                }
+                let amount = amount as i64;
+                let amount = if op == '-' { -amount } else { amount };
+                let seconds = match unit {
+                    'y' | 'M' => {
+                        let months = if unit == 'y' { amount * 12 } else { amount };
+                        let (year, month, day) = shift_months(year, month, day, months);
+                        wall = days_from_civil(year, month, day) * 86_400 + secs_of_day;
+                        0
+                    }
+                    'w' => amount * 7 * 86_400,
+                    'd' => amount * 86_400,
+                    'h' | 'H' => amount * 3600,
+                    'm' => amount * 60,
+                    's' => amount,
+                    _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+                };
+                wall + seconds
+            }
+            '/' if digits == 0 && unit == 's' => wall,
+            '/' if digits == 0 => {
+                let unit = match unit {
+                    'y' => Unit::Year,
+                    'M' => Unit::Month,
+                    'w' => Unit::Week,
+                    'd' => Unit::Day,
+                    'h' | 'H' => Unit::Hour,
+                    'm' => Unit::Minute,
+                    _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+                };
+                unit_bounds(wall, unit, Weekday::Monday).0
+            }
+            _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+        })?;
+    }
+
+    u64::try_from(wall - offset).map_err(|_| WTimeError::OutOfRange("timestamp"))
+}
+
+/// ### parse_prometheus_duration(s: &str) -> Result<Duration, WTimeError>
+///
+/// Parses a duration in the style of Prometheus and Grafana configuration files.
+///
+/// A duration is a sequence of `<integer><unit>` pairs such as `1h`, `90m`, `1d12h` or
+/// `2w`. The units are `y` (365 days), `w`, `d`, `h`, `m`, `s` and `ms`. As in Prometheus,
+/// each unit may appear at most once, the units must be ordered from largest to smallest,
+/// and a bare `0` is accepted. ISO 8601 durations (`PT1H`) are not accepted here.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::parse::parse_prometheus_duration;
+///
+/// assert_eq!(parse_prometheus_duration("90m"), Ok(Duration::from_secs(5_400)));
+/// assert_eq!(parse_prometheus_duration("1d12h"), Ok(Duration::from_secs(129_600)));
+/// assert_eq!(parse_prometheus_duration("2w"), Ok(Duration::from_secs(1_209_600)));
+/// assert_eq!(parse_prometheus_duration("1s500ms"), Ok(Duration::from_millis(1_500)));
+/// assert_eq!(parse_prometheus_duration("1y"), Ok(Duration::from_secs(31_536_000)));
+/// assert!(parse_prometheus_duration("30m1h").is_err());
+/// assert!(parse_prometheus_duration("1.5h").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the parsed `Duration`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is malformed, or
+/// [`WTimeError::OutOfRange`] if the duration overflows.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_prometheus_duration(s: &str) -> Result<Duration, WTimeError> {
+    const EXPECTED: &str = "a duration such as `1h`, `90m` or `1d12h`";
+    // Ordered from largest to smallest, `ms` before `m` so the longer suffix wins
+    const UNITS: [(&str, u64); 7] = [
+        ("y", 365 * 86_400_000),
+        ("w", 7 * 86_400_000),
+        ("d", 86_400_000),
+        ("h", 3_600_000),
+        ("ms", 1),
+        ("m", 60_000),
+        ("s", 1_000),
+    ];
+
+    if s == "0" {
+        return Ok(Duration::ZERO);
+    }
+    if s.is_empty() {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+
+    let mut rest = s;
+    let mut millis: u64 = 0;
+    let mut last_unit_millis = u64::MAX;
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let value = parse_digits(&rest[..digits], EXPECTED)?;
+        rest = &rest[digits..];
+
+        let (unit, unit_millis) = UNITS
+            .iter()
+            .find(|(unit, _)| rest.starts_with(unit))
+            .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+        if *unit_millis >= last_unit_millis {
+            return Err(WTimeError::InvalidFormat(EXPECTED));
+        }
+        last_unit_millis = *unit_millis;
+        rest = &rest[unit.len()..];
+
+        millis = value
+            .checked_mul(*unit_millis)
+            .and_then(|part| millis.checked_add(part))
+            .ok_or(WTimeError::OutOfRange("duration"))?;
+    }
+    Ok(Duration::from_millis(millis))
+}
+
+/// ### parse_duration(s: &str) -> Result<Duration, WTimeError>
+///
+/// Parses a duration written with unit suffixes, such as `1h30m` or `250ms`, or as an ISO
+/// 8601 duration such as `PT1H30M`.
+///
+/// This is the lenient parser for timeouts in configuration files and command-line
+/// arguments. A duration is one or more `<number><unit>` parts, optionally separated by
+/// spaces, in any order. Numbers may have a decimal fraction (`1.5h`). The units are `ns`,
+/// `us` (or `µs`), `ms`, `s`, `m`, `h`, `d` and `w`; days and weeks are exact multiples of
+/// 24 hours. A bare `0` is accepted. ISO 8601 durations may use weeks, days, hours, minutes
+/// and seconds, but not years or months, whose length is not fixed.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::parse::parse_duration;
+///
+/// assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5_400)));
+/// assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+/// assert_eq!(parse_duration("1.5h"), Ok(Duration::from_secs(5_400)));
+/// assert_eq!(parse_duration("2m 30s"), Ok(Duration::from_secs(150)));
+/// assert_eq!(parse_duration("PT1H30M"), Ok(Duration::from_secs(5_400)));
+/// assert_eq!(parse_duration("P1DT0.5S"), Ok(Duration::from_millis(86_400_500)));
+/// assert!(parse_duration("P1M").is_err());
+/// assert!(parse_duration("P1D2D").is_err());
+/// assert!(parse_duration("PT1S1H").is_err());
+/// assert!(parse_duration("90").is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the parsed `Duration`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] if the input is malformed, or
+/// [`WTimeError::OutOfRange`] if the duration overflows.
+///
+/// <small>End Fun Doc</small>
+pub fn parse_duration(s: &str) -> Result<Duration, WTimeError> {
+    const EXPECTED: &str = "a duration such as `1h30m`, `250ms` or `PT1H30M`";
+    // Two-letter suffixes before `m` and `s` so the longer suffix wins
+    const UNITS: [(&str, u128); 9] = [
+        ("ns", 1),
+        ("us", 1_000),
+        ("µs", 1_000),
+        ("ms", 1_000_000),
+        ("s", 1_000_000_000),
+        ("m", 60_000_000_000),
+        ("h", 3_600_000_000_000),
+        ("d", 86_400_000_000_000),
+        ("w", 7 * 86_400_000_000_000),
+    ];
+
+    let s = s.trim();
+    if s.starts_with(['P', 'p']) {
+        return IsoDuration::parse(s)?.exact();
+    }
+    if s == "0" {
+        return Ok(Duration::ZERO);
+    }
+    if s.is_empty() {
+        return Err(WTimeError::InvalidFormat(EXPECTED));
+    }
+
+    let mut rest = s;
+    let mut nanos: u128 = 0;
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let whole = parse_digits(&rest[..digits], EXPECTED)?;
+        rest = &rest[digits..];
+        let fraction = match rest.strip_prefix('.') {
+            Some(after_dot) => {
+                let digits = after_dot.bytes().take_while(u8::is_ascii_digit).count();
+                if digits == 0 {
+                    return Err(WTimeError::InvalidFormat(EXPECTED));
+                }
+                rest = &after_dot[digits..];
+                &after_dot[..digits]
+            }
+            None => "",
+        };
+
+        let (unit, unit_nanos) = UNITS
+            .iter()
+            .find(|(unit, _)| rest.starts_with(unit))
+            .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+        rest = rest[unit.len()..].trim_start();
+
+        // Digits past the precision of the unit are truncated
+        let mut part = u128::from(whole) * unit_nanos;
+        let mut scale = *unit_nanos;
+        for digit in fraction.bytes() {
+            scale /= 10;
+            part += u128::from(digit - b'0') * scale;
+        }
+        nanos = nanos
+            .checked_add(part)
+            .ok_or(WTimeError::OutOfRange("duration"))?;
+    }
+
+    let secs =
+        u64::try_from(nanos / 1_000_000_000).map_err(|_| WTimeError::OutOfRange("duration"))?;
+    Ok(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+}
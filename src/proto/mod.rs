@@ -0,0 +1,274 @@
+use super::{
+    clock::{since_epoch, Clock, SystemClock},
+    datetime::DateTime,
+    error::WTimeError,
+};
+use std::time::Duration;
+
+/// Seconds of 9999-12-31T23:59:59Z, the latest valid `google.protobuf.Timestamp`.
+const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+/// About 10,000 years, the largest valid `google.protobuf.Duration`.
+const MAX_DURATION_SECONDS: i64 = 315_576_000_000;
+
+/// ### to_timestamp(ts: u64, nanos: u64) -> Result<prost_types::Timestamp, WTimeError>
+///
+/// Converts a UNIX timestamp and its sub-second part into a `google.protobuf.Timestamp`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::proto::to_timestamp;
+///
+/// let timestamp = to_timestamp(1728933069, 250_000_000).unwrap();
+/// assert_eq!((timestamp.seconds, timestamp.nanos), (1728933069, 250_000_000));
+/// assert!(to_timestamp(253_402_300_800, 0).is_err()); // year 10000
+/// ```
+///
+/// ### Returns
+///
+/// Returns the `prost_types::Timestamp` message.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `nanos` is not below one second or `ts` is after
+/// 9999-12-31T23:59:59Z, the range the protobuf specification allows.
+///
+/// <small>End Fun Doc</small>
+pub fn to_timestamp(ts: u64, nanos: u64) -> Result<prost_types::Timestamp, WTimeError> {
+    if nanos >= 1_000_000_000 {
+        return Err(WTimeError::OutOfRange("nanos"));
+    }
+    if ts > MAX_TIMESTAMP_SECONDS as u64 {
+        return Err(WTimeError::OutOfRange("seconds"));
+    }
+    Ok(prost_types::Timestamp {
+        seconds: ts as i64,
+        nanos: nanos as i32,
+    })
+}
+
+/// ### from_timestamp(timestamp: &prost_types::Timestamp) -> Result<(u64, u64), WTimeError>
+///
+/// Converts a `google.protobuf.Timestamp` into a UNIX timestamp and its sub-second part.
+///
+/// The message is validated against the protobuf specification. Valid timestamps before the
+/// UNIX epoch are rejected as well, because they cannot be represented as a `u64`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::proto::from_timestamp;
+///
+/// let timestamp = prost_types::Timestamp { seconds: 1728933069, nanos: 250_000_000 };
+/// assert_eq!(from_timestamp(&timestamp), Ok((1728933069, 250_000_000)));
+///
+/// let invalid = prost_types::Timestamp { seconds: 1728933069, nanos: -1 };
+/// assert!(from_timestamp(&invalid).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp in seconds and the sub-second part in nanoseconds.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `nanos` is outside 0-999,999,999 or `seconds` is
+/// outside 1970-01-01T00:00:00Z to 9999-12-31T23:59:59Z.
+///
+/// <small>End Fun Doc</small>
+pub fn from_timestamp(timestamp: &prost_types::Timestamp) -> Result<(u64, u64), WTimeError> {
+    if !(0..1_000_000_000).contains(&timestamp.nanos) {
+        return Err(WTimeError::OutOfRange("nanos"));
+    }
+    // The specification allows timestamps back to year 1, a `u64` only from 1970
+    if !(0..=MAX_TIMESTAMP_SECONDS).contains(&timestamp.seconds) {
+        return Err(WTimeError::OutOfRange("seconds"));
+    }
+    Ok((timestamp.seconds as u64, timestamp.nanos as u64))
+}
+
+/// ### to_duration(duration: Duration) -> Result<prost_types::Duration, WTimeError>
+///
+/// Converts a `Duration` into a `google.protobuf.Duration`.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::proto::to_duration;
+///
+/// let duration = to_duration(Duration::from_millis(1_500)).unwrap();
+/// assert_eq!((duration.seconds, duration.nanos), (1, 500_000_000));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the `prost_types::Duration` message.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the duration exceeds the roughly 10,000 years the
+/// protobuf specification allows.
+///
+/// <small>End Fun Doc</small>
+pub fn to_duration(duration: Duration) -> Result<prost_types::Duration, WTimeError> {
+    if duration.as_secs() > MAX_DURATION_SECONDS as u64 {
+        return Err(WTimeError::OutOfRange("seconds"));
+    }
+    Ok(prost_types::Duration {
+        seconds: duration.as_secs() as i64,
+        nanos: duration.subsec_nanos() as i32,
+    })
+}
+
+/// ### from_duration(duration: &prost_types::Duration) -> Result<Duration, WTimeError>
+///
+/// Converts a `google.protobuf.Duration` into a `Duration`.
+///
+/// The message is validated against the protobuf specification. Valid negative durations
+/// are rejected as well, because a `Duration` cannot be negative.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::proto::from_duration;
+///
+/// let duration = prost_types::Duration { seconds: 1, nanos: 500_000_000 };
+/// assert_eq!(from_duration(&duration), Ok(Duration::from_millis(1_500)));
+///
+/// let negative = prost_types::Duration { seconds: -1, nanos: 0 };
+/// assert!(from_duration(&negative).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the corresponding `Duration`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the message is invalid or negative.
+///
+/// <small>End Fun Doc</small>
+pub fn from_duration(duration: &prost_types::Duration) -> Result<Duration, WTimeError> {
+    if !(0..1_000_000_000).contains(&duration.nanos) {
+        return Err(WTimeError::OutOfRange("nanos"));
+    }
+    if !(0..=MAX_DURATION_SECONDS).contains(&duration.seconds) {
+        return Err(WTimeError::OutOfRange("seconds"));
+    }
+    Ok(Duration::new(
+        duration.seconds as u64,
+        duration.nanos as u32,
+    ))
+}
+
+/// ### now_timestamp() -> prost_types::Timestamp
+///
+/// Reads the system clock as a `google.protobuf.Timestamp`, the usual value of `created_at`
+/// and `update_time` fields in gRPC messages.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::proto::{from_timestamp, now_timestamp};
+/// use wtime::utc::utc_ts_sec;
+///
+/// let now = now_timestamp();
+/// assert!(now.seconds as u64 <= utc_ts_sec());
+/// assert!(from_timestamp(&now).is_ok());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current time as a `prost_types::Timestamp`.
+///
+/// <small>End Fun Doc</small>
+pub fn now_timestamp() -> prost_types::Timestamp {
+    now_timestamp_with_clock(&SystemClock)
+}
+
+/// ### now_timestamp_with_clock<C: Clock>(clock: &C) -> prost_types::Timestamp
+///
+/// Reads a [`Clock`] as a `google.protobuf.Timestamp`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::proto::now_timestamp_with_clock;
+///
+/// let timestamp = now_timestamp_with_clock(&FixedClock::from_ts(1728933069));
+/// assert_eq!((timestamp.seconds, timestamp.nanos), (1728933069, 0));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the time of the clock, or the UNIX epoch for a clock set before 1970. A clock set
+/// after the year 9999 is clamped to the end of that year.
+///
+/// <small>End Fun Doc</small>
+pub fn now_timestamp_with_clock<C: Clock>(clock: &C) -> prost_types::Timestamp {
+    let since = since_epoch(clock);
+    match to_timestamp(since.as_secs(), u64::from(since.subsec_nanos())) {
+        Ok(timestamp) => timestamp,
+        Err(_) => prost_types::Timestamp {
+            seconds: MAX_TIMESTAMP_SECONDS,
+            nanos: 999_999_999,
+        },
+    }
+}
+
+/// Converts to a `google.protobuf.Timestamp`, dropping the offset.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 123_000_000, 120);
+/// let timestamp = prost_types::Timestamp::try_from(dt).unwrap();
+/// assert_eq!((timestamp.seconds, timestamp.nanos), (1728933069, 123_000_000));
+///
+/// assert!(prost_types::Timestamp::try_from(DateTime::from_ts(253_402_300_800)).is_err());
+/// ```
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for instants after 9999-12-31T23:59:59Z, the range the
+/// protobuf specification allows.
+impl TryFrom<DateTime> for prost_types::Timestamp {
+    type Error = WTimeError;
+
+    fn try_from(dt: DateTime) -> Result<Self, WTimeError> {
+        to_timestamp(dt.ts, dt.nanos)
+    }
+}
+
+/// Converts from a `google.protobuf.Timestamp` into a UTC date-time.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::datetime::DateTime;
+///
+/// let timestamp = prost_types::Timestamp { seconds: 1728933069, nanos: 123_000_000 };
+/// let dt = DateTime::try_from(timestamp).unwrap();
+/// assert_eq!(dt.to_string(), "2024-10-14T19:11:09.123Z");
+///
+/// let before_1970 = prost_types::Timestamp { seconds: -1, nanos: 0 };
+/// assert!(DateTime::try_from(before_1970).is_err());
+/// ```
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for an invalid message or an instant before the UNIX
+/// epoch, as [`from_timestamp`] does.
+impl TryFrom<prost_types::Timestamp> for DateTime {
+    type Error = WTimeError;
+
+    fn try_from(timestamp: prost_types::Timestamp) -> Result<Self, WTimeError> {
+        let (ts, nanos) = from_timestamp(&timestamp)?;
+        Ok(DateTime::new(ts, nanos, 0))
+    }
+}
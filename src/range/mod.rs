@@ -0,0 +1,212 @@
+use super::{
+    calc::{
+        civil_from_days, clamp_offset, days_from_civil, shift_months, unit_bounds, Unit, Weekday,
+        MAX_TS,
+    },
+    config,
+    error::WTimeError,
+};
+use std::time::Duration;
+
+/// ### TimeRange
+///
+/// A half-open span of time `[start, end)` between two UNIX timestamps in seconds.
+///
+/// The start is included and the end excluded, so adjacent ranges such as consecutive days
+/// share a boundary without overlapping. A range is never inverted: `start <= end` always
+/// holds.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::range::TimeRange;
+///
+/// let day = TimeRange::new(1728864000, 1728950400).unwrap();
+/// assert!(day.contains(1728933069));
+/// assert!(!day.contains(1728950400));
+/// assert_eq!(day.duration(), Duration::from_secs(86_400));
+/// assert!(TimeRange::new(10, 5).is_err());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeRange {
+    start: u64,
+    end: u64,
+}
+
+impl TimeRange {
+    /// Creates a range from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// Returns [`WTimeError::OutOfRange`] if `end` is before `start`.
+    pub fn new(start: u64, end: u64) -> Result<Self, WTimeError> {
+        if end < start {
+            return Err(WTimeError::OutOfRange("end"));
+        }
+        Ok(TimeRange { start, end })
+    }
+
+    /// Returns the first second included in the range.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the first second after the range.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Returns the length of the range.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.end - self.start)
+    }
+
+    /// Returns `true` if `ts` lies within the range.
+    pub fn contains(&self, ts: u64) -> bool {
+        self.start <= ts && ts < self.end
+    }
+}
+
+/// Converts local wall-clock seconds back into a UNIX timestamp.
+fn wall_to_ts(wall: i64, offset_minutes: i64) -> Result<u64, WTimeError> {
+    u64::try_from(wall - offset_minutes * 60).map_err(|_| WTimeError::OutOfRange("timestamp"))
+}
+
+/// Builds a range between two local wall-clock instants.
+fn wall_range(start: i64, end: i64, offset_minutes: i64) -> Result<TimeRange, WTimeError> {
+    TimeRange::new(
+        wall_to_ts(start, offset_minutes)?,
+        wall_to_ts(end, offset_minutes)?,
+    )
+}
+
+/// Builds the range of `count` calendar months starting at the local month `(year, month)`.
+fn month_range(
+    (year, month): (i64, u64),
+    count: i64,
+    offset_minutes: i64,
+) -> Result<TimeRange, WTimeError> {
+    let (end_year, end_month, _) = shift_months(year, month, 1, count);
+    wall_range(
+        days_from_civil(year, month, 1) * 86_400,
+        days_from_civil(end_year, end_month, 1) * 86_400,
+        offset_minutes,
+    )
+}
+
+/// ### resolve_range(name: &str, now: u64, offset_minutes: i64) -> Result<TimeRange, WTimeError>
+///
+/// Resolves a named relative range, such as `"last_7_days"` or `"this_month"`, into a
+/// [`TimeRange`].
+///
+/// Dashboards and reports tend to re-implement these presets with subtly different edges.
+/// This function gives them one definition. Calendar presets are aligned to local midnight
+/// in the given UTC offset, which saturates below 24 hours, and cover the whole period,
+/// including the part after `now`:
+///
+/// | Name | Range |
+/// | :--- | :--- |
+/// | `today`, `yesterday` | the local calendar day |
+/// | `this_week`, `previous_week` | the week, starting on the configured [`WeekStart`](crate::config::WeekStart) (Monday by default) |
+/// | `this_month`, `previous_month` | the calendar month |
+/// | `this_quarter`, `previous_quarter` | January-March, April-June, July-September or October-December |
+/// | `this_year`, `previous_year` | the calendar year |
+///
+/// Rolling presets named `last_<n>_<unit>` end at `now` and reach back `n` units, where the
+/// unit is `minutes`, `hours`, `days` or `weeks` (singular forms are accepted too). So
+/// `last_7_days` is `now - 7 * 24h .. now`. `last_hour`, `last_day` and `last_week`
+/// are shorthands for `n = 1`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::range::resolve_range;
+///
+/// let now = 1728933069; // Monday, 2024-10-14 19:11:09 UTC
+///
+/// let today = resolve_range("today", now, 0).unwrap();
+/// assert_eq!((today.start(), today.end()), (1728864000, 1728950400));
+///
+/// let week = resolve_range("last_7_days", now, 0).unwrap();
+/// assert_eq!((week.start(), week.end()), (now - 7 * 86_400, now));
+///
+/// let month = resolve_range("this_month", now, 0).unwrap();
+/// assert_eq!((month.start(), month.end()), (1727740800, 1730419200));
+///
+/// let quarter = resolve_range("previous_quarter", now, 0).unwrap();
+/// assert_eq!((quarter.start(), quarter.end()), (1719792000, 1727740800));
+///
+/// // In UTC+05:00 it is already Tuesday
+/// let today = resolve_range("today", now, 300).unwrap();
+/// assert_eq!(today.start(), 1728932400);
+///
+/// assert!(resolve_range("fortnight", now, 0).is_err());
+/// assert_eq!(resolve_range("today", now, i64::MAX), resolve_range("today", now, 1439));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the [`TimeRange`] described by `name`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::InvalidFormat`] for an unknown name, or
+/// [`WTimeError::OutOfRange`] if the range would start before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn resolve_range(name: &str, now: u64, offset_minutes: i64) -> Result<TimeRange, WTimeError> {
+    const EXPECTED: &str = "a range preset such as `today`, `this_month` or `last_7_days`";
+
+    let offset_minutes = clamp_offset(offset_minutes);
+    let wall = now.min(MAX_TS) as i64 + offset_minutes * 60;
+    let (year, month, _) = civil_from_days(wall.div_euclid(86_400));
+    let (today, tomorrow) = unit_bounds(wall, Unit::Day, Weekday::Monday);
+    let (week, next_week) = unit_bounds(wall, Unit::Week, config::get().week_start.into());
+    let quarter_month = (month - 1) / 3 * 3 + 1;
+
+    match name {
+        "today" => wall_range(today, tomorrow, offset_minutes),
+        "yesterday" => wall_range(today - 86_400, today, offset_minutes),
+        "this_week" => wall_range(week, next_week, offset_minutes),
+        "previous_week" => wall_range(week - 7 * 86_400, week, offset_minutes),
+        "this_month" => month_range((year, month), 1, offset_minutes),
+        "previous_month" => {
+            let (year, month, _) = shift_months(year, month, 1, -1);
+            month_range((year, month), 1, offset_minutes)
+        }
+        "this_quarter" => month_range((year, quarter_month), 3, offset_minutes),
+        "previous_quarter" => {
+            let (year, month, _) = shift_months(year, quarter_month, 1, -3);
+            month_range((year, month), 3, offset_minutes)
+        }
+        "this_year" => month_range((year, 1), 12, offset_minutes),
+        "previous_year" => month_range((year - 1, 1), 12, offset_minutes),
+        _ => {
+            let rolling = name
+                .strip_prefix("last_")
+                .ok_or(WTimeError::InvalidFormat(EXPECTED))?;
+            let (count, unit) = match rolling.split_once('_') {
+                Some((count, unit)) => (
+                    count
+                        .parse::<u64>()
+                        .map_err(|_| WTimeError::InvalidFormat(EXPECTED))?,
+                    unit,
+                ),
+                None => (1, rolling),
+            };
+            let unit_secs = match unit.strip_suffix('s').unwrap_or(unit) {
+                "minute" => 60,
+                "hour" => 3600,
+                "day" => 86_400,
+                "week" => 7 * 86_400,
+                _ => return Err(WTimeError::InvalidFormat(EXPECTED)),
+            };
+            let start = count
+                .checked_mul(unit_secs)
+                .and_then(|span| now.checked_sub(span))
+                .ok_or(WTimeError::OutOfRange("timestamp"))?;
+            TimeRange::new(start, now)
+        }
+    }
+}
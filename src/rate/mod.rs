@@ -0,0 +1,209 @@
+use super::clock::{since_epoch, Clock, SystemClock};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// ### TokenBucket
+///
+/// A token-bucket rate limiter: requests are allowed at a steady rate, with bursts of up to a
+/// given size.
+///
+/// The bucket holds up to `burst` tokens and starts full. Each allowed request takes a
+/// token, and tokens flow back in at `rate` per second. The time is read from the system
+/// clock, which the `testing` module can freeze, or from any [`Clock`] passed to the
+/// `_with_clock` methods. If the clock goes back, no tokens are added until it catches up.
+///
+/// The bucket is not shared between threads by itself; wrap it in a `Mutex` to throttle
+/// several threads together.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::FixedClock;
+/// use wtime::rate::TokenBucket;
+///
+/// let mut limiter = TokenBucket::new(10.0, 5);
+/// assert!(limiter.try_acquire());
+///
+/// // Two requests per second, in bursts of up to three
+/// let mut bucket = TokenBucket::new(2.0, 3);
+/// let mut clock = FixedClock::from_ts(1728933069);
+/// assert!(bucket.try_acquire_n_with_clock(3, &clock));
+/// assert!(!bucket.try_acquire_with_clock(&clock));
+///
+/// clock.advance(Duration::from_millis(500));
+/// assert!(bucket.try_acquire_with_clock(&clock));
+/// assert!(!bucket.try_acquire_with_clock(&clock));
+///
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(bucket.available_with_clock(&clock), 3);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    /// When tokens were last added, or `None` before the first request.
+    last: Option<Duration>,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket refilled at `rate` tokens per second and holding at most
+    /// `burst` tokens.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        TokenBucket {
+            rate,
+            burst: f64::from(burst),
+            tokens: f64::from(burst),
+            last: None,
+        }
+    }
+
+    /// Takes a token if one is available, reading the system clock.
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_n_with_clock(1, &SystemClock)
+    }
+
+    /// Takes a token if one is available, reading the time from `clock`.
+    pub fn try_acquire_with_clock<C: Clock>(&mut self, clock: &C) -> bool {
+        self.try_acquire_n_with_clock(1, clock)
+    }
+
+    /// Takes `n` tokens if that many are available, reading the system clock. Either all
+    /// `n` are taken or none.
+    pub fn try_acquire_n(&mut self, n: u32) -> bool {
+        self.try_acquire_n_with_clock(n, &SystemClock)
+    }
+
+    /// Takes `n` tokens if that many are available, reading the time from `clock`.
+    pub fn try_acquire_n_with_clock<C: Clock>(&mut self, n: u32, clock: &C) -> bool {
+        self.refill(since_epoch(clock));
+        if self.tokens >= f64::from(n) {
+            self.tokens -= f64::from(n);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the number of whole tokens available, reading the system clock.
+    pub fn available(&mut self) -> u32 {
+        self.available_with_clock(&SystemClock)
+    }
+
+    /// Returns the number of whole tokens available, reading the time from `clock`.
+    pub fn available_with_clock<C: Clock>(&mut self, clock: &C) -> u32 {
+        self.refill(since_epoch(clock));
+        self.tokens as u32
+    }
+
+    /// Adds the tokens earned since the last refill, up to `burst`.
+    fn refill(&mut self, now: Duration) {
+        let last = *self.last.get_or_insert(now);
+        if now > last {
+            let earned = (now - last).as_secs_f64() * self.rate;
+            self.tokens = (self.tokens + earned).min(self.burst);
+            self.last = Some(now);
+        }
+    }
+}
+
+/// ### WindowCounter
+///
+/// Counts the events of the last `window` of time, such as requests in the last minute or
+/// frames in the last second.
+///
+/// Each [`record`](WindowCounter::record) notes the time of one event, and events older than
+/// the window are forgotten as time passes. The counter keeps one entry per event within the
+/// window, so it suits rates of up to some thousands of events per window. Like
+/// [`TokenBucket`], it reads the system clock or any [`Clock`] passed to the `_with_clock`
+/// methods.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::FixedClock;
+/// use wtime::rate::WindowCounter;
+///
+/// let mut frames = WindowCounter::new(Duration::from_secs(1));
+/// frames.record();
+/// assert_eq!(frames.count(), 1);
+///
+/// let mut requests = WindowCounter::new(Duration::from_secs(10));
+/// let mut clock = FixedClock::from_ts(1728933069);
+/// for _ in 0..30 {
+///     requests.record_with_clock(&clock);
+///     clock.advance(Duration::from_millis(500));
+/// }
+/// // The events of the last 10 seconds, one every half second: an event exactly 10
+/// // seconds old has left the window
+/// assert_eq!(requests.count_with_clock(&clock), 19);
+/// assert_eq!(requests.per_second_with_clock(&clock), 1.9);
+///
+/// clock.advance(Duration::from_secs(10));
+/// assert_eq!(requests.count_with_clock(&clock), 0);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WindowCounter {
+    window: Duration,
+    /// The times of the events still in the window, oldest first.
+    events: VecDeque<Duration>,
+}
+
+impl WindowCounter {
+    /// Creates a counter over the last `window` of time.
+    pub fn new(window: Duration) -> Self {
+        WindowCounter {
+            window,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Records an event now, reading the system clock.
+    pub fn record(&mut self) {
+        self.record_with_clock(&SystemClock);
+    }
+
+    /// Records an event at the time of `clock`.
+    pub fn record_with_clock<C: Clock>(&mut self, clock: &C) {
+        let now = since_epoch(clock);
+        self.expire(now);
+        self.events.push_back(now);
+    }
+
+    /// Returns the number of events within the window, reading the system clock.
+    pub fn count(&mut self) -> usize {
+        self.count_with_clock(&SystemClock)
+    }
+
+    /// Returns the number of events within the window ending at the time of `clock`.
+    pub fn count_with_clock<C: Clock>(&mut self, clock: &C) -> usize {
+        self.expire(since_epoch(clock));
+        self.events.len()
+    }
+
+    /// Returns the average number of events per second over the window, reading the system
+    /// clock.
+    pub fn per_second(&mut self) -> f64 {
+        self.per_second_with_clock(&SystemClock)
+    }
+
+    /// Returns the average number of events per second over the window ending at the time
+    /// of `clock`.
+    pub fn per_second_with_clock<C: Clock>(&mut self, clock: &C) -> f64 {
+        self.count_with_clock(clock) as f64 / self.window.as_secs_f64()
+    }
+
+    /// Forgets the events that are at least `window` older than `now`.
+    fn expire(&mut self, now: Duration) {
+        let start = now.saturating_sub(self.window);
+        while self.events.front().is_some_and(|&event| event <= start) {
+            self.events.pop_front();
+        }
+    }
+}
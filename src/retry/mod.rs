@@ -0,0 +1,144 @@
+use super::calc::duration_since;
+use std::time::Duration;
+
+/// ### Jitter
+///
+/// The randomness a [`Backoff`] adds to its waits.
+///
+/// Clients that fail together and retry on the same schedule keep colliding. Jitter spreads
+/// their retries out; `Full` spreads them the most, `Equal` keeps every wait at least half
+/// of the exponential delay.
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Jitter {
+    /// Exactly the exponential delay.
+    #[default]
+    None,
+    /// A random wait between zero and the exponential delay.
+    Full,
+    /// Half the exponential delay plus a random wait of up to the other half.
+    Equal,
+}
+
+/// ### Backoff
+///
+/// An endless iterator of the waits between successive retries, growing exponentially.
+///
+/// The `n`th wait, counting from zero, is `base * factor^n`, capped at `max` and then
+/// randomized by the [`Jitter`]. The defaults are a factor of 2, a maximum of one minute and
+/// no jitter. The random numbers come from a small generator seeded from the clock, which
+/// is enough to desynchronize clients but not for anything security-related;
+/// [`with_seed`](Backoff::with_seed) makes the sequence reproducible in tests.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::retry::{Backoff, Jitter};
+///
+/// let waits: Vec<Duration> = Backoff::new(Duration::from_millis(100))
+///     .with_max(Duration::from_secs(1))
+///     .take(6)
+///     .collect();
+/// let millis: Vec<u128> = waits.iter().map(Duration::as_millis).collect();
+/// assert_eq!(millis, [100, 200, 400, 800, 1000, 1000]);
+///
+/// let jittered = Backoff::new(Duration::from_millis(100))
+///     .with_factor(3.0)
+///     .with_jitter(Jitter::Equal)
+///     .with_seed(42);
+/// for (attempt, wait) in jittered.take(4).enumerate() {
+///     let delay = Duration::from_millis(100 * 3u64.pow(attempt as u32));
+///     assert!(wait >= delay / 2 && wait <= delay);
+/// }
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backoff {
+    base: Duration,
+    factor: f64,
+    max: Duration,
+    jitter: Jitter,
+    attempt: i32,
+    state: u64,
+}
+
+impl Backoff {
+    /// Creates a backoff whose first wait is `base`.
+    pub fn new(base: Duration) -> Self {
+        Backoff {
+            base,
+            factor: 2.0,
+            max: Duration::from_secs(60),
+            jitter: Jitter::None,
+            attempt: 0,
+            state: duration_since().as_nanos() as u64,
+        }
+    }
+
+    /// Replaces the growth factor between successive waits.
+    pub fn with_factor(self, factor: f64) -> Self {
+        Backoff { factor, ..self }
+    }
+
+    /// Replaces the longest wait, before jitter.
+    pub fn with_max(self, max: Duration) -> Self {
+        Backoff { max, ..self }
+    }
+
+    /// Replaces the jitter.
+    pub fn with_jitter(self, jitter: Jitter) -> Self {
+        Backoff { jitter, ..self }
+    }
+
+    /// Seeds the random numbers of the jitter, for a reproducible sequence.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Backoff {
+            state: seed,
+            ..self
+        }
+    }
+
+    /// Starts again from the first wait, for example after a success.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Returns the exponential delay of the current attempt, capped at `max`.
+    fn delay(&self) -> Duration {
+        // A negative or NaN factor makes no sense; treat it as no wait rather than panicking
+        let secs = (self.base.as_secs_f64() * self.factor.powi(self.attempt)).max(0.0);
+        if secs < self.max.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max
+        }
+    }
+
+    /// Returns a random duration from zero up to and including `up_to`, with SplitMix64.
+    fn random_up_to(&mut self, up_to: Duration) -> Duration {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        let nanos = u128::from(z) % (up_to.as_nanos() + 1);
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let delay = self.delay();
+        self.attempt = self.attempt.saturating_add(1);
+        Some(match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => self.random_up_to(delay),
+            Jitter::Equal => delay / 2 + self.random_up_to(delay - delay / 2),
+        })
+    }
+}
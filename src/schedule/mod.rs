@@ -0,0 +1,808 @@
+#[cfg(feature = "std")]
+use super::clock::{since_epoch, Clock, SystemClock};
+use super::{
+    calc::{civil_from_days, days_from_civil, shift_months, Weekday, MAX_TS},
+    error::WTimeError,
+};
+#[cfg(feature = "std")]
+use std::panic::{self, AssertUnwindSafe};
+#[cfg(feature = "std")]
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+#[cfg(feature = "std")]
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+/// The longest each month can be, February counting its leap day.
+const MAX_MONTH_LENGTH: [u64; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+const MONTH_NAMES: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+const DAY_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// ### CronExpr
+///
+/// A parsed five-field cron expression: minute, hour, day of month, month and day of week.
+///
+/// The syntax is that of the classic Unix cron:
+/// - `*` for every value, `5` for one value, `1-5` for a range and `1,15,30` for a list
+/// - `/n` for steps, as in `*/15` or `9-17/2`
+/// - `JAN`-`DEC` and `SUN`-`SAT` as names, in any case; Sunday is either `0` or `7`
+/// - the shorthands `@yearly` (or `@annually`), `@monthly`, `@weekly`, `@daily` (or
+///   `@midnight`) and `@hourly`
+///
+/// As in Unix cron, when both the day of month and the day of week are restricted (neither
+/// starts with `*`) a day matches if either of them does. Times are evaluated in UTC.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::schedule::CronExpr;
+///
+/// let now = 1728933069; // Monday 2024-10-14 19:11:09 UTC
+///
+/// let every_five_minutes = CronExpr::parse("*/5 * * * *").unwrap();
+/// assert_eq!(every_five_minutes.next_after(now), 1728933300); // 19:15
+///
+/// let weekday_mornings = CronExpr::parse("0 9 * * MON-FRI").unwrap();
+/// assert_eq!(weekday_mornings.next_after(now), 1728982800); // Tuesday 09:00
+///
+/// let monthly = CronExpr::parse("@monthly").unwrap();
+/// assert_eq!(monthly.next_after(now), 1730419200); // 2024-11-01 00:00
+/// assert!(monthly.next_after(u64::MAX) > wtime::calc::MAX_TS);
+///
+/// assert!(CronExpr::parse("60 * * * *").is_err());
+/// assert!(CronExpr::parse("0 0 30 2 *").is_err()); // February 30 never comes
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronExpr {
+    /// Parses a five-field cron expression or one of the `@` shorthands.
+    ///
+    /// Returns [`WTimeError::InvalidFormat`] for malformed input, and
+    /// [`WTimeError::OutOfRange`] for a value outside its field or a day of month that none
+    /// of the selected months has.
+    pub fn parse(expr: &str) -> Result<Self, WTimeError> {
+        const EXPECTED: &str = "a cron expression such as `*/5 * * * *` or `@daily`";
+
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expr => expr,
+        };
+        let mut fields = expr.split_whitespace();
+        let mut next_field = || fields.next().ok_or(WTimeError::InvalidFormat(EXPECTED));
+        let (minute, hour, day, month, weekday) = (
+            next_field()?,
+            next_field()?,
+            next_field()?,
+            next_field()?,
+            next_field()?,
+        );
+        if fields.next().is_some() {
+            return Err(WTimeError::InvalidFormat(EXPECTED));
+        }
+
+        let mut weekdays = parse_field(weekday, 0, 7, &DAY_NAMES, "day of week", EXPECTED)?;
+        // Sunday is both 0 and 7
+        if weekdays & 1 << 7 != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+        let cron = CronExpr {
+            minutes: parse_field(minute, 0, 59, &[], "minute", EXPECTED)?,
+            hours: parse_field(hour, 0, 23, &[], "hour", EXPECTED)?,
+            days: parse_field(day, 1, 31, &[], "day of month", EXPECTED)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, "month", EXPECTED)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        };
+
+        // Without a day of week to fall back on, some selected month must have a selected day
+        let days_alone = !(cron.days_restricted && cron.weekdays_restricted);
+        let reachable = (1..=12).any(|month| {
+            cron.months & 1 << month != 0
+                && cron.days & ((1 << (MAX_MONTH_LENGTH[month - 1] + 1)) - 1) != 0
+        });
+        if days_alone && !reachable {
+            return Err(WTimeError::OutOfRange("day of month"));
+        }
+        Ok(cron)
+    }
+
+    /// Returns `true` if the minute containing the UNIX timestamp `ts` matches.
+    pub fn matches(&self, ts: u64) -> bool {
+        let days = (ts / 86_400) as i64;
+        let (_, month, day) = civil_from_days(days);
+        let secs_of_day = ts % 86_400;
+        self.months & 1 << month != 0
+            && self.day_matches(days, day)
+            && self.hours & 1 << (secs_of_day / 3600) != 0
+            && self.minutes & 1 << (secs_of_day / 60 % 60) != 0
+    }
+
+    /// Returns the first matching minute strictly after the UNIX timestamp `ts`. Timestamps
+    /// after [`MAX_TS`] are treated as that instant.
+    pub fn next_after(&self, ts: u64) -> u64 {
+        // Far enough below u64::MAX that the search, at most a few years, cannot overflow
+        let ts = ts.min(MAX_TS);
+        let mut ts = ts - ts % 60 + 60;
+        loop {
+            let days = (ts / 86_400) as i64;
+            let (year, month, day) = civil_from_days(days);
+            let secs_of_day = ts % 86_400;
+
+            if self.months & 1 << month == 0 {
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                ts = (days_from_civil(year, month, 1) as u64).saturating_mul(86_400);
+            } else if !self.day_matches(days, day) {
+                ts = (days as u64 + 1).saturating_mul(86_400);
+            } else if self.hours & 1 << (secs_of_day / 3600) == 0 {
+                ts += 3600 - secs_of_day % 3600;
+            } else if self.minutes & 1 << (secs_of_day / 60 % 60) == 0 {
+                ts += 60;
+            } else {
+                return ts;
+            }
+        }
+    }
+
+    /// Applies the cron rule for combining the day of month and day of week fields.
+    fn day_matches(&self, days: i64, day: u64) -> bool {
+        let day_matches = self.days & 1 << day != 0;
+        let weekday_matches =
+            self.weekdays & 1 << (Weekday::from_days(days).number_from_sunday() - 1) != 0;
+        if self.days_restricted && self.weekdays_restricted {
+            day_matches || weekday_matches
+        } else {
+            day_matches && weekday_matches
+        }
+    }
+}
+
+/// Parses one cron field into a bitmask with bit `n` set for each selected value `n`.
+fn parse_field(
+    field: &str,
+    min: u64,
+    max: u64,
+    names: &[&str],
+    name: &'static str,
+    expected: &'static str,
+) -> Result<u64, WTimeError> {
+    let value = |s: &str| -> Result<u64, WTimeError> {
+        if let Some(index) = names.iter().position(|n| n.eq_ignore_ascii_case(s)) {
+            return Ok(index as u64 + min);
+        }
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(WTimeError::InvalidFormat(expected));
+        }
+        match s.parse() {
+            Ok(value) if (min..=max).contains(&value) => Ok(value),
+            _ => Err(WTimeError::OutOfRange(name)),
+        }
+    };
+
+    let mut bits = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (part, None),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else if step.is_some() {
+            // `5/15` runs from 5 to the end of the field
+            (value(range)?, max)
+        } else {
+            (value(range)?, value(range)?)
+        };
+        let step = match step {
+            Some(step) if step.is_empty() || !step.bytes().all(|b| b.is_ascii_digit()) => {
+                return Err(WTimeError::InvalidFormat(expected));
+            }
+            Some(step) => match step.parse() {
+                Ok(step) if step > 0 => step,
+                _ => return Err(WTimeError::OutOfRange(name)),
+            },
+            None => 1,
+        };
+        if start > end {
+            return Err(WTimeError::OutOfRange(name));
+        }
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+/// How far apart the periods of a [`Recurrence`] are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// ### Recurrence
+///
+/// A repeating schedule, every `n` days, weeks or months from a start time, which yields its
+/// occurrences as UNIX timestamps.
+///
+/// Start with [`Recurrence::daily`], [`Recurrence::weekly`] or [`Recurrence::monthly`] and
+/// chain [`every`](Recurrence::every), [`on`](Recurrence::on), [`until`](Recurrence::until)
+/// and [`count`](Recurrence::count). Every occurrence keeps the UTC time of day of the start,
+/// and the start itself is the first occurrence unless it falls on an excluded weekday.
+///
+/// Monthly recurrences keep the day of month of the start, moved back to the last day of
+/// shorter months, as billing cycles do: a recurrence from January 31 falls on February 29,
+/// March 31 and April 30. Without `until` or `count` the occurrences never end.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::{calc_date, Weekday};
+/// use wtime::schedule::Recurrence;
+///
+/// let billing = Recurrence::monthly(1706695200).count(4); // 2024-01-31 10:00 UTC
+/// let dates: Vec<_> = billing.into_iter().map(calc_date).collect();
+/// assert_eq!(
+///     dates,
+///     [(2024, 1, 31), (2024, 2, 29), (2024, 3, 31), (2024, 4, 30)]
+/// );
+///
+/// // Monday, Wednesday and Friday, from Monday 2024-10-14 to the end of the next week
+/// let workouts = Recurrence::weekly(1728933069)
+///     .on(&[Weekday::Monday, Weekday::Wednesday, Weekday::Friday])
+///     .until(1729900799);
+/// let days: Vec<_> = workouts.into_iter().map(|ts| calc_date(ts).2).collect();
+/// assert_eq!(days, [14, 16, 18, 21, 23, 25]);
+///
+/// // Every other day, skipping weekends
+/// let reminders = Recurrence::daily(1728933069)
+///     .every(2)
+///     .on(&[Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday, Weekday::Friday]);
+/// let days: Vec<_> = reminders.into_iter().take(4).map(|ts| calc_date(ts).2).collect();
+/// assert_eq!(days, [14, 16, 18, 22]);
+///
+/// // Every 7 days from a Monday never falls on a Tuesday
+/// let unreachable = Recurrence::daily(1728864000).every(7).on(&[Weekday::Tuesday]);
+/// assert_eq!(unreachable.iter().next(), None);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Recurrence {
+    start: u64,
+    frequency: Frequency,
+    interval: u64,
+    weekdays: u8,
+    until: Option<u64>,
+    count: Option<u64>,
+}
+
+impl Recurrence {
+    fn new(start: u64, frequency: Frequency) -> Self {
+        Recurrence {
+            start,
+            frequency,
+            interval: 1,
+            weekdays: 0,
+            until: None,
+            count: None,
+        }
+    }
+
+    /// Creates a recurrence every day from the UNIX timestamp `start`.
+    pub fn daily(start: u64) -> Self {
+        Recurrence::new(start, Frequency::Daily)
+    }
+
+    /// Creates a recurrence every week from the UNIX timestamp `start`.
+    pub fn weekly(start: u64) -> Self {
+        Recurrence::new(start, Frequency::Weekly)
+    }
+
+    /// Creates a recurrence every month from the UNIX timestamp `start`.
+    pub fn monthly(start: u64) -> Self {
+        Recurrence::new(start, Frequency::Monthly)
+    }
+
+    /// Repeats every `n` days, weeks or months instead of every one. `0` is treated as `1`.
+    pub fn every(self, n: u64) -> Self {
+        Recurrence {
+            interval: n.max(1),
+            ..self
+        }
+    }
+
+    /// Restricts the occurrences to the given weekdays.
+    ///
+    /// Daily recurrences skip the other days. Weekly recurrences fall on each of the given
+    /// days of every `n`th week, weeks starting on Monday. Monthly recurrences ignore the
+    /// weekdays, and an empty list removes the restriction. A daily recurrence whose stride
+    /// never lands on one of the days, such as every 7 days from a Monday restricted to
+    /// Tuesdays, has no occurrences.
+    pub fn on(self, weekdays: &[Weekday]) -> Self {
+        let weekdays = weekdays
+            .iter()
+            .fold(0, |bits, weekday| bits | 1 << *weekday as u8);
+        Recurrence { weekdays, ..self }
+    }
+
+    /// Ends the recurrence at the UNIX timestamp `until`, inclusive.
+    pub fn until(self, until: u64) -> Self {
+        Recurrence {
+            until: Some(until),
+            ..self
+        }
+    }
+
+    /// Ends the recurrence after `count` occurrences.
+    pub fn count(self, count: u64) -> Self {
+        Recurrence {
+            count: Some(count),
+            ..self
+        }
+    }
+
+    /// Returns an iterator over the occurrences.
+    pub fn iter(&self) -> Occurrences {
+        Occurrences {
+            rule: *self,
+            period: 0,
+            weekday: 0,
+            emitted: 0,
+            exhausted: false,
+        }
+    }
+
+    /// Returns the candidate occurrence of `period` and `weekday`, before any filtering.
+    fn candidate(&self, period: u64, weekday: u64) -> Option<u64> {
+        let days = self.start / 86_400;
+        let time_of_day = self.start % 86_400;
+        let steps = period.checked_mul(self.interval)?;
+        let day = match self.frequency {
+            Frequency::Daily => days.checked_add(steps)?,
+            Frequency::Weekly if self.weekdays == 0 => days.checked_add(steps.checked_mul(7)?)?,
+            Frequency::Weekly => {
+                let monday = days - Weekday::from_days(days as i64) as u64;
+                monday.checked_add(steps.checked_mul(7)?)? + weekday
+            }
+            Frequency::Monthly => {
+                let (year, month, day) = civil_from_days(days as i64);
+                let (year, month, day) = shift_months(year, month, day, i64::try_from(steps).ok()?);
+                u64::try_from(days_from_civil(year, month, day)).ok()?
+            }
+        };
+        day.checked_mul(86_400)?.checked_add(time_of_day)
+    }
+}
+
+impl IntoIterator for Recurrence {
+    type Item = u64;
+    type IntoIter = Occurrences;
+
+    fn into_iter(self) -> Occurrences {
+        self.iter()
+    }
+}
+
+/// ### Occurrences
+///
+/// The iterator over the occurrences of a [`Recurrence`], in ascending order.
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    rule: Recurrence,
+    period: u64,
+    weekday: u64,
+    emitted: u64,
+    /// Set once the weekday filter is found to reject every remaining candidate.
+    exhausted: bool,
+}
+
+impl Iterator for Occurrences {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let rule = &self.rule;
+        if self.exhausted || rule.count.is_some_and(|count| self.emitted >= count) {
+            return None;
+        }
+        // Two weeks of candidates cover every weekday reachable by any stride, so a longer run
+        // of rejected candidates means the filter can never match
+        let mut rejected = 0;
+        let ts = loop {
+            let weekly_on = rule.frequency == Frequency::Weekly && rule.weekdays != 0;
+            let ts = rule.candidate(self.period, self.weekday)?;
+            let weekday = self.weekday;
+            if weekly_on && self.weekday < 6 {
+                self.weekday += 1;
+            } else {
+                self.weekday = 0;
+                self.period += 1;
+            }
+
+            if rule.until.is_some_and(|until| ts > until) {
+                return None;
+            }
+            let selected = match rule.frequency {
+                Frequency::Daily => {
+                    rule.weekdays == 0
+                        || rule.weekdays & 1 << Weekday::from_days((ts / 86_400) as i64) as u8 != 0
+                }
+                Frequency::Weekly => {
+                    !weekly_on || (rule.weekdays & 1 << weekday != 0 && ts >= rule.start)
+                }
+                Frequency::Monthly => true,
+            };
+            if selected {
+                break ts;
+            }
+            rejected += 1;
+            if rejected > 14 {
+                self.exhausted = true;
+                return None;
+            }
+        };
+        self.emitted += 1;
+        Some(ts)
+    }
+}
+
+/// The longest the scheduler thread waits before reading the clock again, so that changes
+/// to the system time are noticed.
+#[cfg(feature = "std")]
+const MAX_NAP: Duration = Duration::from_secs(1);
+
+/// ### JobId
+///
+/// The handle of a job registered with a [`Scheduler`], used to cancel it.
+///
+/// <small>End Struct Doc</small>
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobId(u64);
+
+/// When a job runs again after its current run.
+#[cfg(feature = "std")]
+enum Repeat {
+    Once,
+    Cron(CronExpr),
+    Recurrence(Occurrences),
+}
+
+#[cfg(feature = "std")]
+struct Job {
+    id: JobId,
+    next: u64,
+    repeat: Repeat,
+    /// The closure, or `None` while the scheduler thread is running it.
+    task: Option<Box<dyn FnMut() + Send>>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct State {
+    jobs: Vec<Job>,
+    next_id: u64,
+    shutdown: bool,
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+#[cfg(feature = "std")]
+impl Shared {
+    /// Locks the state. Jobs run outside the lock, so a panicking job cannot poison it, and
+    /// the state is consistent even if it was.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// ### Scheduler
+///
+/// A registry of one-shot and recurring jobs, run on a background thread at their UNIX
+/// timestamps.
+///
+/// Jobs are closures registered with [`once`](Scheduler::once), [`cron`](Scheduler::cron) or
+/// [`recurring`](Scheduler::recurring). The scheduler thread reads its [`Clock`] at least once
+/// a second, so it follows adjustments of the system time, and runs each job when the clock
+/// reaches the job's next occurrence, one job at a time. A job that runs late, because an
+/// earlier job was slow or the machine slept, runs once and then moves on to its next
+/// occurrence after the current time rather than catching up. A job that panics is skipped
+/// for that run and stays scheduled.
+///
+/// [`shutdown`](Scheduler::shutdown), or dropping the scheduler, stops the thread gracefully:
+/// a job already running finishes, and pending jobs are discarded, including those due at the
+/// same time that have not started yet.
+///
+/// ### Example
+///
+/// ```
+/// use std::sync::mpsc;
+/// use std::time::Duration;
+/// use wtime::schedule::{CronExpr, Recurrence, Scheduler};
+/// use wtime::utc::utc_ts_sec;
+///
+/// let scheduler = Scheduler::new();
+/// let (done, finished) = mpsc::channel();
+///
+/// let now = utc_ts_sec();
+/// scheduler.once(now + 1, move || done.send("report sent").unwrap());
+///
+/// let nightly = CronExpr::parse("30 2 * * *").unwrap();
+/// let backup = scheduler.cron(nightly, || println!("backing up"));
+/// assert_eq!(scheduler.next_run(backup), Some(nightly.next_after(now)));
+///
+/// let standup = scheduler.recurring(Recurrence::daily(now + 3600).count(10), || {});
+/// assert_eq!(scheduler.len(), 3);
+///
+/// assert_eq!(finished.recv_timeout(Duration::from_secs(5)), Ok("report sent"));
+/// assert!(utc_ts_sec() >= now + 1);
+/// assert_eq!(scheduler.len(), 2);
+///
+/// assert!(scheduler.cancel(standup));
+/// assert!(!scheduler.cancel(standup));
+/// scheduler.shutdown();
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[cfg(feature = "std")]
+pub struct Scheduler {
+    shared: Arc<Shared>,
+    clock: Arc<dyn Clock + Send + Sync>,
+    worker: Option<JoinHandle<()>>,
+}
+
+#[cfg(feature = "std")]
+impl Scheduler {
+    /// Creates a scheduler reading the system clock and starts its thread.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the operating system fails to create the thread.
+    pub fn new() -> Self {
+        Scheduler::with_clock(SystemClock)
+    }
+
+    /// Creates a scheduler reading `clock` and starts its thread.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the operating system fails to create the thread.
+    pub fn with_clock<C: Clock + Send + Sync + 'static>(clock: C) -> Self {
+        let shared = Arc::new(Shared::default());
+        let clock: Arc<dyn Clock + Send + Sync> = Arc::new(clock);
+        let worker = {
+            let shared = Arc::clone(&shared);
+            let clock = Arc::clone(&clock);
+            thread::Builder::new()
+                .name("wtime-scheduler".into())
+                .spawn(move || run_jobs(&shared, &clock.as_ref()))
+                .expect("failed to spawn the scheduler thread")
+        };
+        Scheduler {
+            shared,
+            clock,
+            worker: Some(worker),
+        }
+    }
+
+    /// Runs `task` once at the UNIX timestamp `at`, or as soon as possible if it has passed.
+    pub fn once<F: FnMut() + Send + 'static>(&self, at: u64, task: F) -> JobId {
+        self.add(at, Repeat::Once, Box::new(task))
+    }
+
+    /// Runs `task` at every minute matched by `expr`, from the next one on.
+    pub fn cron<F: FnMut() + Send + 'static>(&self, expr: CronExpr, task: F) -> JobId {
+        let next = expr.next_after(self.now());
+        self.add(next, Repeat::Cron(expr), Box::new(task))
+    }
+
+    /// Runs `task` at every occurrence of `rule`, skipping those that have already passed.
+    /// A rule without occurrences left registers nothing and returns an ID that is never
+    /// scheduled.
+    pub fn recurring<F: FnMut() + Send + 'static>(&self, rule: Recurrence, task: F) -> JobId {
+        let now = self.now();
+        let mut occurrences = rule.iter();
+        match occurrences.find(|&ts| ts >= now) {
+            Some(next) => self.add(next, Repeat::Recurrence(occurrences), Box::new(task)),
+            None => {
+                let mut state = self.shared.lock();
+                state.next_id += 1;
+                JobId(state.next_id)
+            }
+        }
+    }
+
+    /// Cancels a job. Returns `false` if it had already finished or been cancelled. A run
+    /// already in progress is not interrupted.
+    pub fn cancel(&self, id: JobId) -> bool {
+        let mut state = self.shared.lock();
+        let before = state.jobs.len();
+        state.jobs.retain(|job| job.id != id);
+        state.jobs.len() != before
+    }
+
+    /// Returns the UNIX timestamp of the next run of a job, or `None` if it is no longer
+    /// scheduled.
+    pub fn next_run(&self, id: JobId) -> Option<u64> {
+        let state = self.shared.lock();
+        state
+            .jobs
+            .iter()
+            .find(|job| job.id == id)
+            .map(|job| job.next)
+    }
+
+    /// Returns the number of scheduled jobs.
+    pub fn len(&self) -> usize {
+        self.shared.lock().jobs.len()
+    }
+
+    /// Returns `true` if no job is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stops the scheduler thread, waiting for a job already running to finish. Pending jobs
+    /// are discarded.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    /// Returns the current time of the scheduler's clock as a UNIX timestamp.
+    fn now(&self) -> u64 {
+        since_epoch(&self.clock.as_ref()).as_secs()
+    }
+
+    /// Registers a job and wakes the scheduler thread, whose next wake-up may now be earlier.
+    fn add(&self, next: u64, repeat: Repeat, task: Box<dyn FnMut() + Send>) -> JobId {
+        let mut state = self.shared.lock();
+        state.next_id += 1;
+        let id = JobId(state.next_id);
+        state.jobs.push(Job {
+            id,
+            next,
+            repeat,
+            task: Some(task),
+        });
+        self.shared.changed.notify_one();
+        id
+    }
+
+    /// Asks the scheduler thread to stop and joins it.
+    fn stop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.changed.notify_one();
+        if let Some(worker) = self.worker.take() {
+            // The thread catches panics from jobs, so it ends normally
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for Scheduler {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("jobs", &self.len())
+            .finish()
+    }
+}
+
+/// The loop of the scheduler thread: runs the due jobs, then waits for the next one, a new
+/// registration or shutdown.
+#[cfg(feature = "std")]
+fn run_jobs<C: Clock>(shared: &Shared, clock: &C) {
+    let mut state = shared.lock();
+    loop {
+        if state.shutdown {
+            return;
+        }
+        let now = since_epoch(clock);
+        let secs = now.as_secs();
+
+        let mut due = Vec::new();
+        state.jobs.retain_mut(|job| {
+            if job.next > secs {
+                return true;
+            }
+            let next = match &mut job.repeat {
+                Repeat::Once => None,
+                Repeat::Cron(expr) => Some(expr.next_after(secs)),
+                Repeat::Recurrence(occurrences) => occurrences.find(|&ts| ts > secs),
+            };
+            let task = job.task.take();
+            due.push((job.id, task, next.is_some()));
+            match next {
+                Some(next) => {
+                    job.next = next;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        if due.is_empty() {
+            let wait = state
+                .jobs
+                .iter()
+                .map(|job| Duration::from_secs(job.next).saturating_sub(now))
+                .min()
+                .unwrap_or(MAX_NAP)
+                .min(MAX_NAP);
+            state = shared
+                .changed
+                .wait_timeout(state, wait)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+            continue;
+        }
+
+        drop(state);
+        for (id, task, repeats) in &mut due {
+            // Jobs due together still wait for their turn, and a shutdown discards them
+            if shared.lock().shutdown {
+                return;
+            }
+            if let Some(task) = task {
+                // The default panic hook has already reported the panic
+                let _ = panic::catch_unwind(AssertUnwindSafe(task));
+            }
+            if *repeats {
+                let mut state = shared.lock();
+                if let Some(job) = state.jobs.iter_mut().find(|job| job.id == *id) {
+                    job.task = task.take();
+                }
+            }
+        }
+        state = shared.lock();
+    }
+}
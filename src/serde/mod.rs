@@ -0,0 +1,174 @@
+use super::{datetime::DateTime, parse::parse_rfc3339};
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Deserializes an RFC 3339 string into a [`DateTime`] without allocating.
+struct Rfc3339Visitor;
+
+impl de::Visitor<'_> for Rfc3339Visitor {
+    type Value = DateTime;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 timestamp such as `2024-10-14T19:11:09Z`")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<DateTime, E> {
+        parse_rfc3339(value).map_err(E::custom)
+    }
+}
+
+/// Serializes as an RFC 3339 string, e.g. `"2024-10-14T19:11:09.123+02:00"`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 123_000_000, 120);
+/// let json = serde_json::to_string(&dt).unwrap();
+/// assert_eq!(json, r#""2024-10-14T21:11:09.123+02:00""#);
+/// assert_eq!(serde_json::from_str::<DateTime>(&json).unwrap(), dt);
+/// ```
+impl Serialize for DateTime {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateTime {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(Rfc3339Visitor)
+    }
+}
+
+/// ### ts_seconds
+///
+/// Serializes a [`DateTime`] field as whole seconds since the UNIX epoch.
+///
+/// The sub-second part and the offset are not stored, so a deserialized value is always in
+/// UTC with `nanos == 0`.
+///
+/// ### Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use wtime::datetime::DateTime;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "wtime::serde::ts_seconds")]
+///     at: DateTime,
+/// }
+///
+/// let event = Event { at: DateTime::new(1728933069, 123_000_000, 120) };
+/// let json = serde_json::to_string(&event).unwrap();
+/// assert_eq!(json, r#"{"at":1728933069}"#);
+///
+/// let event: Event = serde_json::from_str(&json).unwrap();
+/// assert_eq!(event.at, DateTime::from_ts(1728933069));
+/// ```
+///
+/// <small>End Mod Doc</small>
+pub mod ts_seconds {
+    use super::*;
+
+    /// Serializes a [`DateTime`] as seconds since the UNIX epoch.
+    pub fn serialize<S: Serializer>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(dt.ts)
+    }
+
+    /// Deserializes seconds since the UNIX epoch into a UTC [`DateTime`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime, D::Error> {
+        u64::deserialize(deserializer).map(DateTime::from_ts)
+    }
+}
+
+/// ### ts_millis
+///
+/// Serializes a [`DateTime`] field as milliseconds since the UNIX epoch, the representation
+/// of JavaScript's `Date.now()`.
+///
+/// Sub-millisecond precision and the offset are not stored, so a deserialized value is
+/// always in UTC.
+///
+/// ### Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use wtime::datetime::DateTime;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "wtime::serde::ts_millis")]
+///     at: DateTime,
+/// }
+///
+/// let event = Event { at: DateTime::new(1728933069, 123_456_789, 0) };
+/// let json = serde_json::to_string(&event).unwrap();
+/// assert_eq!(json, r#"{"at":1728933069123}"#);
+///
+/// let event: Event = serde_json::from_str(&json).unwrap();
+/// assert_eq!(event.at, DateTime::new(1728933069, 123_000_000, 0));
+/// ```
+///
+/// <small>End Mod Doc</small>
+pub mod ts_millis {
+    use super::*;
+
+    /// Serializes a [`DateTime`] as milliseconds since the UNIX epoch.
+    pub fn serialize<S: Serializer>(dt: &DateTime, serializer: S) -> Result<S::Ok, S::Error> {
+        let millis = dt
+            .ts
+            .checked_mul(1000)
+            .and_then(|millis| millis.checked_add(dt.nanos / 1_000_000))
+            .ok_or_else(|| ::serde::ser::Error::custom("timestamp out of range for millis"))?;
+        serializer.serialize_u64(millis)
+    }
+
+    /// Deserializes milliseconds since the UNIX epoch into a UTC [`DateTime`].
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime, D::Error> {
+        let millis = u64::deserialize(deserializer)?;
+        Ok(DateTime::new(millis / 1000, millis % 1000 * 1_000_000, 0))
+    }
+}
+
+/// ### rfc3339
+///
+/// Serializes a raw `u64` timestamp field, in seconds since the UNIX epoch, as an RFC 3339
+/// string in UTC.
+///
+/// Deserialization accepts any offset and keeps only the instant; a fraction of a second is
+/// dropped.
+///
+/// ### Example
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "wtime::serde::rfc3339")]
+///     at: u64,
+/// }
+///
+/// let json = serde_json::to_string(&Event { at: 1728933069 }).unwrap();
+/// assert_eq!(json, r#"{"at":"2024-10-14T19:11:09Z"}"#);
+///
+/// let event: Event = serde_json::from_str(r#"{"at":"2024-10-15T00:41:09+05:30"}"#).unwrap();
+/// assert_eq!(event.at, 1728933069);
+/// ```
+///
+/// <small>End Mod Doc</small>
+pub mod rfc3339 {
+    use super::*;
+
+    /// Serializes a UNIX timestamp as an RFC 3339 string in UTC.
+    pub fn serialize<S: Serializer>(ts: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&DateTime::from_ts(*ts))
+    }
+
+    /// Deserializes an RFC 3339 string into a UNIX timestamp.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_str(Rfc3339Visitor).map(|dt| dt.ts)
+    }
+}
@@ -0,0 +1,95 @@
+use super::calc::duration_since;
+#[cfg(feature = "tz")]
+use super::{error::WTimeError, local::next_local_time};
+use std::thread;
+use std::time::Duration;
+
+/// The longest single sleep, so that changes to the system time are noticed.
+const MAX_NAP: Duration = Duration::from_secs(1);
+
+/// ### sleep_until(target_ts: u64)
+///
+/// Blocks the current thread until the system clock reaches a UNIX timestamp in seconds.
+///
+/// The remaining time is recomputed after every nap of at most one second, so when the
+/// system time is adjusted, for example by NTP or by hand, the thread still wakes when the
+/// wall clock reaches `target_ts` rather than after the originally computed duration. A
+/// timestamp that has already passed returns at once. A clock frozen with the `testing`
+/// module never reaches a later target.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::sleep::sleep_until;
+/// use wtime::utc::utc_ts_sec;
+///
+/// let next_second = utc_ts_sec() + 1;
+/// sleep_until(next_second);
+/// assert!(utc_ts_sec() >= next_second);
+///
+/// sleep_until(1728933069); // in the past: returns immediately
+/// ```
+///
+/// <small>End Fun Doc</small>
+pub fn sleep_until(target_ts: u64) {
+    let target = Duration::from_secs(target_ts);
+    loop {
+        let now = duration_since();
+        if now >= target {
+            return;
+        }
+        thread::sleep((target - now).min(MAX_NAP));
+    }
+}
+
+/// ### sleep_until_local(hour: u64, minute: u64, second: u64) -> Result<u64, WTimeError>
+///
+/// Blocks the current thread until the next time the local clock reads `hour:minute:second`.
+///
+/// This is the "run at 02:00" of scripts and daemons: if the time has not come yet today,
+/// the thread wakes today, and otherwise tomorrow. The target is computed with the local
+/// zone's daylight-saving rules; a time skipped when the clocks go forward is reached at
+/// the first instant after the gap. The wait itself is [`sleep_until`], which follows
+/// adjustments of the system time. This function requires the `tz` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::LocalSnapshot;
+/// use wtime::sleep::sleep_until_local;
+///
+/// // Wake at the start of the next local second
+/// let now = LocalSnapshot::now();
+/// let secs = (now.hour() * 3600 + now.minute() * 60 + now.second() + 1) % 86_400;
+/// let woke_at = sleep_until_local(secs / 3600, secs / 60 % 60, secs % 60).unwrap();
+/// assert!(woke_at > now.ts());
+///
+/// assert!(sleep_until_local(24, 0, 0).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp that was waited for.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for an hour above 23 or a minute or second above 59,
+/// without sleeping.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tz")]
+pub fn sleep_until_local(hour: u64, minute: u64, second: u64) -> Result<u64, WTimeError> {
+    if hour > 23 {
+        return Err(WTimeError::OutOfRange("hour"));
+    }
+    if minute > 59 {
+        return Err(WTimeError::OutOfRange("minute"));
+    }
+    if second > 59 {
+        return Err(WTimeError::OutOfRange("second"));
+    }
+    let secs_of_day = (hour * 3600 + minute * 60 + second) as i64;
+    let target = next_local_time(duration_since().as_secs(), secs_of_day);
+    sleep_until(target);
+    Ok(target)
+}
@@ -0,0 +1,182 @@
+use super::{
+    calc::{calc_date, write_wtime_ts},
+    datetime::DateTime,
+};
+use core::fmt::{self, Write};
+use core::time::Duration;
+
+/// ### TimeSource
+///
+/// A source of the current time, for targets where the system clock is unavailable.
+///
+/// Everything in the crate that reads the time uses the standard library's `SystemTime`,
+/// which does not exist on `no_std` targets. Embedded code implements `TimeSource` over its
+/// own clock, typically a real-time clock peripheral, and passes it to [`source_ts`],
+/// [`source_date`], [`source_datetime`] and [`format_source_ts_into`]; the date math of the
+/// `calc` module and the `Display` implementation of [`DateTime`] work on the result without
+/// `std`.
+///
+/// Any `Fn() -> Duration` closure is a `TimeSource`. With the `std` feature, [`SystemSource`]
+/// reads the system clock.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::calc::calc_week;
+/// use wtime::source::{source_date, source_ts, TimeSource};
+///
+/// struct Rtc {
+///     seconds: u32,
+/// }
+///
+/// impl TimeSource for Rtc {
+///     fn since_epoch(&self) -> Duration {
+///         Duration::from_secs(u64::from(self.seconds))
+///     }
+/// }
+///
+/// let rtc = Rtc { seconds: 1728933069 };
+/// assert_eq!(source_ts(&rtc), 1728933069);
+///
+/// let read_rtc = || Duration::from_secs(1728933069);
+/// assert_eq!(calc_week(source_date(&read_rtc)), 42);
+/// ```
+///
+/// <small>End Trait Doc</small>
+pub trait TimeSource {
+    /// Returns the time elapsed since the UNIX epoch.
+    fn since_epoch(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> TimeSource for F {
+    fn since_epoch(&self) -> Duration {
+        self()
+    }
+}
+
+/// ### SystemSource
+///
+/// The [`TimeSource`] backed by the system clock, available with the `std` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::source::{source_ts, SystemSource};
+///
+/// assert!(source_ts(&SystemSource) > 1728933069);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SystemSource;
+
+#[cfg(feature = "std")]
+impl TimeSource for SystemSource {
+    fn since_epoch(&self) -> Duration {
+        super::calc::duration_since()
+    }
+}
+
+/// ### source_ts<T: TimeSource>(source: &T) -> u64
+///
+/// Reads a time source as a UNIX timestamp in seconds.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::source::source_ts;
+///
+/// let rtc = || Duration::new(1728933069, 500_000_000);
+/// assert_eq!(source_ts(&rtc), 1728933069);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the whole seconds since the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn source_ts<T: TimeSource>(source: &T) -> u64 {
+    source.since_epoch().as_secs()
+}
+
+/// ### source_date<T: TimeSource>(source: &T) -> (u64, u64, u64)
+///
+/// Reads a time source as a UTC `(year, month, day)`.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::source::source_date;
+///
+/// let rtc = || Duration::from_secs(1728933069);
+/// assert_eq!(source_date(&rtc), (2024, 10, 14));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current UTC date as a tuple.
+///
+/// <small>End Fun Doc</small>
+pub fn source_date<T: TimeSource>(source: &T) -> (u64, u64, u64) {
+    calc_date(source_ts(source))
+}
+
+/// ### source_datetime<T: TimeSource>(source: &T) -> DateTime
+///
+/// Reads a time source as a UTC [`DateTime`], keeping the sub-second part.
+///
+/// The `Display` implementation of `DateTime` writes RFC 3339 without allocating, so the
+/// result can be written straight into a `no_std` buffer.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::source::source_datetime;
+///
+/// let rtc = || Duration::new(1728933069, 123_000_000);
+/// assert_eq!(source_datetime(&rtc).to_string(), "2024-10-14T19:11:09.123Z");
+/// ```
+///
+/// ### Returns
+///
+/// Returns the current instant as a [`DateTime`] in UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn source_datetime<T: TimeSource>(source: &T) -> DateTime {
+    let since = source.since_epoch();
+    DateTime::new(since.as_secs(), u64::from(since.subsec_nanos()), 0)
+}
+
+/// ### format_source_ts_into<W: Write, T: TimeSource>(out: &mut W, source: &T) -> fmt::Result
+///
+/// Writes the time of a source in the crate's
+/// `year-month-day-hour-minute-second-millis-nanos` layout, in UTC.
+///
+/// This is the `no_std` counterpart of `format_utc_ts_into`: the source is read once and
+/// nothing is allocated.
+///
+/// ### Example
+///
+/// ```
+/// use core::time::Duration;
+/// use wtime::source::format_source_ts_into;
+///
+/// let rtc = || Duration::new(1728933069, 123_456_789);
+/// let mut line = String::new();
+/// format_source_ts_into(&mut line, &rtc).unwrap();
+/// assert_eq!(line, "2024-10-14-19-11-09-123-456789");
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn format_source_ts_into<W: Write, T: TimeSource>(out: &mut W, source: &T) -> fmt::Result {
+    write_wtime_ts(out, source.since_epoch(), 0)
+}
@@ -0,0 +1,347 @@
+use super::monotonic::mono_now;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The function that receives the label and elapsed time of a [`ScopedTimer`].
+type Report<'a> = Box<dyn FnOnce(&'static str, Duration) + 'a>;
+
+/// ### ScopedTimer<'a>
+///
+/// Measures the time until it is dropped, for lightweight profiling.
+///
+/// Create a timer at the start of a scope; when the scope ends, the elapsed time on the
+/// monotonic clock is reported with the timer's label. [`ScopedTimer::new`] prints
+/// `label: elapsed` to standard error, [`ScopedTimer::with_callback`] hands both to a
+/// closure, and [`Collector::start`] stores them for later. The [`time_it!`](crate::time_it)
+/// macro times a single expression.
+///
+/// ### Example
+///
+/// ```
+/// use std::cell::Cell;
+/// use std::time::Duration;
+/// use wtime::stopwatch::ScopedTimer;
+///
+/// {
+///     let _timer = ScopedTimer::new("load config");
+///     // Prints e.g. `load config: 1.2ms` to stderr at the end of the scope
+/// }
+///
+/// let slowest = Cell::new(Duration::ZERO);
+/// {
+///     let _timer = ScopedTimer::with_callback("query", |_, elapsed| {
+///         slowest.set(slowest.get().max(elapsed));
+///     });
+///     std::thread::sleep(Duration::from_millis(5));
+/// }
+/// assert!(slowest.get() >= Duration::from_millis(5));
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[must_use = "the timer reports as soon as it is dropped"]
+pub struct ScopedTimer<'a> {
+    label: &'static str,
+    start: Instant,
+    report: Option<Report<'a>>,
+}
+
+impl<'a> ScopedTimer<'a> {
+    /// Starts a timer that prints its label and elapsed time to standard error when dropped.
+    pub fn new(label: &'static str) -> Self {
+        ScopedTimer {
+            label,
+            start: mono_now(),
+            report: None,
+        }
+    }
+
+    /// Starts a timer that passes its label and elapsed time to `report` when dropped.
+    pub fn with_callback(
+        label: &'static str,
+        report: impl FnOnce(&'static str, Duration) + 'a,
+    ) -> Self {
+        ScopedTimer {
+            label,
+            start: mono_now(),
+            report: Some(Box::new(report)),
+        }
+    }
+
+    /// Returns the label of the timer.
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns the time elapsed so far.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+impl Drop for ScopedTimer<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        match self.report.take() {
+            Some(report) => report(self.label, elapsed),
+            None => eprintln!("{}: {:?}", self.label, elapsed),
+        }
+    }
+}
+
+impl std::fmt::Debug for ScopedTimer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScopedTimer")
+            .field("label", &self.label)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
+}
+
+/// ### Collector
+///
+/// Stores the measurements of [`ScopedTimer`]s, for a report at the end of a run.
+///
+/// A collector is thread-safe and can be a `static`, so timers anywhere in a program can
+/// record into it.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::stopwatch::Collector;
+///
+/// static PROFILE: Collector = Collector::new();
+///
+/// for _ in 0..3 {
+///     let _timer = PROFILE.start("parse");
+/// }
+/// {
+///     let _timer = PROFILE.start("render");
+/// }
+///
+/// let records = PROFILE.records();
+/// assert_eq!(records.len(), 4);
+/// assert_eq!(records.iter().filter(|(label, _)| *label == "parse").count(), 3);
+/// println!("parse took {:?} in total", PROFILE.total("parse"));
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Default)]
+pub struct Collector {
+    records: Mutex<Vec<(&'static str, Duration)>>,
+}
+
+impl Collector {
+    /// Creates an empty collector, usable in a `static`.
+    pub const fn new() -> Self {
+        Collector {
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Starts a timer that records into this collector when dropped.
+    pub fn start(&self, label: &'static str) -> ScopedTimer<'_> {
+        ScopedTimer::with_callback(label, move |label, elapsed| self.record(label, elapsed))
+    }
+
+    /// Records a measurement.
+    pub fn record(&self, label: &'static str, elapsed: Duration) {
+        self.lock().push((label, elapsed));
+    }
+
+    /// Returns the measurements recorded so far, oldest first.
+    pub fn records(&self) -> Vec<(&'static str, Duration)> {
+        self.lock().clone()
+    }
+
+    /// Returns the sum of the measurements with a label.
+    pub fn total(&self, label: &'static str) -> Duration {
+        self.lock()
+            .iter()
+            .filter(|(recorded, _)| *recorded == label)
+            .map(|(_, elapsed)| *elapsed)
+            .sum()
+    }
+
+    /// Removes all measurements.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Locks the measurements.
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(&'static str, Duration)>> {
+        // The lock only guards a list of measurements, which a panic cannot leave invalid
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// ### Bench
+///
+/// A quick micro-benchmark: runs a closure a number of times and summarizes the durations.
+///
+/// Each run is timed on the monotonic clock, after optional warm-up runs that are not
+/// measured. The value returned by the closure is passed through `std::hint::black_box`,
+/// so the compiler cannot optimize the work away. For rigorous measurements, with outlier
+/// detection and comparisons between runs, use a dedicated benchmarking crate.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::calc_date;
+/// use wtime::stopwatch::Bench;
+///
+/// let stats = Bench::new(1000)
+///     .with_warmup(100)
+///     .run(|| calc_date(1728933069));
+///
+/// assert_eq!(stats.iterations, 1000);
+/// assert!(stats.min <= stats.median && stats.median <= stats.p95 && stats.p95 <= stats.max);
+/// println!("calc_date: {}", stats);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bench {
+    iterations: usize,
+    warmup: usize,
+}
+
+impl Bench {
+    /// Creates a benchmark of `iterations` measured runs and no warm-up.
+    pub fn new(iterations: usize) -> Self {
+        Bench {
+            iterations,
+            warmup: 0,
+        }
+    }
+
+    /// Replaces the number of unmeasured runs made first.
+    pub fn with_warmup(self, warmup: usize) -> Self {
+        Bench { warmup, ..self }
+    }
+
+    /// Runs `f` and summarizes the durations of the measured runs.
+    pub fn run<R>(&self, mut f: impl FnMut() -> R) -> BenchStats {
+        for _ in 0..self.warmup {
+            std::hint::black_box(f());
+        }
+        let mut samples: Vec<Duration> = (0..self.iterations)
+            .map(|_| {
+                let start = mono_now();
+                std::hint::black_box(f());
+                start.elapsed()
+            })
+            .collect();
+        samples.sort_unstable();
+        BenchStats::from_sorted(&samples)
+    }
+}
+
+/// ### BenchStats
+///
+/// The summary of the durations measured by a [`Bench`].
+///
+/// The median of an even number of runs is the mean of the two middle ones, and the 95th
+/// percentile is the duration that at least 95% of the runs did not exceed. Its `Display`
+/// implementation writes all of them on one line. With zero iterations, every duration is
+/// zero.
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BenchStats {
+    /// The number of measured runs.
+    pub iterations: usize,
+    /// The fastest run.
+    pub min: Duration,
+    /// The slowest run.
+    pub max: Duration,
+    /// The mean duration.
+    pub mean: Duration,
+    /// The median duration.
+    pub median: Duration,
+    /// The 95th percentile.
+    pub p95: Duration,
+}
+
+impl BenchStats {
+    /// Summarizes durations sorted from the fastest.
+    fn from_sorted(samples: &[Duration]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return BenchStats {
+                iterations: 0,
+                min: Duration::ZERO,
+                max: Duration::ZERO,
+                mean: Duration::ZERO,
+                median: Duration::ZERO,
+                p95: Duration::ZERO,
+            };
+        }
+        let total: u128 = samples.iter().map(Duration::as_nanos).sum();
+        let nanos = |value: u128| Duration::from_nanos(u64::try_from(value).unwrap_or(u64::MAX));
+        let median = if n % 2 == 0 {
+            nanos((samples[n / 2 - 1].as_nanos() + samples[n / 2].as_nanos()) / 2)
+        } else {
+            samples[n / 2]
+        };
+        BenchStats {
+            iterations: n,
+            min: samples[0],
+            max: samples[n - 1],
+            mean: nanos(total / n as u128),
+            median,
+            // The nearest-rank percentile: the ceil(0.95 n)-th fastest run
+            p95: samples[(n * 95).div_ceil(100) - 1],
+        }
+    }
+}
+
+impl std::fmt::Display for BenchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} runs: min {:?}, mean {:?}, median {:?}, p95 {:?}, max {:?}",
+            self.iterations, self.min, self.mean, self.median, self.p95, self.max
+        )
+    }
+}
+
+/// ### time_it!
+///
+/// Times an expression with a [`ScopedTimer`] and evaluates to its value.
+///
+/// `time_it!("label", expr)` prints the label and the elapsed time to standard error, and
+/// `time_it!(collector, "label", expr)` records them into a [`Collector`] instead.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::stopwatch::Collector;
+/// use wtime::time_it;
+///
+/// let sum: u64 = time_it!("sum", (1..=1000u64).sum());
+/// assert_eq!(sum, 500_500);
+///
+/// let profile = Collector::new();
+/// let sorted = time_it!(profile, "sort", {
+///     let mut values = vec![3, 1, 2];
+///     values.sort();
+///     values
+/// });
+/// assert_eq!(sorted, [1, 2, 3]);
+/// assert_eq!(profile.records()[0].0, "sort");
+/// ```
+///
+/// <small>End Macro Doc</small>
+#[macro_export]
+macro_rules! time_it {
+    ($label:expr, $body:expr $(,)?) => {{
+        let _timer = $crate::stopwatch::ScopedTimer::new($label);
+        $body
+    }};
+    ($collector:expr, $label:expr, $body:expr $(,)?) => {{
+        let _timer = $crate::stopwatch::Collector::start(&$collector, $label);
+        $body
+    }};
+}
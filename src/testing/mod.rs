@@ -0,0 +1,150 @@
+use std::cell::Cell;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// An override of the current time on one thread.
+#[derive(Debug, Clone, Copy)]
+enum Now {
+    /// The clock is stopped at this instant.
+    At(SystemTime),
+    /// The clock runs this far ahead of the system clock.
+    Ahead(Duration),
+}
+
+thread_local! {
+    static OVERRIDE: Cell<Option<Now>> = const { Cell::new(None) };
+}
+
+/// ### TimeGuard
+///
+/// Restores the previous notion of "now" on the current thread when dropped.
+///
+/// Returned by [`freeze_at`] and [`travel_by`]. Guards nest: dropping the inner one brings
+/// back the override of the outer one.
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug)]
+#[must_use = "the override ends as soon as the guard is dropped"]
+pub struct TimeGuard {
+    previous: Option<Now>,
+}
+
+impl Drop for TimeGuard {
+    fn drop(&mut self) {
+        OVERRIDE.with(|now| now.set(self.previous));
+    }
+}
+
+/// Replaces the override of the current thread, returning a guard for the previous one.
+fn replace(new: Now) -> TimeGuard {
+    TimeGuard {
+        previous: OVERRIDE.with(|now| now.replace(Some(new))),
+    }
+}
+
+/// ### freeze_at(ts: u64) -> TimeGuard
+///
+/// Stops the crate's clock at a UNIX timestamp on the current thread.
+///
+/// Every function that reads the current time through [`utc_now`](crate::utc::utc_now),
+/// including `utc_ts_sec`, the `local` module, `format_ts` and `SystemClock`, returns the
+/// frozen time until the guard is dropped or [`unfreeze`] is called. Other threads keep the
+/// real clock, so tests running in parallel do not interfere. The `Instant`-based
+/// `monotonic` helpers are not affected.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::testing::{freeze_at, travel_by};
+/// use wtime::utc::utc_ts_sec;
+///
+/// fn is_expired(expires_at: u64) -> bool {
+///     utc_ts_sec() >= expires_at
+/// }
+///
+/// let _frozen = freeze_at(1728933069);
+/// assert_eq!(utc_ts_sec(), 1728933069);
+/// assert!(!is_expired(1728933069 + 3600));
+///
+/// {
+///     let _later = travel_by(Duration::from_secs(3600));
+///     assert!(is_expired(1728933069 + 3600));
+/// }
+/// assert_eq!(utc_ts_sec(), 1728933069);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`TimeGuard`] that restores the previous time when dropped.
+///
+/// <small>End Fun Doc</small>
+pub fn freeze_at(ts: u64) -> TimeGuard {
+    replace(Now::At(UNIX_EPOCH + Duration::from_secs(ts)))
+}
+
+/// ### travel_by(by: Duration) -> TimeGuard
+///
+/// Moves the crate's clock forwards on the current thread.
+///
+/// A frozen clock stays frozen at the later instant; otherwise the clock keeps running, `by`
+/// ahead of the system clock. Travels add up.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::testing::travel_by;
+/// use wtime::utc::utc_ts_sec;
+///
+/// let real = utc_ts_sec();
+/// let _tomorrow = travel_by(Duration::from_secs(86_400));
+/// assert!(utc_ts_sec() >= real + 86_400);
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`TimeGuard`] that restores the previous time when dropped.
+///
+/// <small>End Fun Doc</small>
+pub fn travel_by(by: Duration) -> TimeGuard {
+    let travelled = match OVERRIDE.with(Cell::get) {
+        Some(Now::At(time)) => Now::At(time + by),
+        Some(Now::Ahead(ahead)) => Now::Ahead(ahead + by),
+        None => Now::Ahead(by),
+    };
+    replace(travelled)
+}
+
+/// ### unfreeze()
+///
+/// Removes every time override on the current thread, returning to the system clock.
+///
+/// Guards dropped afterwards still restore the override that was in place when they were
+/// created.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::testing::{freeze_at, unfreeze};
+/// use wtime::utc::utc_ts_sec;
+///
+/// let _frozen = freeze_at(0);
+/// assert_eq!(utc_ts_sec(), 0);
+///
+/// unfreeze();
+/// assert!(utc_ts_sec() > 1728933069);
+/// ```
+///
+/// <small>End Fun Doc</small>
+pub fn unfreeze() {
+    OVERRIDE.with(|now| now.set(None));
+}
+
+/// Applies the override of the current thread, if any, to a reading of the system clock.
+pub(crate) fn adjust(system_now: impl FnOnce() -> SystemTime) -> SystemTime {
+    match OVERRIDE.with(Cell::get) {
+        Some(Now::At(time)) => time,
+        Some(Now::Ahead(ahead)) => system_now() + ahead,
+        None => system_now(),
+    }
+}
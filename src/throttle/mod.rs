@@ -0,0 +1,146 @@
+use super::monotonic::mono_now;
+use std::time::{Duration, Instant};
+
+/// ### Throttler
+///
+/// Lets an action run at most once per interval, measured on the monotonic clock.
+///
+/// The first call to [`should_run`](Throttler::should_run) returns `true`, and later calls
+/// return `true` again only once `interval` has passed since the last one that did. This
+/// suits log-spam suppression and progress reporting. Each method has an `_at` variant that
+/// takes the monotonic reading, for deterministic tests.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::mono_now;
+/// use wtime::throttle::Throttler;
+///
+/// let mut warnings = Throttler::new(Duration::from_secs(10));
+/// assert!(warnings.should_run());
+/// assert!(!warnings.should_run());
+///
+/// let mut progress = Throttler::new(Duration::from_secs(1));
+/// let start = mono_now();
+/// assert!(progress.should_run_at(start));
+/// assert!(!progress.should_run_at(start + Duration::from_millis(999)));
+/// assert!(progress.should_run_at(start + Duration::from_secs(1)));
+/// assert!(!progress.should_run_at(start + Duration::from_millis(1500)));
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Throttler {
+    interval: Duration,
+    /// When the action last ran, or `None` if it never has.
+    last_run: Option<Instant>,
+}
+
+impl Throttler {
+    /// Creates a throttler that allows one run per `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Throttler {
+            interval,
+            last_run: None,
+        }
+    }
+
+    /// Returns `true`, and counts it as a run, if the action may run now.
+    pub fn should_run(&mut self) -> bool {
+        self.should_run_at(mono_now())
+    }
+
+    /// Returns `true`, and counts it as a run, if the action may run at `now`.
+    pub fn should_run_at(&mut self, now: Instant) -> bool {
+        let due = self
+            .last_run
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval);
+        if due {
+            self.last_run = Some(now);
+        }
+        due
+    }
+
+    /// Forgets the last run, so that the next call is allowed.
+    pub fn reset(&mut self) {
+        self.last_run = None;
+    }
+}
+
+/// ### Debouncer
+///
+/// Runs an action once a burst of events has settled, measured on the monotonic clock.
+///
+/// Each event is reported with [`trigger`](Debouncer::trigger).
+/// [`should_run`](Debouncer::should_run) returns `true` once `delay` has passed since the
+/// latest event, a single time per burst. This suits saving after the last keystroke or
+/// reloading after a series of file changes. Each method has an `_at` variant that takes the
+/// monotonic reading, for deterministic tests.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::mono_now;
+/// use wtime::throttle::Debouncer;
+///
+/// let mut save = Debouncer::new(Duration::from_millis(300));
+/// let start = mono_now();
+/// assert!(!save.should_run_at(start)); // nothing to do yet
+///
+/// save.trigger_at(start);
+/// save.trigger_at(start + Duration::from_millis(200));
+/// assert!(!save.should_run_at(start + Duration::from_millis(400)));
+/// assert!(save.should_run_at(start + Duration::from_millis(500)));
+/// assert!(!save.should_run_at(start + Duration::from_millis(600))); // already ran
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Debouncer {
+    delay: Duration,
+    /// The latest event not yet acted upon, if any.
+    pending: Option<Instant>,
+}
+
+impl Debouncer {
+    /// Creates a debouncer that waits for `delay` without events.
+    pub fn new(delay: Duration) -> Self {
+        Debouncer {
+            delay,
+            pending: None,
+        }
+    }
+
+    /// Reports an event now, restarting the wait.
+    pub fn trigger(&mut self) {
+        self.trigger_at(mono_now());
+    }
+
+    /// Reports an event at `now`, restarting the wait.
+    pub fn trigger_at(&mut self, now: Instant) {
+        self.pending = Some(now);
+    }
+
+    /// Returns `true`, and clears the pending events, if they have settled by now.
+    pub fn should_run(&mut self) -> bool {
+        self.should_run_at(mono_now())
+    }
+
+    /// Returns `true`, and clears the pending events, if they have settled by `now`.
+    pub fn should_run_at(&mut self, now: Instant) -> bool {
+        let settled = self
+            .pending
+            .is_some_and(|last| now.saturating_duration_since(last) >= self.delay);
+        if settled {
+            self.pending = None;
+        }
+        settled
+    }
+
+    /// Returns `true` if there are events that have not been acted upon yet.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
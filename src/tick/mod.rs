@@ -0,0 +1,108 @@
+use super::monotonic::mono_now;
+use std::time::{Duration, Instant};
+
+/// ### Ticker
+///
+/// An iterator that blocks until each tick of a fixed period, for game loops and
+/// polling daemons.
+///
+/// Ticks are scheduled at a fixed rate on the monotonic clock: the `n`th tick is due `n`
+/// periods after the first, however long the work between ticks takes, so the schedule does
+/// not drift. Each call to `next` sleeps until the next tick is due and returns the instant
+/// it was scheduled for. The first tick is due immediately. When the work falls more than a
+/// period behind, the ticks that were missed are skipped rather than returned in a burst,
+/// and [`missed`](Ticker::missed) counts them. The iterator ends after the last tick that
+/// `Instant` can represent, which only a period of centuries reaches.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::tick::Ticker;
+///
+/// let period = Duration::from_millis(10);
+/// let mut ticks = Ticker::every(period);
+///
+/// let first = ticks.next().unwrap();
+/// for (n, tick) in ticks.by_ref().take(3).enumerate() {
+///     // Scheduled exactly on the grid, whatever the sleep granularity
+///     assert_eq!(tick - first, period * (n as u32 + 1));
+/// }
+/// assert_eq!(ticks.missed(), 0);
+///
+/// // No tick can follow a period this long
+/// let mut once = Ticker::every(Duration::MAX);
+/// assert!(once.next().is_some());
+/// assert_eq!(once.next(), None);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ticker {
+    period: Duration,
+    /// The instant the next tick is scheduled for, `None` once it would overflow `Instant`.
+    next: Option<Instant>,
+    missed: u64,
+}
+
+impl Ticker {
+    /// Creates a ticker whose first tick is due now and whose later ticks follow every
+    /// `period`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn every(period: Duration) -> Self {
+        Ticker::starting_at(mono_now(), period)
+    }
+
+    /// Creates a ticker whose first tick is due at `start`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn starting_at(start: Instant, period: Duration) -> Self {
+        assert!(!period.is_zero(), "a ticker needs a non-zero period");
+        Ticker {
+            period,
+            next: Some(start),
+            missed: 0,
+        }
+    }
+
+    /// Returns the period between ticks.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns the number of ticks skipped so far because the caller fell behind.
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+}
+
+impl Iterator for Ticker {
+    type Item = Instant;
+
+    fn next(&mut self) -> Option<Instant> {
+        let mut tick = self.next?;
+        let now = mono_now();
+        if now < tick {
+            std::thread::sleep(tick - now);
+        } else {
+            // Skip to the latest tick that is already due, which is never later than now
+            let behind = (now - tick).as_nanos() / self.period.as_nanos();
+            let behind = u32::try_from(behind).unwrap_or(u32::MAX);
+            if let Some(due) = self
+                .period
+                .checked_mul(behind)
+                .and_then(|skipped| tick.checked_add(skipped))
+            {
+                tick = due;
+                self.missed += u64::from(behind);
+            }
+        }
+        self.next = tick.checked_add(self.period);
+        Some(tick)
+    }
+}
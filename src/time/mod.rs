@@ -0,0 +1,193 @@
+use super::{calc::is_valid_date, datetime::DateTime, error::WTimeError};
+use ::time::{Date, Month, OffsetDateTime, UtcOffset};
+
+/// Converts to the `time` crate's date-time, keeping the offset.
+///
+/// ### Example
+///
+/// ```
+/// use time::OffsetDateTime;
+/// use wtime::datetime::DateTime;
+///
+/// let dt = DateTime::new(1728933069, 123_000_000, 120);
+/// let odt = OffsetDateTime::try_from(dt).unwrap();
+/// assert_eq!(odt.unix_timestamp(), 1728933069);
+/// assert_eq!((odt.hour(), odt.offset().whole_minutes()), (21, 120));
+///
+/// assert!(OffsetDateTime::try_from(DateTime::from_ts(253_402_300_800)).is_err()); // year 10000
+/// ```
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the wall-clock time is after the year 9999, or the
+/// offset is 26 hours or more, the limits of the `time` crate.
+impl TryFrom<DateTime> for OffsetDateTime {
+    type Error = WTimeError;
+
+    fn try_from(dt: DateTime) -> Result<Self, WTimeError> {
+        let offset = i32::try_from(dt.offset_minutes * 60)
+            .ok()
+            .and_then(|seconds| UtcOffset::from_whole_seconds(seconds).ok())
+            .ok_or(WTimeError::OutOfRange("offset"))?;
+        OffsetDateTime::from_unix_timestamp_nanos(
+            i128::from(dt.ts) * 1_000_000_000 + i128::from(dt.nanos),
+        )
+        .ok()
+        .and_then(|utc| utc.checked_to_offset(offset))
+        .ok_or(WTimeError::OutOfRange("timestamp"))
+    }
+}
+
+/// Converts from the `time` crate's date-time, keeping its offset.
+///
+/// ### Example
+///
+/// ```
+/// use time::macros::datetime;
+/// use wtime::datetime::DateTime;
+///
+/// let odt = datetime!(2024-10-15 04:11:09 +09:00);
+/// assert_eq!(DateTime::try_from(odt), Ok(DateTime::new(1728933069, 0, 540)));
+/// assert!(DateTime::try_from(datetime!(1969-07-20 20:17 UTC)).is_err());
+/// ```
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for instants before the UNIX epoch, which a `u64`
+/// cannot represent.
+impl TryFrom<OffsetDateTime> for DateTime {
+    type Error = WTimeError;
+
+    fn try_from(odt: OffsetDateTime) -> Result<Self, WTimeError> {
+        Ok(DateTime::new(
+            from_time(odt)?,
+            u64::from(odt.nanosecond()),
+            i64::from(odt.offset().whole_minutes()),
+        ))
+    }
+}
+
+/// ### to_time(ts: u64) -> Result<OffsetDateTime, WTimeError>
+///
+/// Converts a UNIX timestamp into the `time` crate's UTC date-time.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::time::to_time;
+///
+/// let odt = to_time(1728933069).unwrap();
+/// assert_eq!((odt.year(), u8::from(odt.month()), odt.day()), (2024, 10, 14));
+/// assert!(odt.offset().is_utc());
+/// ```
+///
+/// ### Returns
+///
+/// Returns an `OffsetDateTime` in UTC.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if `ts` is after the year 9999.
+///
+/// <small>End Fun Doc</small>
+pub fn to_time(ts: u64) -> Result<OffsetDateTime, WTimeError> {
+    OffsetDateTime::try_from(DateTime::from_ts(ts))
+}
+
+/// ### from_time(odt: OffsetDateTime) -> Result<u64, WTimeError>
+///
+/// Converts the `time` crate's date-time in any offset into a UNIX timestamp in seconds.
+///
+/// ### Example
+///
+/// ```
+/// use time::macros::datetime;
+/// use wtime::time::from_time;
+///
+/// assert_eq!(from_time(datetime!(2024-10-14 21:11:09 +02:00)), Ok(1728933069));
+/// assert!(from_time(datetime!(1969-12-31 23:59:59 UTC)).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the whole seconds since the UNIX epoch.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for instants before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+pub fn from_time(odt: OffsetDateTime) -> Result<u64, WTimeError> {
+    u64::try_from(odt.unix_timestamp()).map_err(|_| WTimeError::OutOfRange("timestamp"))
+}
+
+/// ### to_time_date(date: (u64, u64, u64)) -> Result<Date, WTimeError>
+///
+/// Converts a `(year, month, day)` tuple, as used throughout the `calc` module, into the
+/// `time` crate's `Date`.
+///
+/// ### Example
+///
+/// ```
+/// use time::{Month, Weekday};
+/// use wtime::time::to_time_date;
+///
+/// let date = to_time_date((2024, 10, 14)).unwrap();
+/// assert_eq!((date.month(), date.weekday()), (Month::October, Weekday::Monday));
+/// assert!(to_time_date((2023, 2, 29)).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the corresponding `Date`.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] if the date does not exist or its year is after 9999.
+///
+/// <small>End Fun Doc</small>
+pub fn to_time_date((year, month, day): (u64, u64, u64)) -> Result<Date, WTimeError> {
+    if !is_valid_date(year, month, day) || year > 9999 {
+        return Err(WTimeError::OutOfRange("date"));
+    }
+    let month = Month::try_from(month as u8).map_err(|_| WTimeError::OutOfRange("date"))?;
+    Date::from_calendar_date(year as i32, month, day as u8)
+        .map_err(|_| WTimeError::OutOfRange("date"))
+}
+
+/// ### from_time_date(date: Date) -> Result<(u64, u64, u64), WTimeError>
+///
+/// Converts the `time` crate's `Date` into a `(year, month, day)` tuple, as used throughout
+/// the `calc` module, for example to compute its week number with `calc_week`.
+///
+/// ### Example
+///
+/// ```
+/// use time::macros::date;
+/// use wtime::calc::calc_week;
+/// use wtime::time::from_time_date;
+///
+/// let date = from_time_date(date!(2024-10-14)).unwrap();
+/// assert_eq!(date, (2024, 10, 14));
+/// assert_eq!(calc_week(date), 42);
+/// assert!(from_time_date(date!(-0044-03-15)).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the date as a tuple.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] for dates before the year 0, which the tuple cannot
+/// represent.
+///
+/// <small>End Fun Doc</small>
+pub fn from_time_date(date: Date) -> Result<(u64, u64, u64), WTimeError> {
+    let year = u64::try_from(date.year()).map_err(|_| WTimeError::OutOfRange("year"))?;
+    Ok((
+        year,
+        u64::from(u8::from(date.month())),
+        u64::from(date.day()),
+    ))
+}
@@ -0,0 +1,226 @@
+use super::{
+    calc::duration_since,
+    monotonic::{mono_now, Deadline},
+    schedule::CronExpr,
+};
+use ::futures_core::Stream;
+use ::tokio::time::{self, error::Elapsed, Interval, MissedTickBehavior};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// The longest single sleep, so that changes to the system time are noticed.
+const MAX_NAP: Duration = Duration::from_secs(1);
+
+/// ### sleep_until(target_ts: u64)
+///
+/// Waits asynchronously until the system clock reaches a UNIX timestamp in seconds.
+///
+/// This is the async counterpart of `sleep::sleep_until`: the task naps for at most one
+/// second at a time on Tokio's timer and recomputes the remaining time after each nap, so it
+/// still wakes when the wall clock reaches `target_ts` after the system time is adjusted. A
+/// timestamp that has already passed returns at once. It must run inside a Tokio runtime
+/// with the time driver enabled.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tokio::sleep_until;
+/// use wtime::utc::utc_ts_sec;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .build()
+///     .unwrap();
+///
+/// runtime.block_on(async {
+///     let next_second = utc_ts_sec() + 1;
+///     sleep_until(next_second).await;
+///     assert!(utc_ts_sec() >= next_second);
+///
+///     sleep_until(1728933069).await; // in the past: returns immediately
+/// });
+/// ```
+///
+/// <small>End Fun Doc</small>
+pub async fn sleep_until(target_ts: u64) {
+    let target = Duration::from_secs(target_ts);
+    loop {
+        let now = duration_since();
+        if now >= target {
+            return;
+        }
+        time::sleep((target - now).min(MAX_NAP)).await;
+    }
+}
+
+/// ### sleep_until_next(cron: &CronExpr) -> u64
+///
+/// Waits asynchronously until the next minute matched by a cron expression.
+///
+/// The target is the first matching minute strictly after the current one, in UTC, as
+/// given by [`CronExpr::next_after`]; the wait is [`sleep_until`]. Calling it in a loop
+/// runs a job on the cron schedule without a separate scheduler task.
+///
+/// ### Example
+///
+/// ```no_run
+/// use wtime::schedule::CronExpr;
+/// use wtime::tokio::sleep_until_next;
+///
+/// # async fn rotate_logs() {}
+/// # async fn run() -> Result<(), wtime::error::WTimeError> {
+/// let nightly = CronExpr::parse("30 2 * * *")?;
+/// loop {
+///     let due = sleep_until_next(&nightly).await;
+///     println!("rotating logs scheduled for {due}");
+///     rotate_logs().await;
+/// }
+/// # }
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp that was waited for.
+///
+/// <small>End Fun Doc</small>
+pub async fn sleep_until_next(cron: &CronExpr) -> u64 {
+    let target = cron.next_after(duration_since().as_secs());
+    sleep_until(target).await;
+    target
+}
+
+/// ### timeout_at<F: Future>(deadline: Deadline, future: F) -> Result<F::Output, Elapsed>
+///
+/// Runs a future until it completes or a [`Deadline`] is reached, whichever comes first.
+///
+/// The same `Deadline` can bound the blocking and async parts of an operation: a request
+/// handler creates it once and passes it down to both. A deadline too far away for the
+/// platform's clock never fires. It must run inside a Tokio runtime with the time driver
+/// enabled.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::monotonic::Deadline;
+/// use wtime::tokio::timeout_at;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .build()
+///     .unwrap();
+///
+/// runtime.block_on(async {
+///     let deadline = Deadline::after(Duration::from_millis(50));
+///
+///     let quick = timeout_at(deadline, async { 42 }).await;
+///     assert_eq!(quick, Ok(42));
+///
+///     let slow = timeout_at(deadline, tokio::time::sleep(Duration::from_secs(5))).await;
+///     assert!(slow.is_err());
+///     assert!(deadline.expired());
+/// });
+/// ```
+///
+/// ### Errors
+///
+/// Returns Tokio's [`Elapsed`] error if the deadline is reached before the future
+/// completes. The future is dropped.
+///
+/// <small>End Fun Doc</small>
+pub async fn timeout_at<F: Future>(deadline: Deadline, future: F) -> Result<F::Output, Elapsed> {
+    match deadline.instant() {
+        Some(at) => time::timeout_at(at.into(), future).await,
+        None => Ok(future.await),
+    }
+}
+
+/// ### Ticker
+///
+/// An endless stream of ticks at a fixed period, the async counterpart of `tick::Ticker`.
+///
+/// Ticks are scheduled at a fixed rate on the monotonic clock, so the schedule does not
+/// drift however long the work between ticks takes. The first tick is due immediately. When
+/// a task falls more than a period behind, the missed ticks are skipped rather than
+/// returned in a burst. A `Ticker` is driven by Tokio's timer and must be created inside a
+/// Tokio runtime with the time driver enabled. Besides [`tick`](Ticker::tick), it
+/// implements `futures_core::Stream`, yielding the instant each tick was scheduled for.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::tokio::Ticker;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread()
+///     .enable_time()
+///     .build()
+///     .unwrap();
+///
+/// runtime.block_on(async {
+///     let period = Duration::from_millis(10);
+///     let mut ticks = Ticker::every(period);
+///
+///     let first = ticks.tick().await;
+///     for n in 1..=3 {
+///         // Scheduled exactly on the grid, whatever the timer granularity
+///         assert_eq!(ticks.tick().await - first, period * n);
+///     }
+/// });
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug)]
+pub struct Ticker {
+    interval: Interval,
+}
+
+impl Ticker {
+    /// Creates a ticker whose first tick is due now and whose later ticks follow every
+    /// `period`.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `period` is zero, or outside a Tokio runtime with the time driver enabled.
+    pub fn every(period: Duration) -> Self {
+        Ticker::starting_at(mono_now(), period)
+    }
+
+    /// Creates a ticker whose first tick is due at `start`. Ticks too far away for the
+    /// platform's clock never fire.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if `period` is zero, or outside a Tokio runtime with the time driver enabled;
+    /// both checks are Tokio's own.
+    pub fn starting_at(start: Instant, period: Duration) -> Self {
+        let mut interval = time::interval_at(start.into(), period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        Ticker { interval }
+    }
+
+    /// Returns the period between ticks.
+    pub fn period(&self) -> Duration {
+        self.interval.period()
+    }
+
+    /// Waits for the next tick and returns the instant it was scheduled for.
+    pub async fn tick(&mut self) -> Instant {
+        self.interval.tick().await.into_std()
+    }
+
+    /// Polls for the next tick, returning the instant it was scheduled for once it is due.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        self.interval.poll_tick(cx).map(|at| at.into_std())
+    }
+}
+
+impl Stream for Ticker {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Instant>> {
+        self.get_mut().poll_tick(cx).map(Some)
+    }
+}
@@ -0,0 +1,115 @@
+use super::{
+    calc::duration_since,
+    clock::{since_epoch, Clock},
+};
+use std::time::Duration;
+
+/// ### Expiry
+///
+/// The instant at which a cache entry, session or token stops being valid.
+///
+/// An `Expiry` holds a UTC wall-clock instant, so it can be stored as a UNIX timestamp with
+/// [`expires_at`](Expiry::expires_at) and shared between processes and machines. Sliding
+/// expiration, where each use keeps an entry alive for longer, is done with
+/// [`extend`](Expiry::extend). Every method that reads the time has a `_with_clock` variant
+/// for deterministic tests.
+///
+/// Because the wall clock can be adjusted, an `Expiry` may end early or late when the system
+/// time jumps; timeouts that only live within one process are better measured with a
+/// [`Deadline`](crate::monotonic::Deadline) on the monotonic clock.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::FixedClock;
+/// use wtime::ttl::Expiry;
+///
+/// let session = Expiry::in_secs(1800);
+/// assert!(!session.is_expired());
+/// assert!(session.remaining() <= Duration::from_secs(1800));
+///
+/// let mut clock = FixedClock::from_ts(1728933069);
+/// let mut token = Expiry::in_secs_with_clock(60, &clock);
+/// assert_eq!(token.expires_at(), 1728933129);
+///
+/// clock.advance(Duration::from_secs(45));
+/// assert_eq!(token.remaining_with_clock(&clock), Duration::from_secs(15));
+///
+/// token.extend(60);
+/// clock.advance(Duration::from_secs(45));
+/// assert!(!token.is_expired_with_clock(&clock));
+///
+/// clock.advance(Duration::from_secs(30));
+/// assert!(token.is_expired_with_clock(&clock));
+/// assert_eq!(token.remaining_with_clock(&clock), Duration::ZERO);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Expiry {
+    /// The expiry instant as time since the UNIX epoch.
+    at: Duration,
+}
+
+impl Expiry {
+    /// Creates an expiry `secs` seconds from now.
+    pub fn in_secs(secs: u64) -> Self {
+        Expiry::after(duration_since(), Duration::from_secs(secs))
+    }
+
+    /// Creates an expiry `secs` seconds after the time of `clock`.
+    pub fn in_secs_with_clock<C: Clock>(secs: u64, clock: &C) -> Self {
+        Expiry::after(since_epoch(clock), Duration::from_secs(secs))
+    }
+
+    /// Creates an expiry `ttl` from now.
+    pub fn in_duration(ttl: Duration) -> Self {
+        Expiry::after(duration_since(), ttl)
+    }
+
+    /// Creates an expiry at a UNIX timestamp in seconds, such as one read back from storage.
+    pub fn at(ts: u64) -> Self {
+        Expiry {
+            at: Duration::from_secs(ts),
+        }
+    }
+
+    /// Returns the expiry instant as a UNIX timestamp in seconds, rounded down.
+    pub fn expires_at(&self) -> u64 {
+        self.at.as_secs()
+    }
+
+    /// Returns `true` once the expiry instant has been reached.
+    pub fn is_expired(&self) -> bool {
+        duration_since() >= self.at
+    }
+
+    /// Returns `true` once the time of `clock` has reached the expiry instant.
+    pub fn is_expired_with_clock<C: Clock>(&self, clock: &C) -> bool {
+        since_epoch(clock) >= self.at
+    }
+
+    /// Returns the time left before the expiry, or zero once it has expired.
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_sub(duration_since())
+    }
+
+    /// Returns the time left before the expiry by the time of `clock`, or zero once it has
+    /// expired.
+    pub fn remaining_with_clock<C: Clock>(&self, clock: &C) -> Duration {
+        self.at.saturating_sub(since_epoch(clock))
+    }
+
+    /// Pushes the expiry `secs` seconds later, whether or not it has already passed.
+    pub fn extend(&mut self, secs: u64) {
+        self.at = self.at.saturating_add(Duration::from_secs(secs));
+    }
+
+    /// Adds `ttl` to `now`, saturating at the largest `Duration`.
+    fn after(now: Duration, ttl: Duration) -> Self {
+        Expiry {
+            at: now.saturating_add(ttl),
+        }
+    }
+}
@@ -1,12 +1,36 @@
-use chrono::Local;
+#[cfg(feature = "tzdb")]
+use super::calc::calc_timestamp_signed;
+use super::calc::{
+    civil_from_days, days_from_civil, duration_since, month_length, write_offset,
+    write_offset_suffix,
+};
+use super::error::WTimeError;
+#[cfg(feature = "tzdb")]
+use super::{
+    clock::{since_epoch, Clock},
+    datetime::DateTime,
+};
+#[cfg(feature = "tzdb")]
+use chrono::Offset;
+#[cfg(any(feature = "tzdb", feature = "chrono-fallback"))]
+use chrono::TimeZone;
+use std::{env, sync::RwLock};
+#[cfg(unix)]
+use std::{
+    ffi::{OsStr, OsString},
+    fs,
+    path::Path,
+    sync::Mutex,
+    time::SystemTime,
+};
 
 /// ### tz_string()
 ///
 /// Retrieves the current local timezone offset as a string.
 ///
-/// This function obtains the local timezone offset from the `chrono` crate and formats it
-/// as a string. It provides a human-readable representation of the timezone offset, which
-/// can be useful for logging or displaying the user's local time settings.
+/// This function obtains the local timezone offset from [`tz_offset_minutes`] and formats it
+/// as `+HH:MM` or `-HH:MM`. It provides a human-readable representation of the timezone
+/// offset, which can be useful for logging or displaying the user's local time settings.
 ///
 /// ### Example
 ///
@@ -15,6 +39,12 @@ use chrono::Local;
 ///
 /// let offset_string = tz_string();
 /// println!("Current timezone offset: {}", offset_string);
+///
+/// // India, UTC+05:30 (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "IST-5:30");
+///     assert_eq!(tz_string(), "+05:30");
+/// }
 /// ```
 ///
 /// ### Returns
@@ -23,17 +53,19 @@ use chrono::Local;
 ///
 /// <small>End Fun Doc</small>
 pub fn tz_string() -> String {
-    let offset_string = Local::now().offset().to_string();
-    format!("{}", offset_string)
+    let mut offset = String::with_capacity(6);
+    // Writing into a `String` never fails
+    let _ = write_offset(&mut offset, tz_offset_minutes(), ":");
+    offset
 }
 
 /// ### tz_number()
 ///
-/// Retrieves the local timezone offset as an `i64` in hours.
+/// Retrieves the local timezone offset as an `i64` in whole hours.
 ///
-/// This function calculates the local timezone offset and returns it as an integer value
-/// representing the number of hours offset from UTC. This can be useful for calculating
-/// time differences or adjusting timestamps to local time.
+/// This function returns the number of whole hours the local timezone is offset from UTC.
+/// Zones with a sub-hour offset, such as India (UTC+05:30) or Nepal (UTC+05:45), are
+/// truncated towards zero; use [`tz_offset_minutes`] wherever the exact offset matters.
 ///
 /// ### Example
 ///
@@ -47,21 +79,1058 @@ pub fn tz_string() -> String {
 /// ### Returns
 ///
 /// Returns the local timezone offset as an `i64` representing the total number of hours from UTC.
-/// If there is an error in parsing, it defaults to returning `0`.
 ///
 /// <small>End Fun Doc</small>
 pub fn tz_number() -> i64 {
-    // Get the local timezone offset as a string
-    let offset_str = Local::now().offset().to_string();
-    // Split the string into hours and minutes
-    let parts: Vec<&str> = offset_str.split(':').collect();
-    if parts.len() == 2 {
-        // Get the sign for the offset
-        let sign = if parts[0].starts_with('+') { 1 } else { -1 };
-        // Parse the hours (skip the sign)
-        if let Ok(hours) = parts[0][1..].parse::<i64>() {
-            return sign * hours; // Return the total offset in hours
+    tz_offset_minutes() / 60
+}
+
+/// ### tz_offset_minutes() -> i64
+///
+/// Retrieves the local timezone offset in minutes.
+///
+/// Unlike [`tz_number`], this keeps the sub-hour part of the offset, so UTC+05:30 is `330`,
+/// UTC+05:45 is `345` and UTC-03:30 is `-210`. The `local` module uses this offset for all
+/// of its conversions. See [`offset_at`] for how the local zone is detected.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{tz_number, tz_offset_minutes};
+///
+/// let offset = tz_offset_minutes();
+/// println!("Current timezone offset: {} minutes", offset);
+/// assert_eq!(offset / 60, tz_number());
+/// ```
+///
+/// ### Returns
+///
+/// Returns the local timezone offset as an `i64` number of minutes east of UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn tz_offset_minutes() -> i64 {
+    offset_at(duration_since().as_secs())
+}
+
+/// ### offset_at(ts: u64) -> i64
+///
+/// Retrieves the local timezone offset in minutes in effect at a given UNIX timestamp.
+///
+/// [`tz_offset_minutes`] only reports the offset right now. This function applies the local
+/// zone's daylight-saving rules to `ts` instead, so historical and future local times are
+/// shifted by the offset that was, or will be, in force at that instant.
+///
+/// The local zone is detected without third-party crates:
+///
+/// - On Unix, from the `TZ` environment variable, either a zone name such as
+///   `Europe/Berlin` looked up in the system's tz database (`TZDIR`, or
+///   `/usr/share/zoneinfo`), a file path, or a POSIX rule such as `EST5EDT,M3.2.0,M11.1.0`.
+///   Without `TZ`, the zone comes from `/etc/localtime`, and an empty `TZ` means UTC.
+/// - On Windows, from the system time zone settings, including historical rules.
+/// - On `wasm32-unknown-unknown` with the `wasm` feature, from JavaScript's `Date`.
+///
+/// Where the zone cannot be detected, UTC is used, unless the `chrono-fallback` feature
+/// hands the lookup to chrono instead.
+///
+/// A fixed offset set with [`set_override`], or else given by the `WTIME_TZ` environment
+/// variable, takes precedence over the detected zone.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::offset_at;
+///
+/// // New York: UTC-04:00 in summer, UTC-05:00 in winter (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5EDT,M3.2.0,M11.1.0");
+///     assert_eq!(offset_at(1720000000), -240); // 2024-07-03
+///     assert_eq!(offset_at(1736000000), -300); // 2025-01-04
+/// }
+/// println!("Offset at the UNIX epoch: {} minutes", offset_at(0));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the offset as an `i64` number of minutes east of UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn offset_at(ts: u64) -> i64 {
+    if let Some(offset_minutes) = override_minutes() {
+        return offset_minutes;
+    }
+    let ts = ts.min(i64::MAX as u64) as i64;
+    native_offset(ts)
+        .or_else(|| fallback_offset(ts))
+        .unwrap_or(0)
+        / 60
+}
+
+/// ### tz_offset_string_for(ts: u64) -> String
+///
+/// Formats the local timezone offset in effect at a given UNIX timestamp as an RFC 3339
+/// offset suffix.
+///
+/// The offset comes from [`offset_at`], so daylight-saving time is applied. Unlike
+/// [`tz_string`], UTC is written as `Z`, which makes the result ready to append to a
+/// timestamp; every other offset is `+HH:MM` or `-HH:MM`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::tz_offset_string_for;
+///
+/// // New York: UTC-04:00 in summer, UTC-05:00 in winter (the TZ variable is read on Unix)
+/// if cfg!(unix) {
+///     std::env::set_var("TZ", "EST5EDT,M3.2.0,M11.1.0");
+///     assert_eq!(tz_offset_string_for(1720000000), "-04:00"); // 2024-07-03
+///     assert_eq!(tz_offset_string_for(1736000000), "-05:00"); // 2025-01-04
+///
+///     std::env::set_var("TZ", "IST-5:30");
+///     assert_eq!(tz_offset_string_for(1728933069), "+05:30");
+///
+///     std::env::set_var("TZ", "UTC0");
+///     assert_eq!(tz_offset_string_for(1728933069), "Z");
+/// }
+/// ```
+///
+/// ### Returns
+///
+/// Returns `"Z"` for UTC, and the offset as `+HH:MM` or `-HH:MM` otherwise.
+///
+/// <small>End Fun Doc</small>
+pub fn tz_offset_string_for(ts: u64) -> String {
+    let mut offset = String::with_capacity(6);
+    // Writing into a `String` never fails
+    let _ = write_offset_suffix(&mut offset, offset_at(ts));
+    offset
+}
+
+/// The offset in minutes set by [`set_override`].
+static OVERRIDE: RwLock<Option<i64>> = RwLock::new(None);
+
+/// ### set_override(offset_minutes: i64) -> Result<(), WTimeError>
+///
+/// Forces the local timezone to a fixed UTC offset in minutes, regardless of the host's
+/// configuration.
+///
+/// Servers often run with whatever zone the host or container image happens to have. Once
+/// an override is set, [`offset_at`], [`tz_offset_minutes`] and every function of the
+/// `local` module use it for the whole process, until [`clear_override`] is called.
+///
+/// Without a call to `set_override`, the `WTIME_TZ` environment variable is consulted with
+/// the same effect. It holds `Z` or `UTC`, or an offset such as `+05:30`, `-0700` or `+03`;
+/// a value that does not parse is ignored.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::local::local_ts_sec;
+/// use wtime::tz::{clear_override, set_override, tz_offset_minutes, tz_string};
+/// use wtime::utc::utc_ts_sec;
+///
+/// set_override(330).unwrap();
+/// assert_eq!(tz_offset_minutes(), 330);
+/// assert_eq!(tz_string(), "+05:30");
+/// assert!(local_ts_sec() >= utc_ts_sec() + 330 * 60);
+///
+/// assert!(set_override(24 * 60).is_err());
+///
+/// clear_override();
+/// std::env::set_var("WTIME_TZ", "-07:00");
+/// assert_eq!(tz_offset_minutes(), -420);
+/// ```
+///
+/// ### Errors
+///
+/// Returns `WTimeError::OutOfRange` if the offset is 24 hours or more either way.
+///
+/// <small>End Fun Doc</small>
+pub fn set_override(offset_minutes: i64) -> Result<(), WTimeError> {
+    if offset_minutes.unsigned_abs() >= 24 * 60 {
+        return Err(WTimeError::OutOfRange("offset"));
+    }
+    // The lock only guards a `Copy` value, so a poisoned lock still holds a valid offset
+    *OVERRIDE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(offset_minutes);
+    Ok(())
+}
+
+/// ### clear_override()
+///
+/// Removes the offset set by [`set_override`], so that the local timezone is taken from the
+/// `WTIME_TZ` environment variable or detected from the host again.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{clear_override, get_override, set_override};
+///
+/// set_override(-210).unwrap();
+/// assert_eq!(get_override(), Some(-210));
+///
+/// clear_override();
+/// assert_eq!(get_override(), None);
+/// ```
+///
+/// <small>End Fun Doc</small>
+pub fn clear_override() {
+    *OVERRIDE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// ### get_override() -> Option<i64>
+///
+/// Retrieves the offset set by [`set_override`].
+///
+/// The `WTIME_TZ` environment variable is not reflected here.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{get_override, set_override};
+///
+/// assert_eq!(get_override(), None);
+/// set_override(345).unwrap();
+/// assert_eq!(get_override(), Some(345));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the override in minutes east of UTC, or `None` if none is set.
+///
+/// <small>End Fun Doc</small>
+pub fn get_override() -> Option<i64> {
+    *OVERRIDE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Returns the offset in minutes from [`set_override`], or else from `WTIME_TZ`.
+fn override_minutes() -> Option<i64> {
+    get_override().or_else(|| parse_override(env::var("WTIME_TZ").ok()?.trim()))
+}
+
+/// Parses `Z`, `UTC`, `+HH`, `+HHMM` or `+HH:MM` (or with `-`) into minutes.
+fn parse_override(s: &str) -> Option<i64> {
+    if s.eq_ignore_ascii_case("z") || s.eq_ignore_ascii_case("utc") {
+        return Some(0);
+    }
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let digits = match s[1..].split_once(':') {
+        Some((hours, minutes)) if hours.len() == 2 && minutes.len() == 2 => {
+            hours.to_owned() + minutes
         }
+        Some(_) => return None,
+        None => s[1..].to_owned(),
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i64>().ok()?, 0),
+        4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+        _ => return None,
+    };
+    (hours < 24 && minutes < 60).then_some(sign * (hours * 60 + minutes))
+}
+
+/// Returns the local offset in seconds at `ts` from the `TZ` variable or `/etc/localtime`.
+#[cfg(unix)]
+fn native_offset(ts: i64) -> Option<i64> {
+    type Key = (Option<OsString>, Option<SystemTime>);
+    static CACHE: Mutex<Option<(Key, Option<ZoneRules>)>> = Mutex::new(None);
+
+    // Reload the rules when `TZ` changes, or `/etc/localtime` when `TZ` is unset
+    let tz = env::var_os("TZ");
+    let modified = match tz {
+        Some(_) => None,
+        None => fs::symlink_metadata("/etc/localtime")
+            .and_then(|metadata| metadata.modified())
+            .ok(),
+    };
+    let key = (tz, modified);
+
+    // The lock only guards a cache, so a poisoned lock still holds usable rules
+    let mut cache = CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if cache.as_ref().map(|(cached, _)| cached) != Some(&key) {
+        let rules = load_rules(key.0.as_deref());
+        *cache = Some((key, rules));
+    }
+    cache.as_ref()?.1.as_ref().map(|rules| rules.offset_at(ts))
+}
+
+/// Reads the rules named by the value of `TZ`, following glibc: a file first, then a POSIX
+/// rule.
+#[cfg(unix)]
+fn load_rules(tz: Option<&OsStr>) -> Option<ZoneRules> {
+    let Some(tz) = tz else {
+        return ZoneRules::from_tzif(&fs::read("/etc/localtime").ok()?);
+    };
+    let tz = tz.to_str()?;
+    if tz.is_empty() {
+        return Some(ZoneRules::default());
+    }
+    let tz = tz.strip_prefix(':').unwrap_or(tz);
+    let path = match env::var_os("TZDIR") {
+        Some(dir) => Path::new(&dir).join(tz),
+        None => Path::new("/usr/share/zoneinfo").join(tz),
+    };
+    fs::read(path)
+        .ok()
+        .and_then(|data| ZoneRules::from_tzif(&data))
+        .or_else(|| PosixRule::parse(tz).map(ZoneRules::from))
+}
+
+/// Returns the local offset in seconds at `ts` from the Windows time zone settings.
+#[cfg(windows)]
+fn native_offset(ts: i64) -> Option<i64> {
+    // SAFETY: all-zero bytes are a valid `Win32TimeZone`, which the call then fills in
+    let mut zone: Win32TimeZone = unsafe { std::mem::zeroed() };
+    // SAFETY: `zone` is a writable `DYNAMIC_TIME_ZONE_INFORMATION`
+    if unsafe { GetDynamicTimeZoneInformation(&mut zone) } == u32::MAX {
+        return None;
+    }
+
+    let days = ts.div_euclid(86_400);
+    let secs_of_day = ts.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    // `SYSTEMTIME` covers the years 1601 to 30827; elsewhere the standard offset applies
+    if !(1601..=30827).contains(&year) {
+        return Some(-i64::from(zone.bias) * 60);
     }
-    0 // Default return value if something goes wrong
+    let utc = Win32SystemTime {
+        year: year as u16,
+        month: month as u16,
+        day_of_week: 0,
+        day: day as u16,
+        hour: (secs_of_day / 3600) as u16,
+        minute: (secs_of_day / 60 % 60) as u16,
+        second: (secs_of_day % 60) as u16,
+        milliseconds: 0,
+    };
+    let mut local = Win32SystemTime::default();
+    // SAFETY: the three pointers are valid for the duration of the call
+    if unsafe { SystemTimeToTzSpecificLocalTimeEx(&zone, &utc, &mut local) } == 0 {
+        return None;
+    }
+
+    let local_days = days_from_civil(
+        i64::from(local.year),
+        u64::from(local.month),
+        u64::from(local.day),
+    );
+    let local_secs_of_day =
+        i64::from(local.hour) * 3600 + i64::from(local.minute) * 60 + i64::from(local.second);
+    Some((local_days - days) * 86_400 + local_secs_of_day - secs_of_day)
+}
+
+/// The Win32 `SYSTEMTIME` structure.
+#[cfg(windows)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct Win32SystemTime {
+    year: u16,
+    month: u16,
+    day_of_week: u16,
+    day: u16,
+    hour: u16,
+    minute: u16,
+    second: u16,
+    milliseconds: u16,
+}
+
+/// The Win32 `DYNAMIC_TIME_ZONE_INFORMATION` structure.
+#[cfg(windows)]
+#[repr(C)]
+struct Win32TimeZone {
+    bias: i32,
+    standard_name: [u16; 32],
+    standard_date: Win32SystemTime,
+    standard_bias: i32,
+    daylight_name: [u16; 32],
+    daylight_date: Win32SystemTime,
+    daylight_bias: i32,
+    time_zone_key_name: [u16; 128],
+    dynamic_daylight_time_disabled: u8,
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetDynamicTimeZoneInformation(info: *mut Win32TimeZone) -> u32;
+    fn SystemTimeToTzSpecificLocalTimeEx(
+        info: *const Win32TimeZone,
+        utc: *const Win32SystemTime,
+        local: *mut Win32SystemTime,
+    ) -> i32;
+}
+
+/// Returns the local offset in seconds at `ts` from JavaScript's `Date`.
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+fn native_offset(ts: i64) -> Option<i64> {
+    let date = js_sys::Date::new(&(ts as f64 * 1000.0).into());
+    // `getTimezoneOffset` counts minutes west of UTC
+    Some(-(date.get_timezone_offset() as i64) * 60)
+}
+
+/// Reports that the local zone cannot be detected natively on this target.
+#[cfg(not(any(
+    unix,
+    windows,
+    all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")
+)))]
+fn native_offset(_ts: i64) -> Option<i64> {
+    None
+}
+
+/// Returns chrono's local offset in seconds at `ts`.
+#[cfg(feature = "chrono-fallback")]
+fn fallback_offset(ts: i64) -> Option<i64> {
+    Some(
+        chrono::Local
+            .offset_from_utc_datetime(&naive_utc(ts as u64))
+            .local_minus_utc() as i64,
+    )
+}
+
+/// Without the `chrono-fallback` feature there is no fallback.
+#[cfg(not(feature = "chrono-fallback"))]
+fn fallback_offset(_ts: i64) -> Option<i64> {
+    None
+}
+
+/// The UTC offsets of a time zone, read from a TZif file or a POSIX `TZ` rule. The default
+/// is UTC.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct ZoneRules {
+    /// The offset in seconds before the first transition.
+    initial: i64,
+    /// The UNIX timestamps at which the offset changes, with the offset in seconds from then.
+    transitions: Vec<(i64, i64)>,
+    /// The rule after the last transition.
+    rule: Option<PosixRule>,
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+impl ZoneRules {
+    /// Parses a TZif file (RFC 8536), preferring the 64-bit data of version 2 and later.
+    fn from_tzif(data: &[u8]) -> Option<ZoneRules> {
+        let number = |bytes: &[u8]| bytes.iter().fold(0u64, |n, &b| n << 8 | u64::from(b));
+        let counts = |data: &[u8]| -> Option<[usize; 6]> {
+            let mut counts = [0; 6];
+            for (i, count) in counts.iter_mut().enumerate() {
+                *count = number(data.get(20 + 4 * i..24 + 4 * i)?) as usize;
+            }
+            Some(counts)
+        };
+
+        if data.get(..4)? != b"TZif" {
+            return None;
+        }
+        let version = *data.get(4)?;
+        let (data, time_size) = if version >= b'2' {
+            // Skip the 32-bit data of version 1
+            let [ut, std, leap, times, types, chars] = counts(data)?;
+            (
+                data.get(44 + times * 5 + types * 6 + chars + leap * 8 + std + ut..)?,
+                8,
+            )
+        } else {
+            (data, 4)
+        };
+        if data.get(..4)? != b"TZif" {
+            return None;
+        }
+
+        let [ut, std, leap, times, types, chars] = counts(data)?;
+        let mut pos = 44;
+        let mut take = |len: usize| {
+            pos += len;
+            data.get(pos - len..pos)
+        };
+        let at = take(times * time_size)?;
+        let indices = take(times)?;
+        let infos = take(types * 6)?;
+        take(chars + leap * (time_size + 4) + std + ut)?;
+        let footer = data.get(pos..).unwrap_or_default();
+
+        let offset =
+            |index: usize| Some(number(infos.get(index * 6..index * 6 + 4)?) as u32 as i32);
+        let mut transitions = Vec::with_capacity(times);
+        for (at, &index) in at.chunks_exact(time_size).zip(indices) {
+            let at = match time_size {
+                4 => number(at) as u32 as i32 as i64,
+                _ => number(at) as i64,
+            };
+            transitions.push((at, i64::from(offset(index as usize)?)));
+        }
+
+        // The footer of version 2 and later is a POSIX rule between newlines
+        let rule = match footer {
+            [b'\n', rest @ ..] => rest
+                .split(|&b| b == b'\n')
+                .next()
+                .and_then(|rule| std::str::from_utf8(rule).ok())
+                .and_then(PosixRule::parse),
+            _ => None,
+        };
+
+        Some(ZoneRules {
+            initial: i64::from(offset(0)?),
+            transitions,
+            rule,
+        })
+    }
+
+    /// Returns the offset in seconds at the UNIX timestamp `ts`.
+    fn offset_at(&self, ts: i64) -> i64 {
+        let passed = self.transitions.partition_point(|&(at, _)| at <= ts);
+        match &self.rule {
+            Some(rule) if passed == self.transitions.len() => rule.offset_at(ts),
+            _ if passed == 0 => self.initial,
+            _ => self.transitions[passed - 1].1,
+        }
+    }
+}
+
+impl From<PosixRule> for ZoneRules {
+    fn from(rule: PosixRule) -> Self {
+        ZoneRules {
+            initial: rule.std_offset,
+            transitions: Vec::new(),
+            rule: Some(rule),
+        }
+    }
+}
+
+/// The length of the 400-year Gregorian cycle in seconds, 146,097 days.
+#[cfg_attr(not(unix), allow(dead_code))]
+const GREGORIAN_CYCLE_SECS: i64 = 146_097 * 86_400;
+
+/// A POSIX `TZ` rule such as `CET-1CEST,M3.5.0,M10.5.0/3`, with offsets in seconds east of
+/// UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct PosixRule {
+    std_offset: i64,
+    dst: Option<DstRule>,
+}
+
+/// The daylight saving part of a [`PosixRule`], with the local times of its transitions in
+/// seconds after midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct DstRule {
+    offset: i64,
+    start: (RuleDate, i64),
+    end: (RuleDate, i64),
+}
+
+/// The day of a daylight saving transition in a [`PosixRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(unix), allow(dead_code))]
+enum RuleDate {
+    /// `Jn`: day 1-365, never counting February 29.
+    Julian(i64),
+    /// `n`: day 0-365, counting February 29.
+    Ordinal(i64),
+    /// `Mm.w.d`: weekday `d` (0 is Sunday) of week `w` of month `m`, where week 5 is the last.
+    Weekday { month: u64, week: i64, weekday: i64 },
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+impl RuleDate {
+    /// Returns the days since 1970-01-01 of the date in `year`.
+    fn days_in(self, year: i64) -> i64 {
+        let jan_1 = days_from_civil(year, 1, 1);
+        match self {
+            RuleDate::Julian(day) => {
+                jan_1 + day - 1 + i64::from(day >= 60 && month_length(year, 2) == 29)
+            }
+            RuleDate::Ordinal(day) => jan_1 + day,
+            RuleDate::Weekday {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = days_from_civil(year, month, 1);
+                // 1970-01-01 was a Thursday, weekday 4
+                let mut day = first + (weekday - (first + 4)).rem_euclid(7) + 7 * (week - 1);
+                if day >= first + month_length(year, month) as i64 {
+                    day -= 7;
+                }
+                day
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(unix), allow(dead_code))]
+impl PosixRule {
+    /// Parses a rule, returning `None` for anything POSIX and RFC 8536 do not allow.
+    fn parse(rule: &str) -> Option<PosixRule> {
+        let mut s = rule.as_bytes();
+        skip_name(&mut s)?;
+        let std_offset = -parse_time(&mut s)?;
+        if s.is_empty() {
+            return Some(PosixRule {
+                std_offset,
+                dst: None,
+            });
+        }
+
+        skip_name(&mut s)?;
+        let offset = match s.first() {
+            None | Some(b',') => std_offset + 3600,
+            Some(_) => -parse_time(&mut s)?,
+        };
+        // Without transitions, the United States rules apply, as in glibc
+        let (start, end) = match s.split_first() {
+            None => (
+                (
+                    RuleDate::Weekday {
+                        month: 3,
+                        week: 2,
+                        weekday: 0,
+                    },
+                    7200,
+                ),
+                (
+                    RuleDate::Weekday {
+                        month: 11,
+                        week: 1,
+                        weekday: 0,
+                    },
+                    7200,
+                ),
+            ),
+            Some((b',', rest)) => {
+                s = rest;
+                let start = parse_transition(&mut s)?;
+                s = s.strip_prefix(b",")?;
+                (start, parse_transition(&mut s)?)
+            }
+            Some(_) => return None,
+        };
+        if !s.is_empty() {
+            return None;
+        }
+
+        Some(PosixRule {
+            std_offset,
+            dst: Some(DstRule { offset, start, end }),
+        })
+    }
+
+    /// Returns the offset in seconds at the UNIX timestamp `ts`.
+    fn offset_at(&self, ts: i64) -> i64 {
+        let Some(dst) = self.dst else {
+            return self.std_offset;
+        };
+        // The Gregorian calendar, weekdays included, repeats every 400 years, and so do the
+        // transitions; folding `ts` into one cycle keeps the civil math far from overflow
+        let ts = ts.rem_euclid(GREGORIAN_CYCLE_SECS);
+        let (year, _, _) = civil_from_days((ts + self.std_offset).div_euclid(86_400));
+        let start = dst.start.0.days_in(year) * 86_400 + dst.start.1 - self.std_offset;
+        let end = dst.end.0.days_in(year) * 86_400 + dst.end.1 - dst.offset;
+        // On the southern hemisphere daylight saving time spans the new year
+        let in_dst = if start < end {
+            start <= ts && ts < end
+        } else {
+            !(end <= ts && ts < start)
+        };
+        if in_dst {
+            dst.offset
+        } else {
+            self.std_offset
+        }
+    }
+}
+
+/// Skips a zone abbreviation: three or more letters, or any text between `<` and `>`.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn skip_name(s: &mut &[u8]) -> Option<()> {
+    let len = match s.first()? {
+        b'<' => s.iter().position(|&b| b == b'>')? + 1,
+        _ => s.iter().take_while(|b| b.is_ascii_alphabetic()).count(),
+    };
+    if len < 3 {
+        return None;
+    }
+    *s = &s[len..];
+    Some(())
+}
+
+/// Parses an unsigned number, at least one digit.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_number(s: &mut &[u8]) -> Option<i64> {
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    if len == 0 || len > 6 {
+        return None;
+    }
+    let number = s[..len]
+        .iter()
+        .fold(0, |n, &b| n * 10 + i64::from(b - b'0'));
+    *s = &s[len..];
+    Some(number)
+}
+
+/// Parses `[+|-]hh[:mm[:ss]]` into seconds.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_time(s: &mut &[u8]) -> Option<i64> {
+    let sign = match s.first()? {
+        b'-' => -1,
+        _ => 1,
+    };
+    if let [b'+' | b'-', rest @ ..] = *s {
+        *s = rest;
+    }
+    let mut seconds = parse_number(s)? * 3600;
+    for unit in [60, 1] {
+        match s.strip_prefix(b":") {
+            Some(rest) => {
+                *s = rest;
+                seconds += parse_number(s)? * unit;
+            }
+            None => break,
+        }
+    }
+    Some(sign * seconds)
+}
+
+/// Parses a transition `date[/time]`, where the time defaults to 02:00.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_transition(s: &mut &[u8]) -> Option<(RuleDate, i64)> {
+    let date = match s.first()? {
+        b'J' => {
+            *s = &s[1..];
+            RuleDate::Julian(parse_number(s).filter(|day| (1..=365).contains(day))?)
+        }
+        b'M' => {
+            *s = &s[1..];
+            let month = parse_number(s).filter(|month| (1..=12).contains(month))? as u64;
+            *s = s.strip_prefix(b".")?;
+            let week = parse_number(s).filter(|week| (1..=5).contains(week))?;
+            *s = s.strip_prefix(b".")?;
+            let weekday = parse_number(s).filter(|weekday| (0..=6).contains(weekday))?;
+            RuleDate::Weekday {
+                month,
+                week,
+                weekday,
+            }
+        }
+        _ => RuleDate::Ordinal(parse_number(s).filter(|day| (0..=365).contains(day))?),
+    };
+    let time = match s.strip_prefix(b"/") {
+        Some(rest) => {
+            *s = rest;
+            parse_time(s)?
+        }
+        None => 7200,
+    };
+    Some((date, time))
+}
+
+/// Converts a UNIX timestamp into chrono's UTC date-time. Instants beyond chrono's range
+/// (about 262,000 years) are clamped to its upper limit.
+#[cfg(any(feature = "tzdb", feature = "chrono-fallback"))]
+fn naive_utc(ts: u64) -> chrono::NaiveDateTime {
+    chrono::DateTime::from_timestamp(ts.min(i64::MAX as u64) as i64, 0)
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC)
+        .naive_utc()
+}
+
+/// ### Tz
+///
+/// A named IANA time zone, such as `Europe/Berlin` or `America/New_York`.
+///
+/// The zone rules come from a copy of the tz database embedded in the binary, so conversions
+/// work for any zone regardless of the machine's own configuration, and daylight-saving
+/// transitions are applied for every instant. This type requires the `tzdb` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::Tz;
+///
+/// let berlin = Tz::from_name("Europe/Berlin").unwrap();
+/// assert_eq!(berlin.name(), "Europe/Berlin");
+/// assert_eq!(berlin.offset_minutes_at(1728933069), 120); // summer time
+/// assert_eq!(berlin.offset_minutes_at(1704067200), 60); // winter time
+///
+/// assert!(Tz::from_name("Mars/Olympus_Mons").is_err());
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[cfg(feature = "tzdb")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tz(chrono_tz::Tz);
+
+#[cfg(feature = "tzdb")]
+impl Tz {
+    /// Coordinated Universal Time.
+    pub const UTC: Tz = Tz(chrono_tz::Tz::UTC);
+
+    /// Looks up a zone by its IANA name, such as `Asia/Kolkata`. The name is case-sensitive.
+    ///
+    /// Returns [`WTimeError::InvalidFormat`] if the name is not in the tz database.
+    pub fn from_name(name: &str) -> Result<Self, WTimeError> {
+        name.parse().map(Tz).map_err(|_| {
+            WTimeError::InvalidFormat("an IANA time zone name such as `Europe/Berlin`")
+        })
+    }
+
+    /// Returns the IANA name of the zone.
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// Returns the UTC offset in minutes that the zone observes at the UNIX timestamp `ts`.
+    pub fn offset_minutes_at(&self, ts: u64) -> i64 {
+        self.0
+            .offset_from_utc_datetime(&naive_utc(ts))
+            .fix()
+            .local_minus_utc() as i64
+            / 60
+    }
+}
+
+/// ### to_zone(ts: u64, tz: &Tz) -> DateTime
+///
+/// Converts a UNIX timestamp into the wall-clock time of a named time zone.
+///
+/// The offset in effect at `ts`, including daylight saving time, is looked up in the tz
+/// database. The returned [`DateTime`] gives the local date and time through its
+/// [`date`](DateTime::date) and [`time`](DateTime::time) methods. This function requires the
+/// `tzdb` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{to_zone, Tz};
+///
+/// let ts = 1728933069; // 2024-10-14 19:11:09 UTC
+///
+/// let tokyo = to_zone(ts, &Tz::from_name("Asia/Tokyo").unwrap());
+/// assert_eq!(tokyo.date(), (2024, 10, 15));
+/// assert_eq!(tokyo.time(), (4, 11, 9));
+///
+/// let kathmandu = to_zone(ts, &Tz::from_name("Asia/Kathmandu").unwrap());
+/// assert_eq!(kathmandu.to_string(), "2024-10-15T00:56:09+05:45");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`DateTime`] for `ts` carrying the zone's UTC offset at that instant.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tzdb")]
+pub fn to_zone(ts: u64, tz: &Tz) -> DateTime {
+    DateTime::new(ts, 0, tz.offset_minutes_at(ts))
+}
+
+/// ### Disambiguation
+///
+/// What [`convert`] does with a wall-clock time that a daylight-saving transition makes
+/// ambiguous or nonexistent.
+///
+/// When the clocks go back, an hour of wall-clock times occurs twice; when they go forward,
+/// an hour is skipped. For a repeated time, `Earliest` and `Latest` pick the first or the
+/// second occurrence. For a skipped time, they shift it by the length of the gap, to the
+/// wall-clock time before or after it: 02:30 on the night New York springs forward is taken
+/// as 01:30 EST or 03:30 EDT.
+///
+/// <small>End Enum Doc</small>
+#[cfg(feature = "tzdb")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Disambiguation {
+    /// The earlier of the two instants.
+    Earliest,
+    /// The later of the two instants.
+    Latest,
+    /// An error instead of a guess.
+    Reject,
+}
+
+/// ### convert(year: u64, month: u64, day: u64, hour: u64, minute: u64, from: &Tz, to: &Tz, policy: Disambiguation) -> Result<DateTime, WTimeError>
+///
+/// Converts a wall-clock time in one named time zone into the wall-clock time of another.
+///
+/// The local time in `from` is first resolved to an instant, following the zone's
+/// daylight-saving rules; a time that occurs twice or not at all is resolved by `policy`.
+/// The instant is then shown in `to` with the offset that zone observes at that moment.
+/// This function requires the `tzdb` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{convert, Disambiguation, Tz};
+///
+/// let new_york = Tz::from_name("America/New_York").unwrap();
+/// let tokyo = Tz::from_name("Asia/Tokyo").unwrap();
+///
+/// // A 15:11 meeting in New York is at 04:11 the next day in Tokyo
+/// let meeting = convert(2024, 10, 14, 15, 11, &new_york, &tokyo, Disambiguation::Reject);
+/// assert_eq!(meeting.unwrap().to_string(), "2024-10-15T04:11:00+09:00");
+///
+/// // 01:30 happens twice when New York falls back on 2024-11-03
+/// let earliest = convert(2024, 11, 3, 1, 30, &new_york, &Tz::UTC, Disambiguation::Earliest);
+/// let latest = convert(2024, 11, 3, 1, 30, &new_york, &Tz::UTC, Disambiguation::Latest);
+/// assert_eq!(earliest.unwrap().to_string(), "2024-11-03T05:30:00Z");
+/// assert_eq!(latest.unwrap().to_string(), "2024-11-03T06:30:00Z");
+///
+/// // 02:30 never happens when New York springs forward on 2024-03-10
+/// let skipped = convert(2024, 3, 10, 2, 30, &new_york, &Tz::UTC, Disambiguation::Latest);
+/// assert_eq!(skipped.unwrap().to_string(), "2024-03-10T07:30:00Z");
+/// assert!(convert(2024, 3, 10, 2, 30, &new_york, &Tz::UTC, Disambiguation::Reject).is_err());
+/// ```
+///
+/// ### Returns
+///
+/// Returns a [`DateTime`] carrying the offset of `to` at the converted instant.
+///
+/// ### Errors
+///
+/// Returns [`WTimeError::OutOfRange`] naming the first invalid date or time component,
+/// `"local time"` if the time is ambiguous or nonexistent in `from` and `policy` is
+/// [`Disambiguation::Reject`], or `"timestamp"` if the instant is before the UNIX epoch.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tzdb")]
+#[allow(clippy::too_many_arguments)]
+pub fn convert(
+    year: u64,
+    month: u64,
+    day: u64,
+    hour: u64,
+    minute: u64,
+    from: &Tz,
+    to: &Tz,
+    policy: Disambiguation,
+) -> Result<DateTime, WTimeError> {
+    let year = i64::try_from(year).map_err(|_| WTimeError::OutOfRange("year"))?;
+    let wall = calc_timestamp_signed(year, month, day, hour, minute, 0)?;
+    let offset_at_secs = |ts: i64| from.offset_minutes_at(ts.max(0) as u64) * 60;
+
+    // Transitions are far more than a day apart, so the offsets a day either side are the
+    // only ones that can apply to `wall`
+    let before = offset_at_secs(wall.saturating_sub(86_400));
+    let after = offset_at_secs(wall.saturating_add(86_400));
+    let matches = |offset: i64| {
+        let ts = wall - offset;
+        (offset_at_secs(ts) == offset).then_some(ts)
+    };
+    let (earliest, latest) = match (matches(before), matches(after)) {
+        (Some(first), Some(second)) => (first.min(second), first.max(second)),
+        (Some(ts), None) | (None, Some(ts)) => (ts, ts),
+        // Skipped: the offset after the gap gives the earlier instant
+        (None, None) => (wall - after, wall - before),
+    };
+    let ts = match policy {
+        _ if earliest == latest => earliest,
+        Disambiguation::Earliest => earliest,
+        Disambiguation::Latest => latest,
+        Disambiguation::Reject => return Err(WTimeError::OutOfRange("local time")),
+    };
+    let ts = u64::try_from(ts).map_err(|_| WTimeError::OutOfRange("timestamp"))?;
+    Ok(to_zone(ts, to))
+}
+
+/// ### world_clock(zones: &[Tz]) -> Vec<(Tz, DateTime)>
+///
+/// Retrieves the current time in several named time zones at once.
+///
+/// The clock is read a single time, so every entry shows the same instant, each in its own
+/// zone's wall-clock time with the offset it observes right now. This is the building block
+/// of dashboards and meeting planners. This function requires the `tzdb` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::tz::{world_clock, Tz};
+///
+/// let zones = [
+///     Tz::from_name("America/New_York").unwrap(),
+///     Tz::from_name("Europe/London").unwrap(),
+///     Tz::from_name("Asia/Tokyo").unwrap(),
+/// ];
+/// for (zone, now) in world_clock(&zones) {
+///     println!("{:<20} {}", zone.name(), now);
+/// }
+///
+/// let clock = world_clock(&zones);
+/// assert_eq!(clock.len(), 3);
+/// assert_eq!(clock[0].1.ts, clock[2].1.ts);
+/// ```
+///
+/// ### Returns
+///
+/// Returns one `(zone, time)` pair per zone, in the order of `zones`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tzdb")]
+pub fn world_clock(zones: &[Tz]) -> Vec<(Tz, DateTime)> {
+    world_clock_at(duration_since(), zones)
+}
+
+/// ### world_clock_with_clock<C: Clock>(clock: &C, zones: &[Tz]) -> Vec<(Tz, DateTime)>
+///
+/// Retrieves the time of a [`Clock`] in several named time zones at once, like
+/// [`world_clock`]. This function requires the `tzdb` feature.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::tz::{world_clock_with_clock, Tz};
+///
+/// let clock = FixedClock::from_ts(1728933069); // 2024-10-14 19:11:09 UTC
+/// let zones = [
+///     Tz::from_name("America/Los_Angeles").unwrap(),
+///     Tz::UTC,
+///     Tz::from_name("Asia/Kolkata").unwrap(),
+/// ];
+///
+/// let times: Vec<String> = world_clock_with_clock(&clock, &zones)
+///     .iter()
+///     .map(|(_, time)| time.to_string())
+///     .collect();
+/// assert_eq!(
+///     times,
+///     [
+///         "2024-10-14T12:11:09-07:00",
+///         "2024-10-14T19:11:09Z",
+///         "2024-10-15T00:41:09+05:30",
+///     ]
+/// );
+/// ```
+///
+/// ### Returns
+///
+/// Returns one `(zone, time)` pair per zone, in the order of `zones`.
+///
+/// <small>End Fun Doc</small>
+#[cfg(feature = "tzdb")]
+pub fn world_clock_with_clock<C: Clock>(clock: &C, zones: &[Tz]) -> Vec<(Tz, DateTime)> {
+    world_clock_at(since_epoch(clock), zones)
+}
+
+/// Converts one instant into each of `zones`.
+#[cfg(feature = "tzdb")]
+fn world_clock_at(since: std::time::Duration, zones: &[Tz]) -> Vec<(Tz, DateTime)> {
+    let ts = since.as_secs();
+    let nanos = u64::from(since.subsec_nanos());
+    zones
+        .iter()
+        .map(|zone| (*zone, DateTime::new(ts, nanos, zone.offset_minutes_at(ts))))
+        .collect()
 }
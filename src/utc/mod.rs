@@ -1,5 +1,18 @@
-use super::calc::{calc_date, duration_since, get_millis, get_minute, get_nanos, get_second};
-use std::time::SystemTime;
+use super::{
+    calc::{
+        calc_date, days_from_civil, duration_since, is_same_day, truncate_to, unit_bounds,
+        write_offset_suffix, write_rfc3339, write_wtime_fields, write_wtime_ts, ArrayWriter,
+        Snapshot, Unit, MAX_TS, MAX_YEAR,
+    },
+    clock::{since_epoch, Clock},
+    config::WeekStart,
+};
+use std::fmt::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The length in bytes of the timestamps produced by [`format_utc_ts`] and
+/// `format_local_ts`, for years up to 9999.
+pub const FORMAT_TS_LEN: usize = 30;
 
 /// ### utc_now()
 ///
@@ -29,9 +42,37 @@ use std::time::SystemTime;
 /// The return value is based on the system's current clock and may be affected by system
 /// time changes, such as adjustments from network time protocols.
 ///
+/// On `wasm32-unknown-unknown`, where `SystemTime::now()` panics, enable the `wasm` feature
+/// to read the clock through JavaScript's `Date.now()` instead, with millisecond precision.
+/// The crate's wall-clock functions all read the time through `utc_now`, so they work in
+/// browsers and Node.js; the `Instant`-based `monotonic` helpers are not covered.
+///
+/// With the `testing` feature, tests can stop or move this clock with the functions of the
+/// `testing` module.
+///
 /// <small>End Fun Doc</small>
 pub fn utc_now() -> SystemTime {
-    SystemTime::now()
+    #[cfg(feature = "testing")]
+    {
+        super::testing::adjust(system_now)
+    }
+    #[cfg(not(feature = "testing"))]
+    {
+        system_now()
+    }
+}
+
+/// Reads the system clock, bypassing the overrides of the `testing` module.
+fn system_now() -> SystemTime {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown"))]
+    {
+        // `Date.now()` is a whole number of milliseconds; a clock before 1970 saturates at 0
+        UNIX_EPOCH + std::time::Duration::from_millis(js_sys::Date::now() as u64)
+    }
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32", target_os = "unknown")))]
+    {
+        SystemTime::now()
+    }
 }
 
 /// ### utc_ts_sec()
@@ -65,6 +106,66 @@ pub fn utc_ts_sec() -> u64 {
     duration_since().as_secs()
 }
 
+/// ### utc_ts_sec_with_clock<C: Clock>(clock: &C) -> u64
+///
+/// Reads a [`Clock`] as a UNIX timestamp in seconds.
+///
+/// This is [`utc_ts_sec`] with an injectable clock, for code that must be tested
+/// deterministically.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::utc_ts_sec_with_clock;
+///
+/// assert_eq!(utc_ts_sec_with_clock(&FixedClock::from_ts(1728933069)), 1728933069);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the seconds since the UNIX epoch, or 0 for a clock set before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_sec_with_clock<C: Clock>(clock: &C) -> u64 {
+    since_epoch(clock).as_secs()
+}
+
+/// ### utc_ts_sec_signed() -> i64
+///
+/// Retrieves the current UTC time as a signed UNIX timestamp in seconds.
+///
+/// Unlike [`utc_ts_sec`], a system clock set before 1970 is not an error: it gives a
+/// negative timestamp, rounded down to the whole second before, so that
+/// [`calc_date_signed`](crate::calc::calc_date_signed) returns the right date.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::calc_date_signed;
+/// use wtime::utc::utc_ts_sec_signed;
+///
+/// let now = utc_ts_sec_signed();
+/// assert!(now > 1728933069);
+/// println!("Today: {:?}", calc_date_signed(now));
+/// ```
+///
+/// ### Returns
+///
+/// Returns the seconds since the UNIX epoch as an `i64`, negative before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_sec_signed() -> i64 {
+    match utc_now().duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_secs()).unwrap_or(i64::MAX),
+        Err(before) => {
+            let before = before.duration();
+            let secs = before.as_secs() + u64::from(before.subsec_nanos() > 0);
+            i64::try_from(secs).map_or(i64::MIN, |secs| -secs)
+        }
+    }
+}
+
 /// ### utc_ts_millis()
 ///
 /// Retrieves the current UTC time as a UNIX timestamp.
@@ -96,6 +197,28 @@ pub fn utc_ts_millis() -> u128 {
     duration_since().as_millis()
 }
 
+/// ### utc_ts_millis_with_clock<C: Clock>(clock: &C) -> u128
+///
+/// Reads a [`Clock`] as a UNIX timestamp in milliseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::utc_ts_millis_with_clock;
+///
+/// assert_eq!(utc_ts_millis_with_clock(&FixedClock::from_ts(1728933069)), 1728933069000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the milliseconds since the UNIX epoch, or 0 for a clock set before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_millis_with_clock<C: Clock>(clock: &C) -> u128 {
+    since_epoch(clock).as_millis()
+}
+
 /// ### utc_ts_nanos()
 ///
 /// Retrieves the current UTC time as a UNIX timestamp.
@@ -127,6 +250,82 @@ pub fn utc_ts_nanos() -> u128 {
     duration_since().as_nanos()
 }
 
+/// ### utc_ts_nanos_with_clock<C: Clock>(clock: &C) -> u128
+///
+/// Reads a [`Clock`] as a UNIX timestamp in nanoseconds.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::utc_ts_nanos_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+/// assert_eq!(utc_ts_nanos_with_clock(&clock), 1728933069_000_000_000);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the nanoseconds since the UNIX epoch, or 0 for a clock set before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_nanos_with_clock<C: Clock>(clock: &C) -> u128 {
+    since_epoch(clock).as_nanos()
+}
+
+/// ### utc_ts_f64() -> f64
+///
+/// Retrieves the current UTC time as a UNIX timestamp in seconds with a fractional part.
+///
+/// This is the representation of Python's `time.time()`, Prometheus and many JSON APIs. An
+/// `f64` holds current timestamps to within about a quarter of a microsecond; use
+/// [`utc_ts_nanos`] where exact nanoseconds matter. `DateTime::from_ts_f64` converts such a
+/// value back.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{utc_ts_f64, utc_ts_sec};
+///
+/// let now = utc_ts_f64();
+/// assert!(now >= 1728933069.0);
+/// assert!(now - (utc_ts_sec() as f64) < 1.0);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the seconds since the UNIX epoch as an `f64`.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_f64() -> f64 {
+    duration_since().as_secs_f64()
+}
+
+/// ### utc_ts_f64_with_clock<C: Clock>(clock: &C) -> f64
+///
+/// Reads a [`Clock`] as a UNIX timestamp in seconds with a fractional part.
+///
+/// ### Example
+///
+/// ```
+/// use std::time::Duration;
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::utc_ts_f64_with_clock;
+///
+/// let mut clock = FixedClock::from_ts(1728933069);
+/// clock.advance(Duration::from_millis(250));
+/// assert_eq!(utc_ts_f64_with_clock(&clock), 1728933069.25);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the seconds since the UNIX epoch, or 0.0 for a clock set before 1970.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_ts_f64_with_clock<C: Clock>(clock: &C) -> f64 {
+    since_epoch(clock).as_secs_f64()
+}
+
 /// ### get_year() -> u64
 ///
 /// Retrieves the current year.
@@ -228,6 +427,28 @@ pub fn get_hour() -> u64 {
     hour
 }
 
+/// ### is_today_utc(ts: u64) -> bool
+///
+/// Checks whether a UNIX timestamp in seconds falls on the current UTC day.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{is_today_utc, utc_ts_sec};
+///
+/// assert!(is_today_utc(utc_ts_sec()));
+/// assert!(!is_today_utc(utc_ts_sec() - 86_400));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if `ts` shares today's UTC date.
+///
+/// <small>End Fun Doc</small>
+pub fn is_today_utc(ts: u64) -> bool {
+    is_same_day(ts, utc_ts_sec())
+}
+
 /// ### format_utc_ts()
 ///
 /// Retrieves the current UTC timestamp formatted as a string.
@@ -242,7 +463,8 @@ pub fn get_hour() -> u64 {
 /// - Minute: 2 digits, zero-padded (00-59)
 /// - Second: 2 digits, zero-padded (00-59)
 /// - Milliseconds: 3 digits, zero-padded (000-999)
-/// - Nanoseconds: 6 digits, zero-padded (000000-999999)
+/// - Nanoseconds: 6 digits, zero-padded (000000-999999), the nanoseconds within the
+///   millisecond, so that the last two fields together are the 9-digit fraction of the second
 ///
 /// This function does not perform error handling or validation beyond the necessary
 /// calculations for the timestamp components. It is assumed that the underlying
@@ -255,6 +477,9 @@ pub fn get_hour() -> u64 {
 /// within your application's context, either by combining it with a unique suffix or
 /// using it by itself.
 ///
+/// Two calls within the same microsecond return the same timestamp. Where IDs must be
+/// unique, use `next_id` from the `id` module, behind the `id` feature, instead.
+///
 /// Here's an example using a unique suffix:
 /// ```rust
 /// use wtime::utc::format_utc_ts;
@@ -315,18 +540,684 @@ pub fn get_hour() -> u64 {
 ///
 /// <small>End Fun Doc</small>
 pub fn format_utc_ts() -> String {
-    let year = get_year();
-    let month = get_month();
-    let day = get_day();
-    let hour = get_hour();
-    let minute = get_minute();
-    let second = get_second();
-    let millis = get_millis();
-    let nanos = get_nanos();
-
-    // Create the formatted string with updated formatting
-    format!(
-        "{:04}-{:02}-{:02}-{:02}-{:02}-{:02}-{:03}-{:06}",
-        year, month, day, hour, minute, second, millis, nanos,
-    )
+    Snapshot::now().to_string()
+}
+
+/// ### format_utc_ts_with_clock<C: Clock>(clock: &C) -> String
+///
+/// Formats the time of a [`Clock`] in the layout of [`format_utc_ts`].
+///
+/// ### Example
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::format_utc_ts_with_clock;
+///
+/// let clock = FixedClock::new(UNIX_EPOCH + Duration::new(1728933069, 123_456_789));
+/// assert_eq!(format_utc_ts_with_clock(&clock), "2024-10-14-19-11-09-123-456789");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of [`FORMAT_TS_LEN`] characters for years up to 9999.
+///
+/// <small>End Fun Doc</small>
+pub fn format_utc_ts_with_clock<C: Clock>(clock: &C) -> String {
+    let mut timestamp = String::with_capacity(FORMAT_TS_LEN);
+    // Writing into a `String` never fails
+    let _ = write_wtime_ts(&mut timestamp, since_epoch(clock), 0);
+    timestamp
+}
+
+/// ### UtcSnapshot
+///
+/// The current UTC time with all of its components read from the clock at once.
+///
+/// Separate calls such as `get_hour()` and `get_minute()` each read the clock, and can
+/// straddle a boundary. A `UtcSnapshot` reads it once; every accessor of [`Snapshot`], from
+/// the year down to the nanoseconds, the weekday and the ISO week, is available on it
+/// through `Deref`.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::calc::Weekday;
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::UtcSnapshot;
+///
+/// let now = UtcSnapshot::now();
+/// println!("{}-{:02}-{:02} {:02}:{:02}", now.year(), now.month(), now.day(), now.hour(), now.minute());
+///
+/// let snapshot = UtcSnapshot::with_clock(&FixedClock::from_ts(1728933069));
+/// assert_eq!(snapshot.date(), (2024, 10, 14));
+/// assert_eq!(snapshot.time(), (19, 11, 9));
+/// assert_eq!(snapshot.weekday(), Weekday::Monday);
+/// assert_eq!(snapshot.week(), 42);
+/// assert_eq!(snapshot.to_string(), "2024-10-14-19-11-09-000-000000");
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UtcSnapshot(Snapshot);
+
+impl UtcSnapshot {
+    /// Reads the system clock once.
+    pub fn now() -> Self {
+        UtcSnapshot(Snapshot::now())
+    }
+
+    /// Reads `clock` once.
+    pub fn with_clock<C: Clock>(clock: &C) -> Self {
+        UtcSnapshot(Snapshot::from_duration(since_epoch(clock), 0))
+    }
+}
+
+impl core::ops::Deref for UtcSnapshot {
+    type Target = Snapshot;
+
+    fn deref(&self) -> &Snapshot {
+        &self.0
+    }
+}
+
+impl fmt::Display for UtcSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// ### Precision
+///
+/// The sub-second digits written by [`format_utc_ts_opts`].
+///
+/// <small>End Enum Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// Whole seconds, with no fraction.
+    Seconds,
+    /// A three-digit millisecond group.
+    Millis,
+    /// A millisecond group followed by a three-digit group of the microseconds within the
+    /// millisecond.
+    Micros,
+    /// A millisecond group followed by a six-digit group of the nanoseconds within the
+    /// millisecond, as in [`format_utc_ts`]. The two groups together spell out the full
+    /// nine-digit nanosecond fraction.
+    Nanos,
+}
+
+/// ### FormatOptions
+///
+/// The precision, separators and offset suffix of [`format_utc_ts_opts`] and
+/// `format_local_ts_opts`.
+///
+/// `separator` goes between every pair of fields except the day and the hour, which are
+/// separated by `date_time_separator`. With `offset_suffix`, the timestamp ends with its
+/// UTC offset, `Z` for UTC and `+05:30` or `-07:00` otherwise, so that it is unambiguous.
+/// The default reproduces the layout of [`format_utc_ts`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{FormatOptions, Precision};
+///
+/// let options = FormatOptions {
+///     precision: Precision::Millis,
+///     date_time_separator: 'T',
+///     ..FormatOptions::DEFAULT
+/// };
+/// assert_eq!(options.separator, '-');
+/// assert!(!options.offset_suffix);
+/// ```
+///
+/// <small>End Struct Doc</small>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The sub-second digits to write.
+    pub precision: Precision,
+    /// The separator between fields.
+    pub separator: char,
+    /// The separator between the day and the hour.
+    pub date_time_separator: char,
+    /// Whether to end with the UTC offset.
+    pub offset_suffix: bool,
+}
+
+impl FormatOptions {
+    /// The layout of [`format_utc_ts`]: nanosecond precision, `-` everywhere and no offset.
+    pub const DEFAULT: FormatOptions = FormatOptions {
+        precision: Precision::Nanos,
+        separator: '-',
+        date_time_separator: '-',
+        offset_suffix: false,
+    };
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions::DEFAULT
+    }
+}
+
+/// ### format_utc_ts_opts(options: FormatOptions) -> String
+///
+/// Retrieves the current UTC timestamp with a chosen precision and separators.
+///
+/// [`format_utc_ts`] always writes eight fields joined by `-`. This variant drops or splits
+/// the sub-second fields according to [`Precision`] and uses the separators of
+/// [`FormatOptions`], so a log line can carry `2024-10-14T19-11-09-123` instead of the full
+/// nanosecond layout. The clock is read once.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::utc::{format_utc_ts_opts, FormatOptions, Precision};
+///
+/// let timestamp = format_utc_ts_opts(FormatOptions {
+///     precision: Precision::Millis,
+///     separator: '-',
+///     date_time_separator: 'T',
+///     offset_suffix: false,
+/// });
+/// assert_eq!(timestamp.len(), 23);
+/// assert_eq!(&timestamp[10..11], "T");
+///
+/// let seconds = format_utc_ts_opts(FormatOptions {
+///     precision: Precision::Seconds,
+///     separator: '.',
+///     date_time_separator: '_',
+///     offset_suffix: true,
+/// });
+/// assert_eq!(seconds.len(), 20);
+/// assert_eq!(seconds.matches('.').count(), 4);
+/// assert!(seconds.ends_with('Z'));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` of 19, 23, 27 or [`FORMAT_TS_LEN`] characters for years up to 9999
+/// and single-byte separators, plus one for the `Z` suffix.
+///
+/// <small>End Fun Doc</small>
+pub fn format_utc_ts_opts(options: FormatOptions) -> String {
+    let mut timestamp = String::with_capacity(FORMAT_TS_LEN);
+    // Writing into a `String` never fails
+    let _ = write_wtime_ts_opts(&mut timestamp, duration_since(), 0, options);
+    timestamp
+}
+
+/// ### format_utc_ts_into<W: Write>(out: &mut W) -> fmt::Result
+///
+/// Writes the current UTC timestamp into a caller-supplied buffer.
+///
+/// This is the allocation-free form of [`format_utc_ts`] for hot logging paths. It produces
+/// the same `year-month-day-hour-minute-second-millis-nanos` layout, but reads the clock
+/// once, so all components always belong to the same instant.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::utc::{format_utc_ts_into, FORMAT_TS_LEN};
+///
+/// let mut line = String::with_capacity(64);
+/// format_utc_ts_into(&mut line).unwrap();
+/// line.push_str(" INFO service started");
+///
+/// assert_eq!(line.len(), FORMAT_TS_LEN + " INFO service started".len());
+/// println!("{}", line);
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn format_utc_ts_into<W: Write>(out: &mut W) -> fmt::Result {
+    write_wtime_ts(out, duration_since(), 0)
+}
+
+/// ### format_utc_ts_bytes() -> [u8; FORMAT_TS_LEN]
+///
+/// Retrieves the current UTC timestamp as a fixed-size byte array.
+///
+/// The layout is the same as [`format_utc_ts`], and since its length is fixed the result
+/// lives on the stack without any heap allocation. The bytes are always ASCII.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::utc::format_utc_ts_bytes;
+///
+/// let bytes = format_utc_ts_bytes();
+/// let timestamp = std::str::from_utf8(&bytes).unwrap();
+/// println!("Formatted UTC Timestamp: {}", timestamp);
+/// assert_eq!(&timestamp[4..5], "-");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `[u8; FORMAT_TS_LEN]` holding the ASCII timestamp.
+///
+/// <small>End Fun Doc</small>
+pub fn format_utc_ts_bytes() -> [u8; FORMAT_TS_LEN] {
+    let mut buf = [b'0'; FORMAT_TS_LEN];
+    // Only a year past 9999 could overflow the buffer
+    let _ = write_wtime_ts(&mut ArrayWriter::new(&mut buf), duration_since(), 0);
+    buf
+}
+
+/// ### utc_rfc3339() -> String
+///
+/// Retrieves the current UTC time as an RFC 3339 / ISO 8601 timestamp with milliseconds.
+///
+/// This is the format most APIs, JSON payloads and log pipelines expect, e.g.
+/// `2024-10-14T19:11:09.123Z`. The time is always shown in UTC with the `Z` suffix.
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::utc::utc_rfc3339;
+///
+/// let timestamp = utc_rfc3339();
+/// println!("RFC 3339 UTC Timestamp: {}", timestamp);
+/// assert_eq!(timestamp.len(), 24);
+/// assert!(timestamp.ends_with('Z'));
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T19:11:09.123Z`.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_rfc3339() -> String {
+    let now = duration_since();
+    let mut timestamp = String::with_capacity(24);
+    // Writing into a `String` never fails
+    let _ = write_rfc3339(
+        &mut timestamp,
+        now.as_secs(),
+        0,
+        now.subsec_nanos() as u64,
+        3,
+    );
+    timestamp
+}
+
+/// ### utc_rfc3339_with_clock<C: Clock>(clock: &C) -> String
+///
+/// Formats the time of a [`Clock`] as an RFC 3339 timestamp with milliseconds, like
+/// [`utc_rfc3339`].
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::clock::FixedClock;
+/// use wtime::utc::utc_rfc3339_with_clock;
+///
+/// let clock = FixedClock::from_ts(1728933069);
+/// assert_eq!(utc_rfc3339_with_clock(&clock), "2024-10-14T19:11:09.000Z");
+/// ```
+///
+/// ### Returns
+///
+/// Returns a `String` such as `2024-10-14T19:11:09.123Z`.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_rfc3339_with_clock<C: Clock>(clock: &C) -> String {
+    let now = since_epoch(clock);
+    let mut timestamp = String::with_capacity(24);
+    // Writing into a `String` never fails
+    let _ = write_rfc3339(
+        &mut timestamp,
+        now.as_secs(),
+        0,
+        now.subsec_nanos() as u64,
+        3,
+    );
+    timestamp
+}
+
+/// ### utc_rfc3339_into<W: Write>(out: &mut W) -> fmt::Result
+///
+/// Writes the current UTC time as an RFC 3339 timestamp with milliseconds into a
+/// caller-supplied buffer.
+///
+/// This is the allocation-free form of [`utc_rfc3339`]. To format a stored timestamp
+/// instead of the current time, use [`write_rfc3339`](crate::calc::write_rfc3339).
+///
+/// ### Example
+///
+/// ```rust
+/// use wtime::utc::utc_rfc3339_into;
+///
+/// let mut line = String::with_capacity(64);
+/// utc_rfc3339_into(&mut line).unwrap();
+/// line.push_str(" INFO service started");
+///
+/// assert_eq!(&line[23..24], "Z");
+/// assert!(line.ends_with(" INFO service started"));
+/// ```
+///
+/// ### Errors
+///
+/// Returns an error only if `out` fails to accept the text.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_rfc3339_into<W: Write>(out: &mut W) -> fmt::Result {
+    let now = duration_since();
+    write_rfc3339(out, now.as_secs(), 0, now.subsec_nanos() as u64, 3)
+}
+
+/// Writes the layout of [`format_utc_ts`] with the precision and separators of `options`,
+/// for a time since the UNIX epoch shifted by `offset_secs`.
+pub(crate) fn write_wtime_ts_opts<W: Write>(
+    out: &mut W,
+    since: Duration,
+    offset_secs: i64,
+    options: FormatOptions,
+) -> fmt::Result {
+    let subsec_digits = match options.precision {
+        Precision::Seconds => 0,
+        Precision::Millis => 3,
+        Precision::Micros => 6,
+        Precision::Nanos => 9,
+    };
+    write_wtime_fields(
+        out,
+        since,
+        offset_secs,
+        subsec_digits,
+        options.separator,
+        options.date_time_separator,
+    )?;
+    if options.offset_suffix {
+        write_offset_suffix(out, offset_secs / 60)?;
+    }
+    Ok(())
+}
+
+/// ### start_of_day(ts: u64) -> u64
+///
+/// Calculates the start of the UTC day containing a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::start_of_day;
+///
+/// assert_eq!(start_of_day(1728933069), 1728864000); // 2024-10-14 00:00:00 UTC
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of midnight UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_day(ts: u64) -> u64 {
+    truncate_to(ts, Unit::Day)
+}
+
+/// ### end_of_day(ts: u64) -> u64
+///
+/// Calculates the last second of the UTC day containing a UNIX timestamp.
+///
+/// Together with [`start_of_day`] it gives the inclusive bounds of a day, as used by SQL
+/// `BETWEEN` queries.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{end_of_day, start_of_day};
+///
+/// let ts = 1728933069;
+/// assert_eq!(end_of_day(ts), 1728950399); // 2024-10-14 23:59:59 UTC
+/// assert_eq!(end_of_day(ts) - start_of_day(ts), 86_399);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of 23:59:59 UTC.
+///
+/// <small>End Fun Doc</small>
+pub fn end_of_day(ts: u64) -> u64 {
+    truncate_to(ts, Unit::Day) + 86_399
+}
+
+/// ### start_of_week(ts: u64, week_start: WeekStart) -> u64
+///
+/// Calculates the start of the UTC week containing a UNIX timestamp, for weeks starting on
+/// `week_start`. Pass `WeekStart::default()` to follow the configured
+/// [`Config::week_start`](crate::config::Config::week_start).
+///
+/// The first days of 1970, whose week started in 1969, give the epoch itself.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::config::WeekStart;
+/// use wtime::utc::start_of_week;
+///
+/// let ts = 1728933069; // Monday 2024-10-14 19:11:09 UTC
+/// assert_eq!(start_of_week(ts, WeekStart::Monday), 1728864000); // 2024-10-14
+/// assert_eq!(start_of_week(ts, WeekStart::Sunday), 1728777600); // 2024-10-13
+/// assert_eq!(start_of_week(ts, WeekStart::Saturday), 1728691200); // 2024-10-12
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of midnight UTC on the first day of the week.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_week(ts: u64, week_start: WeekStart) -> u64 {
+    unit_bounds(ts.min(MAX_TS) as i64, Unit::Week, week_start.into())
+        .0
+        .max(0) as u64
+}
+
+/// ### start_of_month(ts: u64) -> u64
+///
+/// Calculates the start of the UTC month containing a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::start_of_month;
+///
+/// assert_eq!(start_of_month(1728933069), 1727740800); // 2024-10-01 00:00:00 UTC
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of midnight UTC on the first day of the month.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_month(ts: u64) -> u64 {
+    truncate_to(ts, Unit::Month)
+}
+
+/// ### start_of_year(ts: u64) -> u64
+///
+/// Calculates the start of the UTC year containing a UNIX timestamp.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::start_of_year;
+///
+/// assert_eq!(start_of_year(1728933069), 1704067200); // 2024-01-01 00:00:00 UTC
+/// ```
+///
+/// ### Returns
+///
+/// Returns the UNIX timestamp of midnight UTC on January 1.
+///
+/// <small>End Fun Doc</small>
+pub fn start_of_year(ts: u64) -> u64 {
+    truncate_to(ts, Unit::Year)
+}
+
+/// The leap-second table built into the crate: `(ts, offset)` pairs giving the difference
+/// TAI - UTC in seconds in effect from the UNIX timestamp `ts` on, in ascending order.
+///
+/// The first entry is the start of the integer-second UTC in 1972; every later one follows a
+/// leap second inserted at the end of the previous day. The IERS announces leap seconds
+/// about six months ahead in its Bulletin C, and this table ends with the one of
+/// 2016-12-31, the last announced when it was written. Applications that must stay exact
+/// after a future announcement pass their own, longer table to [`utc_tai_offset_in`] and
+/// [`is_leap_second_day_in`], for example one loaded from the IERS `leap-seconds.list`.
+pub const LEAP_SECONDS: [(u64, i64); 28] = [
+    (63072000, 10),   // 1972-01-01
+    (78796800, 11),   // 1972-07-01
+    (94694400, 12),   // 1973-01-01
+    (126230400, 13),  // 1974-01-01
+    (157766400, 14),  // 1975-01-01
+    (189302400, 15),  // 1976-01-01
+    (220924800, 16),  // 1977-01-01
+    (252460800, 17),  // 1978-01-01
+    (283996800, 18),  // 1979-01-01
+    (315532800, 19),  // 1980-01-01
+    (362793600, 20),  // 1981-07-01
+    (394329600, 21),  // 1982-07-01
+    (425865600, 22),  // 1983-07-01
+    (489024000, 23),  // 1985-07-01
+    (567993600, 24),  // 1988-01-01
+    (631152000, 25),  // 1990-01-01
+    (662688000, 26),  // 1991-01-01
+    (709948800, 27),  // 1992-07-01
+    (741484800, 28),  // 1993-07-01
+    (773020800, 29),  // 1994-07-01
+    (820454400, 30),  // 1996-01-01
+    (867715200, 31),  // 1997-07-01
+    (915148800, 32),  // 1999-01-01
+    (1136073600, 33), // 2006-01-01
+    (1230768000, 34), // 2009-01-01
+    (1341100800, 35), // 2012-07-01
+    (1435708800, 36), // 2015-07-01
+    (1483228800, 37), // 2017-01-01
+];
+
+/// ### utc_tai_offset(ts: u64) -> i64
+///
+/// Looks up TAI - UTC, the number of seconds International Atomic Time is ahead of UTC, at a
+/// UNIX timestamp, from the built-in [`LEAP_SECONDS`] table.
+///
+/// UNIX time ignores leap seconds: every day has 86,400 seconds, and the inserted second
+/// repeats or stretches the last second of the day. Adding this offset gives the TAI
+/// reading, which counts every SI second. The lookup is best-effort. It is exact between
+/// 1972 and the last entry of the table, assumes no leap second after it, and returns the
+/// 1972 offset of 10 seconds for earlier timestamps, when the difference was fractional.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::utc_tai_offset;
+///
+/// assert_eq!(utc_tai_offset(1728933069), 37); // 2024-10-14
+/// assert_eq!(utc_tai_offset(1483228799), 36); // 2016-12-31 23:59:59
+/// assert_eq!(utc_tai_offset(1483228800), 37); // 2017-01-01 00:00:00
+/// assert_eq!(utc_tai_offset(0), 10);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the offset in whole seconds.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_tai_offset(ts: u64) -> i64 {
+    utc_tai_offset_in(&LEAP_SECONDS, ts)
+}
+
+/// ### utc_tai_offset_in(table: &[(u64, i64)], ts: u64) -> i64
+///
+/// Looks up TAI - UTC at a UNIX timestamp in a leap-second table laid out like
+/// [`LEAP_SECONDS`], ascending `(ts, offset)` pairs.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{utc_tai_offset_in, LEAP_SECONDS};
+///
+/// // A hypothetical leap second at the end of 2030-06-30
+/// let mut table = LEAP_SECONDS.to_vec();
+/// table.push((1909094400, 38));
+///
+/// assert_eq!(utc_tai_offset_in(&table, 1909094399), 37);
+/// assert_eq!(utc_tai_offset_in(&table, 1909094400), 38);
+/// ```
+///
+/// ### Returns
+///
+/// Returns the offset of the last entry at or before `ts`, or the offset of the first entry
+/// for earlier timestamps. An empty table gives 0.
+///
+/// <small>End Fun Doc</small>
+pub fn utc_tai_offset_in(table: &[(u64, i64)], ts: u64) -> i64 {
+    let after = table.partition_point(|&(from, _)| from <= ts);
+    table
+        .get(after.saturating_sub(1))
+        .map_or(0, |&(_, offset)| offset)
+}
+
+/// ### is_leap_second_day(date: (u64, u64, u64)) -> bool
+///
+/// Checks whether a UTC date ended with a leap second, according to the built-in
+/// [`LEAP_SECONDS`] table.
+///
+/// On those days the last minute has 61 seconds, and `23:59:60` is a valid UTC time. Like
+/// [`utc_tai_offset`], the answer is only as current as the table.
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::is_leap_second_day;
+///
+/// assert!(is_leap_second_day((2016, 12, 31)));
+/// assert!(is_leap_second_day((1972, 6, 30)));
+/// assert!(!is_leap_second_day((2024, 12, 31)));
+/// assert!(!is_leap_second_day((1971, 12, 31))); // the 1972 offset change was fractional
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if a leap second was inserted, or removed, at the end of the day.
+///
+/// <small>End Fun Doc</small>
+pub fn is_leap_second_day(date: (u64, u64, u64)) -> bool {
+    is_leap_second_day_in(&LEAP_SECONDS, date)
+}
+
+/// ### is_leap_second_day_in(table: &[(u64, i64)], date: (u64, u64, u64)) -> bool
+///
+/// Checks whether a UTC date ended with a leap second in a table laid out like
+/// [`LEAP_SECONDS`].
+///
+/// ### Example
+///
+/// ```
+/// use wtime::utc::{is_leap_second_day_in, LEAP_SECONDS};
+///
+/// let mut table = LEAP_SECONDS.to_vec();
+/// table.push((1909094400, 38)); // hypothetical, after 2030-06-30
+///
+/// assert!(is_leap_second_day_in(&table, (2030, 6, 30)));
+/// assert!(!is_leap_second_day_in(&LEAP_SECONDS, (2030, 6, 30)));
+/// assert!(!is_leap_second_day_in(&table, (u64::MAX, 12, 31)));
+/// ```
+///
+/// ### Returns
+///
+/// Returns `true` if the offset changes at the midnight ending the day.
+///
+/// <small>End Fun Doc</small>
+pub fn is_leap_second_day_in(table: &[(u64, i64)], date: (u64, u64, u64)) -> bool {
+    let (year, month, day) = date;
+    // No table reaches that far, and the calendar math would overflow
+    if year > MAX_YEAR {
+        return false;
+    }
+    let next_midnight = (days_from_civil(year as i64, month, day) + 1) * 86_400;
+    table
+        .windows(2)
+        .any(|pair| pair[1].0 as i64 == next_midnight && pair[1].1 != pair[0].1)
 }